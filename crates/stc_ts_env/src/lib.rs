@@ -3,6 +3,7 @@ use std::{str::FromStr, sync::Arc};
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use stc_ts_builtin_types::Lib;
 use stc_ts_errors::{Error, ErrorKind};
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{Id, Type};
@@ -20,11 +21,27 @@ mod marks;
 pub struct BuiltIn {
     vars: FxHashMap<JsWord, Type>,
     types: FxHashMap<JsWord, Type>,
+    /// Libraries this is built from. Empty if unknown.
+    #[serde(skip)]
+    libs: Vec<Lib>,
 }
 
 impl BuiltIn {
     pub fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> Self {
-        BuiltIn { vars, types }
+        BuiltIn {
+            vars,
+            types,
+            libs: Default::default(),
+        }
+    }
+
+    pub fn with_libs(mut self, libs: Vec<Lib>) -> Self {
+        self.libs = libs;
+        self
+    }
+
+    pub fn libs(&self) -> &[Lib] {
+        &self.libs
     }
 }
 
@@ -69,6 +86,22 @@ impl Env {
         self.rule
     }
 
+    pub fn builtin(&self) -> &Arc<BuiltIn> {
+        &self.builtin
+    }
+
+    /// Returns a copy of `self` using `builtin`. Existing clones of `self` are
+    /// not affected, but global declarations are still shared with them.
+    pub fn with_builtin(&self, builtin: Arc<BuiltIn>) -> Self {
+        Self { builtin, ..self.clone() }
+    }
+
+    /// Returns a copy of `self` without global declarations, which shares
+    /// builtin types with `self`.
+    pub fn without_globals(&self) -> Self {
+        Self::new(self.stable.clone(), self.rule, self.target, self.module, self.builtin.clone())
+    }
+
     pub fn declare_global_var(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
@@ -112,6 +145,32 @@ impl Env {
         .into())
     }
 
+    /// Calls `op` with each global variable, including builtin ones, without
+    /// cloning them.
+    pub fn for_each_global_var(&self, mut op: impl FnMut(&JsWord, &Type)) {
+        let vars = self.global_vars.lock();
+        vars.iter().for_each(|(name, ty)| op(name, ty));
+
+        for (name, ty) in &self.builtin.vars {
+            if !vars.contains_key(name) {
+                op(name, ty);
+            }
+        }
+    }
+
+    /// Calls `op` with each global type, including builtin ones, without
+    /// cloning them.
+    pub fn for_each_global_type(&self, mut op: impl FnMut(&JsWord, &Type)) {
+        let types = self.global_types.lock();
+        types.iter().for_each(|(name, ty)| op(name, ty));
+
+        for (name, ty) in &self.builtin.types {
+            if !types.contains_key(name) {
+                op(name, ty);
+            }
+        }
+    }
+
     pub fn get_global_type(&self, span: Span, name: &JsWord) -> Result<Type, Error> {
         let _tracing = dev_span!("get_global_type");
 
@@ -189,6 +248,7 @@ pub struct Rule {
     pub always_strict: bool,
     pub strict_null_checks: bool,
     pub strict_function_types: bool,
+    pub use_unknown_in_catch_variables: bool,
 
     pub allow_unreachable_code: bool,
     pub allow_unused_labels: bool,
@@ -199,7 +259,16 @@ pub struct Rule {
     pub no_strict_generic_checks: bool,
     pub no_unused_locals: bool,
     pub no_unused_parameters: bool,
+    pub exact_optional_property_types: bool,
+    pub no_unchecked_indexed_access: bool,
+    pub no_property_access_from_index_signature: bool,
     pub use_define_property_for_class_fields: bool,
+    /// Check decorators using the legacy semantics instead of ECMAScript
+    /// decorators.
+    pub experimental_decorators: bool,
+    /// Report errors of JavaScript files, which can be enabled per file by
+    /// `// @ts-check`.
+    pub check_js: bool,
 
     pub jsx: JsxMode,
 }
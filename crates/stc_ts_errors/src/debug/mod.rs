@@ -27,7 +27,7 @@ pub fn dump_type_map(map: &FxHashMap<Id, Type>) -> String {
         .join("\n")
 }
 
-struct FakeSourceMap;
+pub(crate) struct FakeSourceMap;
 
 impl SourceMapper for FakeSourceMap {
     fn lookup_char_pos(&self, _pos: swc_common::BytePos) -> swc_common::Loc {
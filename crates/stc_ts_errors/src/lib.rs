@@ -24,9 +24,10 @@ use swc_common::{
 };
 use swc_ecma_ast::{AssignOp, BinaryOp, UpdateOp};
 
-pub use self::result_ext::DebugExt;
+pub use self::{message::print_type, result_ext::DebugExt};
 
 pub mod debug;
+mod message;
 mod result_ext;
 
 /// [ErrorKind] with debug contexts attached.
@@ -78,7 +79,7 @@ impl Error {
 
         let mut err = h.struct_span_err_with_code(
             span,
-            &format!("{:#?}", self),
+            &self.message_chain(),
             DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code()))),
         );
 
@@ -149,6 +150,9 @@ pub enum ErrorKind {
     /// TS2339
     TupleTooShort {
         span: Span,
+        index: usize,
+        /// The union of tuples.
+        ty: Box<Type>,
     },
 
     /// TS2403
@@ -356,6 +360,7 @@ pub enum ErrorKind {
     /// TS2448
     BlockScopedVarUsedBeforeInit {
         span: Span,
+        name: Id,
     },
 
     /// TS2528
@@ -381,6 +386,7 @@ pub enum ErrorKind {
     /// TS2307
     ModuleNotFound {
         span: Span,
+        name: JsWord,
     },
 
     /// TS5061
@@ -1050,6 +1056,7 @@ pub enum ErrorKind {
     /// TS2493
     TupleIndexError {
         span: Span,
+        ty: Box<Type>,
         len: u64,
         index: i64,
     },
@@ -1080,6 +1087,10 @@ pub enum ErrorKind {
     MissingFields {
         span: Span,
         fields: Vec<TypeElement>,
+        /// The type missing `fields`. This is filled by `assign_with_opts`,
+        /// as type elements do not know the type they belong to.
+        left: Option<Box<Type>>,
+        right: Option<Box<Type>>,
     },
 
     /// TS2322
@@ -1104,19 +1115,162 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2578
+    UnusedTsExpectErrorDirective {
+        span: Span,
+    },
+
+    /// TS7030
+    NotAllCodePathsReturnValue {
+        span: Span,
+    },
+
+    /// TS7029
+    FallthroughCaseInSwitch {
+        span: Span,
+    },
+
+    /// TS6133
+    UnusedDecl {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6196
+    UnusedTypeDecl {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6138
+    UnusedProperty {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6192
+    AllImportsUnused {
+        span: Span,
+    },
+
+    /// TS6198
+    AllDestructuredElementsUnused {
+        span: Span,
+    },
+
+    /// TS6199
+    AllVarsUnused {
+        span: Span,
+    },
+
+    /// TS6205
+    AllTypeParamsUnused {
+        span: Span,
+    },
+
+    /// TS4111
+    PropertyAccessFromIndexSignature {
+        span: Span,
+        prop: JsWord,
+    },
+
+    /// TS2375
+    ///
+    /// `undefined` is assigned to an optional property with
+    /// `exactOptionalPropertyTypes`.
+    ExactOptionalPropertyAssignFailed {
+        span: Span,
+        cause: Box<Error>,
+        /// Filled by `assign_with_opts`.
+        left: Option<Box<Type>>,
+        right: Option<Box<Type>>,
+    },
+
+    /// TS2412
+    ///
+    /// `undefined` is written to an optional property with
+    /// `exactOptionalPropertyTypes`.
+    ExactOptionalPropertyWriteFailed {
+        span: Span,
+        left: Box<Type>,
+        right: Box<Type>,
+    },
+
+    /// TS2775
+    AssertionCalleeWithoutTypeAnn {
+        span: Span,
+    },
+
+    /// TS2776
+    AssertionCalleeNotDottedName {
+        span: Span,
+    },
+
+    /// TS1238
+    InvalidClassDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1239
+    InvalidParamDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1240
+    InvalidPropDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1241
+    InvalidMethodDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1206
+    DecoratorsNotValidHere {
+        span: Span,
+    },
+
+    /// TS1xxx, reported by the parser.
+    SyntaxError {
+        span: Span,
+        code: usize,
+        msg: String,
+    },
+
+    /// Used to elaborate the failure of assigning a property.
+    ///
+    /// Has the code of `cause`.
+    PropertyIncompatible {
+        span: Span,
+        key: Box<Key>,
+        cause: Box<Error>,
+    },
+
     ObjectAssignFailed {
         span: Span,
         errors: Vec<Error>,
+        /// Like `MissingFields`, the types are filled by `assign_with_opts`.
+        left: Option<Box<Type>>,
+        right: Option<Box<Type>>,
     },
 
     SimpleAssignFailed {
         span: Span,
         cause: Option<Box<Error>>,
+        left: Option<Box<Type>>,
+        right: Option<Box<Type>>,
     },
 
     SimpleAssignFailedWithCause {
         span: Span,
         cause: Vec<Error>,
+        left: Option<Box<Type>>,
+        right: Option<Box<Type>>,
     },
 
     InvalidAssignmentOfArray {
@@ -1408,6 +1562,7 @@ pub enum ErrorKind {
         span: Span,
         min: usize,
         max: Option<usize>,
+        actual: usize,
     },
 
     ExpectedAtLeastNArgsButGotM {
@@ -2125,6 +2280,36 @@ impl ErrorKind {
 
             ErrorKind::NonStringDynamicImport { .. } => 7036,
 
+            ErrorKind::PropertyIncompatible { cause, .. } => cause.code(),
+
+            ErrorKind::UnusedTsExpectErrorDirective { .. } => 2578,
+
+            ErrorKind::NotAllCodePathsReturnValue { .. } => 7030,
+            ErrorKind::FallthroughCaseInSwitch { .. } => 7029,
+
+            ErrorKind::UnusedDecl { .. } => 6133,
+            ErrorKind::UnusedTypeDecl { .. } => 6196,
+            ErrorKind::UnusedProperty { .. } => 6138,
+            ErrorKind::AllImportsUnused { .. } => 6192,
+            ErrorKind::AllDestructuredElementsUnused { .. } => 6198,
+            ErrorKind::AllVarsUnused { .. } => 6199,
+            ErrorKind::AllTypeParamsUnused { .. } => 6205,
+
+            ErrorKind::PropertyAccessFromIndexSignature { .. } => 4111,
+            ErrorKind::ExactOptionalPropertyAssignFailed { .. } => 2375,
+            ErrorKind::ExactOptionalPropertyWriteFailed { .. } => 2412,
+
+            ErrorKind::AssertionCalleeWithoutTypeAnn { .. } => 2775,
+            ErrorKind::AssertionCalleeNotDottedName { .. } => 2776,
+
+            ErrorKind::InvalidClassDecorator { .. } => 1238,
+            ErrorKind::InvalidParamDecorator { .. } => 1239,
+            ErrorKind::InvalidPropDecorator { .. } => 1240,
+            ErrorKind::InvalidMethodDecorator { .. } => 1241,
+            ErrorKind::DecoratorsNotValidHere { .. } => 1206,
+
+            ErrorKind::SyntaxError { code, .. } => *code,
+
             _ => 0,
         }
     }
@@ -0,0 +1,794 @@
+//! Human-readable messages for diagnostics.
+//!
+//! Messages follow the wording of `tsc` as closely as the information stored in
+//! [ErrorKind] allows.
+
+use std::{fmt::Write, ops::RangeInclusive};
+
+use rnode::RNode;
+use stc_ts_ast_rnode::RTsType;
+use stc_ts_types::{name::Name, Id, Key, Type};
+use stc_utils::cache::ALLOW_DEEP_CLONE;
+use swc_common::{sync::Lrc, SourceMap};
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Node};
+use swc_ecma_utils::DropSpan;
+use swc_ecma_visit::VisitMutWith;
+
+use crate::{debug::FakeSourceMap, Error, ErrorKind};
+
+/// `tsc` stops elaborating after this depth.
+const MAX_ELABORATION_DEPTH: usize = 10;
+
+impl Error {
+    /// The message of this error, followed by the chain of causes.
+    ///
+    /// Each level of the chain is indented by two spaces, like `tsc` does for
+    /// assignability failures.
+    pub fn message_chain(&self) -> String {
+        let mut buf = self.message();
+        write_causes(&mut buf, &self.inner, 1);
+        buf
+    }
+}
+
+fn write_causes(buf: &mut String, parent: &ErrorKind, depth: usize) {
+    if depth > MAX_ELABORATION_DEPTH {
+        return;
+    }
+
+    for cause in parent.causes() {
+        if let ErrorKind::Errors { .. } | ErrorKind::TupleAssignError { .. } = &*cause.inner {
+            write_causes(buf, &cause.inner, depth);
+            continue;
+        }
+
+        let msg = cause.message();
+        // Wrappers like `SimpleAssignFailed` often repeat the message of the error
+        // they wrap.
+        if msg == parent.message() {
+            write_causes(buf, &cause.inner, depth);
+            continue;
+        }
+
+        buf.push('\n');
+        for _ in 0..depth {
+            buf.push_str("  ");
+        }
+        buf.push_str(&msg);
+
+        write_causes(buf, &cause.inner, depth + 1);
+    }
+}
+
+impl ErrorKind {
+    /// Errors used to elaborate this error.
+    fn causes(&self) -> Vec<&Error> {
+        match self {
+            ErrorKind::AssignFailed { cause, .. } | ErrorKind::SimpleAssignFailedWithCause { cause, .. } => cause.iter().collect(),
+
+            ErrorKind::ObjectAssignFailed { errors, .. }
+            | ErrorKind::UnionError { errors, .. }
+            | ErrorKind::TupleAssignError { errors, .. }
+            | ErrorKind::Errors { errors, .. } => errors.iter().collect(),
+
+            ErrorKind::ModuleLoadFailed { errors, .. } => errors.0.iter().collect(),
+
+            ErrorKind::SimpleAssignFailed { cause, .. } => cause.as_deref().into_iter().collect(),
+
+            ErrorKind::VarDeclNotCompatible { cause, .. }
+            | ErrorKind::InvalidInterfaceInheritance { cause, .. }
+            | ErrorKind::InvalidImplOfInterface { cause, .. }
+            | ErrorKind::IncompatibleFnOverload { cause, .. }
+            | ErrorKind::PropertyIncompatible { cause, .. }
+            | ErrorKind::InvalidClassDecorator { cause, .. }
+            | ErrorKind::InvalidParamDecorator { cause, .. }
+            | ErrorKind::InvalidPropDecorator { cause, .. }
+            | ErrorKind::InvalidMethodDecorator { cause, .. }
+            | ErrorKind::ExactOptionalPropertyAssignFailed { cause, .. } => vec![&**cause],
+
+            ErrorKind::IntersectionError { error, .. } => vec![&**error],
+
+            // The message of `WrongArgType` already describes the assignment.
+            ErrorKind::WrongArgType { inner, .. } => match &*inner.inner {
+                ErrorKind::AssignFailed { cause, .. } => cause.iter().collect(),
+                _ => vec![&**inner],
+            },
+
+            _ => vec![],
+        }
+    }
+
+    /// A `tsc`-compatible message, without the chain of causes.
+    pub fn message(&self) -> String {
+        match self {
+            ErrorKind::NonObjectInSpread { .. } => "Spread types may only be created from object types.".into(),
+            ErrorKind::NotExtendableType { .. } => {
+                "An interface can only extend an object type or intersection of object types with statically known members.".into()
+            }
+            ErrorKind::InvalidInterfaceInheritance { .. } => "Interface incorrectly extends its base interface.".into(),
+            ErrorKind::TupleTooShort { index, ty, .. } => format!("Property '{}' does not exist on type '{}'.", index, print_type(ty)),
+            ErrorKind::VarDeclNotCompatible { .. } | ErrorKind::RedeclaredVarWithDifferentType { .. } => {
+                "Subsequent variable declarations must have the same type.".into()
+            }
+            ErrorKind::IntrinsicIsBuiltinOnly { .. } => {
+                "The 'intrinsic' keyword can only be used to declare compiler provided intrinsic types.".into()
+            }
+            ErrorKind::TypeParamsProvidedButCalleeIsNotGeneric { .. } | ErrorKind::AnyTypeUsedAsCalleeWithTypeArgs { .. } => {
+                "Untyped function calls may not accept type arguments.".into()
+            }
+            ErrorKind::OptionalAndNonOptionalMethodPropertyMixed { .. } => "Overload signatures must all be optional or required.".into(),
+            ErrorKind::UpdateArgMustBeVariableOrPropertyAccess { .. } | ErrorKind::ExprInvalidForUpdateArg { .. } => {
+                "The operand of an increment or decrement operator must be a variable or a property access.".into()
+            }
+            ErrorKind::InterfaceNotCompatible { .. } => "Interface cannot simultaneously extend types with incompatible members.".into(),
+            ErrorKind::CannotUseTypeAsIndexIndex { .. } => "Type cannot be used as an index type.".into(),
+            ErrorKind::CannotFunctionReturningNever { .. } => "A function returning 'never' cannot have a reachable end point.".into(),
+            ErrorKind::OnlyOneEnumCanOmitInit { .. } => {
+                "In an enum with multiple declarations, only one declaration can omit an initializer for its first enum element.".into()
+            }
+            ErrorKind::ConstEnumMemberHasInfinityAsInit { .. } => {
+                "'const' enum member initializer was evaluated to a non-finite value.".into()
+            }
+            ErrorKind::ConstEnumMemberHasNaNAsInit { .. } => {
+                "'const' enum member initializer was evaluated to disallowed value 'NaN'.".into()
+            }
+            ErrorKind::UnreachableCode { .. } => "Unreachable code detected.".into(),
+            ErrorKind::VarMayNotBeInitialized { .. } => "Variable is used before being assigned.".into(),
+            ErrorKind::ClassPropNotInitialized { .. } | ErrorKind::ClassPropertyInitRequired { .. } => {
+                "Property has no initializer and is not definitely assigned in the constructor.".into()
+            }
+            ErrorKind::DefinedWithAccessorInSuper { .. } => {
+                "Property is defined as an accessor in the base class, but is overridden here as an instance property.".into()
+            }
+            ErrorKind::OptionInvalidForEs3 { .. } => "Option cannot be specified when option 'target' is 'ES3'.".into(),
+            ErrorKind::ThisUsedBeforeCallingSuper { .. } => {
+                "'super' must be called before accessing 'this' in the constructor of a derived class.".into()
+            }
+            ErrorKind::SuperUsedBeforeCallingSuper { .. } => {
+                "'super' must be called before accessing a property of 'super' in the constructor of a derived class.".into()
+            }
+            ErrorKind::SuperInNestedFunction { .. } => {
+                "Super calls are not permitted outside constructors or in nested functions inside constructors.".into()
+            }
+            ErrorKind::SuperNotCalled { .. } => "Constructors for derived classes must contain a 'super' call.".into(),
+            ErrorKind::CannotAccessAbstractMember { .. } => "Abstract method cannot be accessed via super expression.".into(),
+            ErrorKind::OperatorCannotBeAppliedToTypes { .. } | ErrorKind::TS2365 { .. } => {
+                "Operator cannot be applied to these types.".into()
+            }
+            ErrorKind::AbstractClassMethodShouldBeSequential { .. } => "All declarations of an abstract method must be consecutive.".into(),
+            ErrorKind::ClassMemberNotCompatibleWithStringIndexSignature { .. } => {
+                "Property is not assignable to 'string' index type.".into()
+            }
+            ErrorKind::ClassMemberNotCompatibleWithNumericIndexSignature { .. } => {
+                "Property is not assignable to 'number' index type.".into()
+            }
+            ErrorKind::AssignFailedBecauseTupleLengthDiffers { .. } => "Source and target tuple types have different lengths.".into(),
+            ErrorKind::InvalidUsageOfNewTarget { .. } => "Meta-property 'new.target' is only allowed in the body of a function \
+                                                          declaration, function expression, or constructor."
+                .into(),
+            ErrorKind::ReturnPropertyOfIteratorMustBeMethod { .. } => "The 'return' property of an iterator must be a method.".into(),
+            ErrorKind::NextOfIteratorShouldReturnTypeWithPropertyValue { .. } => {
+                "The type returned by the 'next()' method of an iterator must have a 'value' property.".into()
+            }
+            ErrorKind::CannotAssignToNamespace { .. } => "Cannot assign to a namespace.".into(),
+            ErrorKind::RestArgMustBeVarOrMemberAccess { .. } => {
+                "The target of an object rest assignment must be a variable or a property access.".into()
+            }
+            ErrorKind::InvalidOperandOfIncDecOptionalProp { .. } => {
+                "The operand of an increment or decrement operator may not be an optional property access.".into()
+            }
+            ErrorKind::InvalidRestPatternInOptionalChain { .. } => {
+                "The target of an object rest assignment may not be an optional property access.".into()
+            }
+            ErrorKind::InvalidLhsOfAssignOptionalProp { .. } => {
+                "The left-hand side of an assignment expression may not be an optional property access.".into()
+            }
+            ErrorKind::InvalidRestPatternInForIn { .. } => {
+                "The left-hand side of a 'for...in' statement may not be an optional property access.".into()
+            }
+            ErrorKind::InvalidRestPatternInForOf { .. } => {
+                "The left-hand side of a 'for...of' statement may not be an optional property access.".into()
+            }
+            ErrorKind::BindingPatNotAllowedInRestPatArg { .. } => "A rest element cannot contain a binding pattern.".into(),
+            ErrorKind::DeleteOperandMustBeOptional { .. } => "The operand of a 'delete' operator must be optional.".into(),
+            ErrorKind::SuperCannotUseTypeArgs { .. } => "'super' may not use type arguments.".into(),
+            ErrorKind::TargetLacksConstructSignature { .. } => {
+                "'new' expression, whose target lacks a construct signature, implicitly has an 'any' type.".into()
+            }
+            ErrorKind::BlockScopedVarUsedBeforeInit { name, .. } => {
+                format!("Block-scoped variable '{}' used before its declaration.", id(name))
+            }
+            ErrorKind::DuplicateDefaultExport { .. } => "A module cannot have multiple default exports.".into(),
+            ErrorKind::DuplicateExport { .. } => "Cannot redeclare exported variable.".into(),
+            ErrorKind::DuplicateFnImpl { .. } => "Duplicate function implementation.".into(),
+            ErrorKind::DuplicateConstructor { .. } => "Multiple constructor implementations are not allowed.".into(),
+            ErrorKind::ModuleNotFound { name, .. } => format!("Cannot find module '{}' or its corresponding type declarations.", name),
+            ErrorKind::TooManyAsterisk { .. } => "Pattern can have at most one '*' character.".into(),
+            ErrorKind::DuplicateVar { name, .. } => format!("Cannot redeclare block-scoped variable '{}'.", id(name)),
+            ErrorKind::ClassNameCannotBeObjectWhenTargetingEs5WithModule { .. } => {
+                "Class name cannot be 'Object' when targeting ES5 with module.".into()
+            }
+            ErrorKind::NotArrayType { .. } | ErrorKind::MustBeArray { .. } => "Type is not an array type.".into(),
+            ErrorKind::NotArrayTypeNorStringType { .. } => "Type is not an array type or a string type.".into(),
+            ErrorKind::NotArrayTypeNorStringTypeButDownlevelIterationWouldWork { .. } => {
+                "Type is not an array type or a string type. Use compiler option '--downlevelIteration' to allow iterating of iterators."
+                    .into()
+            }
+            ErrorKind::ForOfStringUsedInEs3 { .. } => {
+                "Using a string in a 'for...of' statement is only supported in ECMAScript 5 and higher.".into()
+            }
+            ErrorKind::LetOrConstIsNotValidIdInLetOrConstVarDecls { .. } => {
+                "'let' is not allowed to be used as a name in 'let' or 'const' declarations.".into()
+            }
+            ErrorKind::InvalidExprOfLhsOfForIn { .. } => {
+                "The left-hand side of a 'for...in' statement must be a variable or a property access.".into()
+            }
+            ErrorKind::InvalidExprOfLhsOfForOf { .. } => {
+                "The left-hand side of a 'for...of' statement must be a variable or a property access.".into()
+            }
+            ErrorKind::WrongTypeForLhsOfForInLoop { .. } => {
+                "The left-hand side of a 'for...in' statement must be of type 'string' or 'any'.".into()
+            }
+            ErrorKind::DestructuringBindingNotAllowedInLhsOfForIn { .. } => {
+                "The left-hand side of a 'for...in' statement cannot be a destructuring pattern.".into()
+            }
+            ErrorKind::TypeAnnOnLhsOfForInLoops { .. } => {
+                "The left-hand side of a 'for...in' statement cannot use a type annotation.".into()
+            }
+            ErrorKind::TypeAnnOnLhsOfForOfLoops { .. } => {
+                "The left-hand side of a 'for...of' statement cannot use a type annotation.".into()
+            }
+            ErrorKind::CannotAccessPrivatePropertyFromOutside { .. } => {
+                "Property is not accessible outside class because it has a private identifier.".into()
+            }
+            ErrorKind::OptionalChainCannotContainPrivateIdentifier { .. } => "An optional chain cannot contain private identifiers.".into(),
+            ErrorKind::CannotDeletePrivateProperty { .. } => "The operand of a 'delete' operator cannot be a private identifier.".into(),
+            ErrorKind::ConstructorIsKeyword { .. } => "'#constructor' is a reserved word.".into(),
+            ErrorKind::PrivateIdUsedAsMethodName { .. } => "A method cannot be named with a private identifier.".into(),
+            ErrorKind::UndefinedOrNullIsNotValidOperand { .. } => "The value 'null' or 'undefined' cannot be used here.".into(),
+            ErrorKind::ThisInConstructorParam { .. } => "'this' cannot be referenced in constructor arguments.".into(),
+            ErrorKind::ThisInStaticPropertyInitializer { .. } => "'this' cannot be referenced in a static property initializer.".into(),
+            ErrorKind::InvalidSuperClass { .. } | ErrorKind::NotConstructorType { .. } => "Type is not a constructor function type.".into(),
+            ErrorKind::WithStmtNotSupported { .. } => {
+                "The 'with' statement is not supported. All symbols in a 'with' block will have type 'any'.".into()
+            }
+            ErrorKind::NamespaceNotFound { name, .. } => format!("Cannot find namespace '{}'.", name_str(name)),
+            ErrorKind::EnumMemberIdCannotBeNumber { .. } => "An enum member cannot have a numeric name.".into(),
+            ErrorKind::InvalidLhsOfAssign { .. } | ErrorKind::CannotAssignToThis { .. } => {
+                "The left-hand side of an assignment expression must be a variable or a property access.".into()
+            }
+            ErrorKind::ImplicitReturnType { .. } => {
+                "Function, which lacks return-type annotation, implicitly has an 'any' return type.".into()
+            }
+            ErrorKind::IncompatibleFnOverload { .. } | ErrorKind::WrongOverloadSignature { .. } => {
+                "This overload signature is not compatible with its implementation signature.".into()
+            }
+            ErrorKind::InitializerDisallowedInAmbientContext { .. } => {
+                "A parameter initializer is only allowed in a function or constructor implementation.".into()
+            }
+            ErrorKind::InvalidClassName { .. } => "Class name cannot be a reserved type name.".into(),
+            ErrorKind::NoSuchVarForShorthand { name, .. } => format!(
+                "No value exists in scope for the shorthand property '{}'. Either declare one or provide an initializer.",
+                id(name)
+            ),
+            ErrorKind::NoMatchingOverload { .. } => "No overload matches this call.".into(),
+            ErrorKind::InvalidInterfaceName { .. } => "Interface name cannot be a reserved type name.".into(),
+            ErrorKind::CannotCallWithNewNonVoidFunction { .. } => "Only a void function can be called with the 'new' keyword.".into(),
+            ErrorKind::DuplicateProperty { .. } | ErrorKind::DuplicateNameWithoutName { .. } => "Duplicate identifier.".into(),
+            ErrorKind::CannotExportNonLocalVar { .. } => "Only local declarations can be exported from a module.".into(),
+            ErrorKind::StaticPropertyCannotBeNamedPrototype { .. } => {
+                "Static property 'prototype' conflicts with built-in property 'Function.prototype'.".into()
+            }
+            ErrorKind::SelfReferentialSuperClass { .. } => "Class is referenced directly or indirectly in its own base expression.".into(),
+            ErrorKind::ExportMixedWithLocal { .. } => {
+                "Individual declarations in merged declaration must be all exported or all local.".into()
+            }
+            ErrorKind::MixedDefaultExports { .. } => "Merged declaration cannot include a default export declaration. Consider adding a \
+                                                      separate 'export default' declaration instead."
+                .into(),
+            ErrorKind::ClassIncorrectlyImplementsInterface { .. } | ErrorKind::InvalidImplOfInterface { .. } => {
+                "Class incorrectly implements interface.".into()
+            }
+            ErrorKind::StackOverflow { .. } => "Maximum call stack size exceeded while checking this node.".into(),
+            ErrorKind::StaticMemberCannotUseTypeParamOfClass { .. } => "Static members cannot reference class type parameters.".into(),
+            ErrorKind::DeclaringTypeParamReferencedByComputedPropName { .. } => {
+                "A computed property name cannot reference a type parameter from its containing type.".into()
+            }
+            ErrorKind::CannotReferenceThisInComputedPropName { .. } => "'this' cannot be referenced in a computed property name.".into(),
+            ErrorKind::CannotReferenceSuperInComputedPropName { .. } => "'super' cannot be referenced in a computed property name.".into(),
+            ErrorKind::ThisRefToModuleOrNamespace { .. } => "'this' cannot be referenced in a module or namespace body.".into(),
+            ErrorKind::SuperInClassWithoutSuper { .. } => "'super' can only be referenced in a derived class.".into(),
+            ErrorKind::SuperCanBeOnlyReferencedInDerivedClass { .. } => {
+                "'super' can only be referenced in members of derived classes or object literal expressions.".into()
+            }
+            ErrorKind::GeneratorCannotHaveVoidAsReturnType { .. } => "A generator cannot have a 'void' type annotation.".into(),
+            ErrorKind::NoSuchVarButThisHasSuchProperty { name, .. } => format!(
+                "Cannot find name '{}'. Did you mean the instance member 'this.{}'?",
+                id(name),
+                id(name)
+            ),
+            ErrorKind::DestructuringAssignInAmbientContext { .. } => {
+                "Destructuring declarations are not allowed in ambient contexts.".into()
+            }
+            ErrorKind::OptionalBindingPatternInImplSignature { .. } => {
+                "A binding pattern parameter cannot be optional in an implementation signature.".into()
+            }
+            ErrorKind::NullishCoalescingMixedWithLogicalWithoutParen { .. } => {
+                "'||' and '??' operations cannot be mixed without parentheses.".into()
+            }
+            ErrorKind::SwitchCaseTestNotCompatible { disc, test, .. } => {
+                format!("Type '{}' is not comparable to type '{}'.", print_type(test), print_type(disc))
+            }
+            ErrorKind::EnumCannotBeLValue { .. } => "Cannot assign to an enum member because it is a read-only property.".into(),
+            ErrorKind::TypeInvalidForUpdateArg { .. } | ErrorKind::InvalidNumericOperand { .. } => {
+                "An arithmetic operand must be of type 'any', 'number', 'bigint' or an enum type.".into()
+            }
+            ErrorKind::PrivatePropertyIsDifferent { .. } => "Types have separate declarations of a private property.".into(),
+            ErrorKind::PrivateMethodIsDifferent { .. } => "Types have separate declarations of a private method.".into(),
+            ErrorKind::CannotCompareWithOp { op, left, right, .. } | ErrorKind::InvalidBinaryOp { op, left, right, .. } => format!(
+                "Operator '{}' cannot be applied to types '{}' and '{}'.",
+                op,
+                print_type(left),
+                print_type(right)
+            ),
+            ErrorKind::NoSuchEnumVariant { name, .. } => format!("Property '{}' does not exist on the enum type.", name),
+            ErrorKind::ObjectIsPossiblyNull { .. } | ErrorKind::TS2531 { .. } => "Object is possibly 'null'.".into(),
+            ErrorKind::ObjectIsPossiblyUndefined { .. } | ErrorKind::MayBeUndefined { .. } => "Object is possibly 'undefined'.".into(),
+            ErrorKind::ObjectIsPossiblyUndefinedWithType { ty, .. } => {
+                format!("Object of type '{}' is possibly 'undefined'.", print_type(ty))
+            }
+            ErrorKind::ObjectIsPossiblyNullOrUndefined { .. } => "Object is possibly 'null' or 'undefined'.".into(),
+            ErrorKind::CannotAssignAbstractConstructorToNonAbstractConstructor { .. } => {
+                "Cannot assign an abstract constructor type to a non-abstract constructor type.".into()
+            }
+            ErrorKind::InvalidUseOfConstEnum { .. } => "'const' enums can only be used in property or index access expressions or the \
+                                                        right hand side of an import declaration or export assignment or type query."
+                .into(),
+            ErrorKind::ComputedMemberInEnumWithStrMember { .. } => {
+                "Computed values are not permitted in an enum with string valued members.".into()
+            }
+            ErrorKind::CannotCreateInstanceOfAbstractClass { .. } => "Cannot create an instance of an abstract class.".into(),
+            ErrorKind::NotSatisfyConstraint { left, right, .. } => {
+                format!(
+                    "Type '{}' does not satisfy the constraint '{}'.",
+                    print_type(right),
+                    print_type(left)
+                )
+            }
+            ErrorKind::WrongArgType { inner, .. } => match &*inner.inner {
+                ErrorKind::AssignFailed { left, right, .. } => format!(
+                    "Argument of type '{}' is not assignable to parameter of type '{}'.",
+                    print_type(right),
+                    print_type(left)
+                ),
+                _ => "Argument is not assignable to the parameter.".into(),
+            },
+            ErrorKind::ImportFailed { orig, .. } => format!("Module has no exported member '{}'.", id(orig)),
+            ErrorKind::NonStringDynamicImport { .. } => "Dynamic import's specifier must be of type 'string'.".into(),
+            ErrorKind::ExportFailed { orig, .. } => format!("Cannot find name '{}' to export.", id(orig)),
+            ErrorKind::ExportAllFailed { .. } => "Failed to re-export all members of the module.".into(),
+            ErrorKind::NoSuchPropertyInThis { .. } => "Property does not exist on type 'this'.".into(),
+            ErrorKind::NoSuchPropertyInClass { class_name, prop, .. } => match class_name {
+                Some(class_name) => format!("Property '{}' does not exist on type '{}'.", key(prop), id(class_name)),
+                None => format!("Property '{}' does not exist on the class.", key(prop)),
+            },
+            ErrorKind::TypeParameterCountMismatch { min, max, actual, .. } => {
+                format!("Expected {} type arguments, but got {}.", count(&(*min..=*max)), actual)
+            }
+            ErrorKind::ParameterCountMismatch { min, max, actual, .. } => {
+                format!("Expected {} arguments, but got {}.", count(&(*min..=*max)), actual)
+            }
+            ErrorKind::NoSuchPropertyInModule { name, .. } => format!("Property '{}' does not exist on the module.", key(name)),
+            ErrorKind::ReturnRequired { .. } => {
+                "A function whose declared type is neither 'undefined', 'void', nor 'any' must return a value.".into()
+            }
+            ErrorKind::ConstructorRequired { .. } | ErrorKind::ConstructorImplMissingOrNotFollowedByDecl { .. } => {
+                "Constructor implementation is missing.".into()
+            }
+            ErrorKind::CannotAssignToNonVariable { .. } => "Cannot assign to this expression because it is not a variable.".into(),
+            ErrorKind::CannotAssignToModule { .. } => "Cannot use namespace as a value.".into(),
+            ErrorKind::CannotAssignToClass { .. } => "Cannot assign to a class.".into(),
+            ErrorKind::CannotAssignToEnum { .. } => "Cannot assign to an enum.".into(),
+            ErrorKind::CannotAssignToFunction { .. } => "Cannot assign to a function.".into(),
+            ErrorKind::AssignedWrapperToPrimitive { .. } => {
+                "A primitive wrapper object is not assignable to a primitive type. Prefer using the primitive type when possible.".into()
+            }
+            ErrorKind::AccessibilityDiffers { .. } => "Property accessibility differs between the types.".into(),
+            ErrorKind::InvalidInitInConstEnum { .. } => "const enum member initializers must be constant expressions.".into(),
+            ErrorKind::InvalidTupleCast { .. } => {
+                "Conversion of type to tuple type may be a mistake because neither type sufficiently overlaps with the other.".into()
+            }
+            ErrorKind::NoOverlap { left, right, .. } => format!(
+                "This comparison appears to be unintentional because the types '{}' and '{}' have no overlap.",
+                print_type(left),
+                print_type(right)
+            ),
+            ErrorKind::CannotAssignToReadonlyProperty { .. } | ErrorKind::ReadOnly { .. } | ErrorKind::InvalidLValue { .. } => {
+                "Cannot assign to this property because it is a read-only property.".into()
+            }
+            ErrorKind::ImplicitAny { .. } => "Variable implicitly has an 'any' type.".into(),
+            ErrorKind::ImplicitAnyBecauseNoIndexSignatureExists { .. } => {
+                "Element implicitly has an 'any' type because the type has no index signature.".into()
+            }
+            ErrorKind::ImplicitAnyBecauseIndexTypeIsWrong { .. } => {
+                "Element implicitly has an 'any' type because expression can't be used to index the type.".into()
+            }
+            ErrorKind::ImplicitAnyBecauseOfSelfRef { .. } => "Variable implicitly has type 'any' because it does not have a type \
+                                                              annotation and is referenced directly or indirectly in its own initializer."
+                .into(),
+            ErrorKind::TupleAssignError { errors, .. } | ErrorKind::Errors { errors, .. } => {
+                errors.iter().map(|err| err.message()).collect::<Vec<_>>().join("\n")
+            }
+            ErrorKind::NoSuchType { name, .. } | ErrorKind::NoSuchVar { name, .. } => format!("Cannot find name '{}'.", id(name)),
+            ErrorKind::UndefinedSymbol { sym, .. } => format!("Cannot find name '{}'.", id(sym)),
+            ErrorKind::TypeNotFound { name, .. } => format!("Cannot find name '{}'.", name_str(name)),
+            ErrorKind::NoSuchTypeButVarExists { name, .. } => format!(
+                "'{}' refers to a value, but is being used as a type here. Did you mean 'typeof {}'?",
+                id(name),
+                id(name)
+            ),
+            ErrorKind::InvalidUseOfArgumentsInEs3OrEs5 { .. } => "The 'arguments' object cannot be referenced in an arrow function in ES3 \
+                                                                  and ES5. Consider using a standard function expression."
+                .into(),
+            ErrorKind::ArgumentsCannotBeUsedInAsyncFnInEs3OrEs5 { .. } => "The 'arguments' object cannot be referenced in an async \
+                                                                           function or method in ES3 and ES5. Consider using a standard \
+                                                                           function or method."
+                .into(),
+            ErrorKind::CannotExtendTypeOnlyItem { name, .. } => {
+                format!("Cannot extend an interface '{}'. Did you mean 'implements'?", id(name))
+            }
+            ErrorKind::TypeUsedAsVar { name, .. } => format!("'{}' only refers to a type, but is being used as a value here.", id(name)),
+            ErrorKind::DuplicateName { name, .. } => format!("Duplicate identifier '{}'.", id(name)),
+            ErrorKind::UselessSeqExpr { .. } => "Left side of comma operator is unused and has no side effects.".into(),
+            ErrorKind::ReferencedInInit { .. } => "Parameter cannot reference itself.".into(),
+            ErrorKind::NotGeneric { .. } => "Type is not generic.".into(),
+            ErrorKind::Unknown { .. } => "Object is of type 'unknown'.".into(),
+            ErrorKind::NoSuchPropertyWhileDeclWithBindingPat { .. } | ErrorKind::NoInitAndNoDefault { .. } => {
+                "Initializer provides no value for this binding element and the binding element has no default value.".into()
+            }
+            ErrorKind::NoSuchProperty { obj, prop, .. } => match (obj, prop) {
+                (Some(obj), Some(prop)) => format!("Property '{}' does not exist on type '{}'.", key(prop), print_type(obj)),
+                (None, Some(prop)) => format!("Property '{}' does not exist.", key(prop)),
+                (Some(obj), None) => format!("Property does not exist on type '{}'.", print_type(obj)),
+                (None, None) => "Property does not exist.".into(),
+            },
+            ErrorKind::TooManyTupleElements { .. } => "Tuple type has too many elements.".into(),
+            ErrorKind::NotTuple { .. } => "Type is not a tuple type.".into(),
+            ErrorKind::NotVariable { .. } => match self.code() {
+                2628 => "Cannot assign to an enum.".into(),
+                2629 => "Cannot assign to a class.".into(),
+                2630 => "Cannot assign to a function.".into(),
+                2631 => "Cannot assign to a namespace.".into(),
+                2632 => "Cannot assign to an import.".into(),
+                _ => "Cannot assign to this expression because it is not a variable.".into(),
+            },
+            ErrorKind::TS2378 { .. } => "A 'get' accessor must return a value.".into(),
+            ErrorKind::ConstEnumNonIndexAccess { .. } => "A const enum member can only be accessed using a string literal.".into(),
+            ErrorKind::TupleIndexError { ty, len, index, .. } => format!(
+                "Tuple type '{}' of length '{}' has no element at index '{}'.",
+                print_type(ty),
+                len,
+                index
+            ),
+            ErrorKind::NegativeTupleIndex { .. } => "A tuple type cannot be indexed with a negative value.".into(),
+            ErrorKind::Unimplemented { msg, .. } => format!("Not implemented yet: {}", msg),
+            ErrorKind::ResolvedFailed { src, .. } => format!("Cannot find module '{}' or its corresponding type declarations.", src),
+            ErrorKind::MissingFields { fields, left, right, .. } => {
+                let keys = fields.iter().filter_map(|f| f.key()).map(key).collect::<Vec<_>>();
+                match (&*keys, left, right) {
+                    ([], ..) => "Type is missing required members.".into(),
+                    ([k], Some(left), Some(right)) => format!(
+                        "Property '{}' is missing in type '{}' but required in type '{}'.",
+                        k,
+                        print_type(right),
+                        print_type(left)
+                    ),
+                    ([k], ..) => format!("Property '{}' is missing.", k),
+                    (_, Some(left), Some(right)) => format!(
+                        "Type '{}' is missing the following properties from type '{}': {}",
+                        print_type(right),
+                        print_type(left),
+                        missing_keys(&keys)
+                    ),
+                    _ => format!("Type is missing the following properties: {}", missing_keys(&keys)),
+                }
+            }
+            ErrorKind::AssignFailed { left, right, .. } => {
+                format!("Type '{}' is not assignable to type '{}'.", print_type(right), print_type(left))
+            }
+            ErrorKind::AssignFailedDueToAccessibility { .. } => "Property is private in one type but not in the other.".into(),
+            ErrorKind::AssignFailedDueToOptionalityDifference { .. } => {
+                "Property is optional in the source type but required in the target type.".into()
+            }
+            ErrorKind::ObjectAssignFailed { left, right, .. }
+            | ErrorKind::SimpleAssignFailed { left, right, .. }
+            | ErrorKind::SimpleAssignFailedWithCause { left, right, .. } => match (left, right) {
+                (Some(left), Some(right)) => format!("Type '{}' is not assignable to type '{}'.", print_type(right), print_type(left)),
+                _ => "Type is not assignable to the target type.".into(),
+            },
+            ErrorKind::InvalidAssignmentOfArray { .. } => "Array type is not assignable to the target type.".into(),
+            ErrorKind::UnionError { .. } => "Type is not assignable to any member of the union type.".into(),
+            ErrorKind::IntersectionError { .. } => "Type is not assignable to the intersection type.".into(),
+            ErrorKind::ModuleLoadFailed { .. } => "Failed to load the module.".into(),
+            ErrorKind::NoSuchExport { items, .. } => format!(
+                "Module has no exported member {}.",
+                items.iter().map(|i| format!("'{}'", id(i))).collect::<Vec<_>>().join(", ")
+            ),
+            ErrorKind::NoNewSignature { callee, .. } => format!(
+                "This expression is not constructable. Type '{}' has no construct signatures.",
+                print_type(callee)
+            ),
+            ErrorKind::NoConstructablePropertyWithName { obj, .. } => {
+                format!(
+                    "Value of type '{}' is not callable. Did you mean to include 'new'?",
+                    print_type(obj)
+                )
+            }
+            ErrorKind::NoCallSignature { callee, .. } => format!(
+                "This expression is not callable. Type '{}' has no call signatures.",
+                print_type(callee)
+            ),
+            ErrorKind::NoCallablePropertyWithName { key: k, .. } => {
+                format!("This expression is not callable. Property '{}' has no call signatures.", key(k))
+            }
+            ErrorKind::WrongTypeParams { expected, actual, .. } => {
+                format!("Expected {} type arguments, but got {}.", count(expected), actual)
+            }
+            ErrorKind::WrongParams { expected, actual, .. } => format!("Expected {} arguments, but got {}.", count(expected), actual),
+            ErrorKind::InvalidEnumInit { .. } => "Enum member initializer must be a constant expression.".into(),
+            ErrorKind::TS1016 { .. } => "A required parameter cannot follow an optional parameter.".into(),
+            ErrorKind::TS1063 { .. } => "An export assignment cannot be used in a namespace.".into(),
+            ErrorKind::TS1094 { .. } => "An accessor cannot have type parameters.".into(),
+            ErrorKind::TS1095 { .. } => "A 'set' accessor cannot have a return type annotation.".into(),
+            ErrorKind::TS1168 { .. } => "A computed property name in a method overload must refer to an expression whose type is a \
+                                         literal type or a 'unique symbol' type."
+                .into(),
+            ErrorKind::TS1169 { .. } => "A computed property name in an interface must refer to an expression whose type is a literal \
+                                         type or a 'unique symbol' type."
+                .into(),
+            ErrorKind::TS1183 { .. } => "An implementation cannot be declared in ambient contexts.".into(),
+            ErrorKind::TS1318 { .. } => "An abstract accessor cannot have an implementation.".into(),
+            ErrorKind::TS1319 { .. } => "A default export can only be used in an ECMAScript-style module.".into(),
+            ErrorKind::ExportEqualsMixedWithOtherExports { .. } => {
+                "An export assignment cannot be used in a module with other exported elements.".into()
+            }
+            ErrorKind::InvalidLhsOfInOperator { .. } => "The left-hand side of an 'in' expression must be a private identifier or of type \
+                                                         'any', 'string', 'number', or 'symbol'."
+                .into(),
+            ErrorKind::InvalidRhsForInOperator { ty, .. } => format!(
+                "Type '{}' may represent a primitive value, which is not permitted as the right operand of the 'in' operator.",
+                print_type(ty)
+            ),
+            ErrorKind::WrongTypeForLhsOfNumericOperation { .. } => {
+                "The left-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.".into()
+            }
+            ErrorKind::WrongTypeForRhsOfNumericOperation { .. } => {
+                "The right-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.".into()
+            }
+            ErrorKind::TS2370 { .. } => "A rest parameter must be of an array type.".into(),
+            ErrorKind::TS1166 { .. } => {
+                "A computed property name in a class property declaration must have a simple literal type or a 'unique symbol' type.".into()
+            }
+            ErrorKind::TS1345 { .. } => "An expression of type 'void' cannot be tested for truthiness.".into(),
+            ErrorKind::TS2353 { .. } | ErrorKind::UnknownPropertyInObjectLiteralAssignment { .. } => {
+                "Object literal may only specify known properties.".into()
+            }
+            ErrorKind::FnImplMissingOrNotFollowedByDecl { .. } => {
+                "Function implementation is missing or not immediately following the declaration.".into()
+            }
+            ErrorKind::InvalidTypeForComputedProperty { .. } => {
+                "A computed property name must be of type 'string', 'number', 'symbol', or 'any'.".into()
+            }
+            ErrorKind::ParamPropIsNotAllowedInAmbientConstructor { .. } => {
+                "A parameter property is only allowed in a constructor implementation.".into()
+            }
+            ErrorKind::TS2389 { .. } => "Function implementation name must be the same as the name of the overloads.".into(),
+            ErrorKind::TS2447 { .. } => "This operator is not allowed for boolean types.".into(),
+            ErrorKind::ClassDoesNotImplementMember { key: k, .. } => {
+                format!("Non-abstract class does not implement inherited abstract member '{}'.", key(k))
+            }
+            ErrorKind::TS2567 { .. } => "Enum declarations can only merge with namespace or other enum declarations.".into(),
+            ErrorKind::TS2585 { .. } => {
+                "This name only refers to a type, but is being used as a value here. Do you need to change your target library?".into()
+            }
+            ErrorKind::TS2704 { .. } => "The operand of a 'delete' operator cannot be a read-only property.".into(),
+            ErrorKind::InvalidLhsInInstanceOf { .. } => {
+                "The left-hand side of an 'instanceof' expression must be of type 'any', an object type or a type parameter.".into()
+            }
+            ErrorKind::InvalidRhsInInstanceOf { .. } => "The right-hand side of an 'instanceof' expression must be of type 'any' or of a \
+                                                         type assignable to the 'Function' interface type."
+                .into(),
+            ErrorKind::NumericOpToSymbol { .. } => "This operator cannot be applied to type 'symbol'.".into(),
+            ErrorKind::UpdateOpToSymbol { op, .. } => format!("The '{}' operator cannot be applied to type 'symbol'.", op),
+            ErrorKind::NonOverlappingTypeCast { .. } => "Conversion of type may be a mistake because neither type sufficiently overlaps \
+                                                         with the other. If this was intentional, convert the expression to 'unknown' \
+                                                         first."
+                .into(),
+            ErrorKind::InvalidOperatorForLhs { op, .. } => format!("Operator '{}' cannot be applied to the left-hand side.", op),
+            ErrorKind::InvalidOpAssign { op, lhs, rhs, .. } => format!(
+                "Operator '{}' cannot be applied to types '{}' and '{}'.",
+                op,
+                print_type(lhs),
+                print_type(rhs)
+            ),
+            ErrorKind::AssignOpCannotBeApplied { op, .. } => format!("Operator '{}' cannot be applied to these types.", op),
+            ErrorKind::ExpectedNArgsButGotM { min, max, actual, .. } => match max {
+                Some(max) => format!("Expected {} arguments, but got {}.", count(&(*min..=*max)), actual),
+                None => format!("Expected {} arguments, but got {}.", min, actual),
+            },
+            ErrorKind::ExpectedAtLeastNArgsButGotM { min, .. } | ErrorKind::ExpectedAtLeastNArgsButGotMOrMore { min, .. } => {
+                format!("Expected at least {} arguments.", min)
+            }
+            ErrorKind::ExpectedNArgsButGotMOrMore { .. } | ErrorKind::SpreadMustBeTupleOrPassedToRest { .. } => {
+                "A spread argument must either have a tuple type or be passed to a rest parameter.".into()
+            }
+            ErrorKind::InvalidDeleteOperand { .. } => "The operand of a 'delete' operator must be a property reference.".into(),
+            ErrorKind::NoMethodNamedNext { .. } => "An iterator must have a 'next()' method.".into(),
+            ErrorKind::MustHaveSymbolIteratorThatReturnsIteratorOrMustBeArray { .. } => {
+                "Type is not an array type or does not have a '[Symbol.iterator]()' method that returns an iterator.".into()
+            }
+            ErrorKind::MustHaveSymbolIteratorThatReturnsIterator { .. } => {
+                "Type must have a '[Symbol.iterator]()' method that returns an iterator.".into()
+            }
+            ErrorKind::RightHandSideMustBeObject { ty, .. } => format!(
+                "The right-hand side of a 'for...in' statement must be of type 'any', an object type or a type parameter, but here has \
+                 type '{}'.",
+                print_type(ty)
+            ),
+            ErrorKind::MustHaveSymbolAsyncIteratorThatReturnsIterator { .. } => {
+                "Type must have a '[Symbol.asyncIterator]()' method that returns an async iterator.".into()
+            }
+            ErrorKind::NoSuchConstructor { key: k, .. } => format!("Property '{}' is not a constructor.", key(k)),
+            ErrorKind::AbstractAndConcreteIsMixed { .. } => "Overload signatures must all be abstract or non-abstract.".into(),
+            ErrorKind::SuperCanOnlyAccessPublicAndProtectedMethod { .. } => {
+                "Only public and protected methods of the base class are accessible via the 'super' keyword.".into()
+            }
+            ErrorKind::ShouldBeStaticMethod { .. } => "Function overload must be static.".into(),
+            ErrorKind::ShouldBeInstanceMethod { .. } => "Function overload must not be static.".into(),
+            ErrorKind::RestPropertyNotLast { .. } => "A rest element must be last in a destructuring pattern.".into(),
+            ErrorKind::ClassConstructorPrivate { .. } => {
+                "Constructor of class is private and only accessible within the class declaration.".into()
+            }
+            ErrorKind::ClassConstructorProtected { .. } => {
+                "Constructor of class is protected and only accessible within the class declaration.".into()
+            }
+            ErrorKind::InvalidExtendDueToConstructorPrivate { .. } => {
+                "Cannot extend a class. Class constructor is marked as private.".into()
+            }
+            ErrorKind::DuplicatePrivateStaticInstance { .. } => {
+                "Duplicate identifier. Static and instance elements cannot share the same private name.".into()
+            }
+            ErrorKind::ExportAmbientModule { .. } => {
+                "'export' modifier cannot be applied to ambient modules and module augmentations since they are always visible.".into()
+            }
+            ErrorKind::IsTypeUnknown { .. } => "Value is of type 'unknown'.".into(),
+            ErrorKind::RestTypeNotFromObject { .. } => "Rest types may only be created from object types.".into(),
+            ErrorKind::UsePropBeforeInit { prop, .. } => match prop {
+                Some(prop) => format!("Property '{}' is used before its initialization.", key(prop)),
+                None => "Property is used before its initialization.".into(),
+            },
+            ErrorKind::UnusedTsExpectErrorDirective { .. } => "Unused '@ts-expect-error' directive.".into(),
+            ErrorKind::NotAllCodePathsReturnValue { .. } => "Not all code paths return a value.".into(),
+            ErrorKind::FallthroughCaseInSwitch { .. } => "Fallthrough case in switch.".into(),
+            ErrorKind::UnusedDecl { name, .. } => format!("'{}' is declared but its value is never read.", name),
+            ErrorKind::UnusedTypeDecl { name, .. } => format!("'{}' is declared but never used.", name),
+            ErrorKind::UnusedProperty { name, .. } => format!("Property '{}' is declared but its value is never read.", name),
+            ErrorKind::AllImportsUnused { .. } => "All imports in import declaration are unused.".into(),
+            ErrorKind::AllDestructuredElementsUnused { .. } => "All destructured elements are unused.".into(),
+            ErrorKind::AllVarsUnused { .. } => "All variables are unused.".into(),
+            ErrorKind::AllTypeParamsUnused { .. } => "All type parameters are unused.".into(),
+            ErrorKind::PropertyAccessFromIndexSignature { prop, .. } => format!(
+                "Property '{}' comes from an index signature, so it must be accessed with ['{}'].",
+                prop, prop
+            ),
+            ErrorKind::ExactOptionalPropertyAssignFailed { left, right, .. } => match (left, right) {
+                (Some(left), Some(right)) => format!(
+                    "Type '{}' is not assignable to type '{}' with 'exactOptionalPropertyTypes: true'. Consider adding 'undefined' to the \
+                     types of the target's properties.",
+                    print_type(right),
+                    print_type(left)
+                ),
+                _ => "Type is not assignable to the target type with 'exactOptionalPropertyTypes: true'.".into(),
+            },
+            ErrorKind::ExactOptionalPropertyWriteFailed { left, right, .. } => format!(
+                "Type '{}' is not assignable to type '{}' with 'exactOptionalPropertyTypes: true'. Consider adding 'undefined' to the \
+                 type of the target.",
+                print_type(right),
+                print_type(left)
+            ),
+            ErrorKind::AssertionCalleeWithoutTypeAnn { .. } => {
+                "Assertions require every name in the call target to be declared with an explicit type annotation.".into()
+            }
+            ErrorKind::AssertionCalleeNotDottedName { .. } => {
+                "Assertions require the call target to be an identifier or qualified name.".into()
+            }
+            ErrorKind::InvalidClassDecorator { .. } => {
+                "Unable to resolve signature of class decorator when called as an expression.".into()
+            }
+            ErrorKind::InvalidParamDecorator { .. } => {
+                "Unable to resolve signature of parameter decorator when called as an expression.".into()
+            }
+            ErrorKind::InvalidPropDecorator { .. } => {
+                "Unable to resolve signature of property decorator when called as an expression.".into()
+            }
+            ErrorKind::InvalidMethodDecorator { .. } => {
+                "Unable to resolve signature of method decorator when called as an expression.".into()
+            }
+            ErrorKind::DecoratorsNotValidHere { .. } => "Decorators are not valid here.".into(),
+            ErrorKind::SyntaxError { msg, .. } => msg.clone(),
+            ErrorKind::PropertyIncompatible { key: k, .. } => format!("Types of property '{}' are incompatible.", key(k)),
+        }
+    }
+}
+
+/// Prints a type in the form used by messages.
+pub fn print_type(ty: &Type) -> String {
+    if let Type::Namespace(ns) = ty.normalize() {
+        return format!("typeof {}", id(&ns.name));
+    }
+
+    let ty = ALLOW_DEEP_CLONE.set(&(), || ty.clone());
+    let mut ty = RTsType::from(ty).into_orig();
+    ty.visit_mut_with(&mut DropSpan { preserve_ctxt: false });
+
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config {
+                minify: false,
+                ..Default::default()
+            },
+            cm: Lrc::new(FakeSourceMap),
+            comments: None,
+            wr: box JsWriter::new(Lrc::new(SourceMap::default()), "\n", &mut buf, None),
+        };
+
+        if ty.emit_with(&mut emitter).is_err() {
+            return "unknown".into();
+        }
+    }
+
+    String::from_utf8_lossy(&buf).trim().to_string()
+}
+
+fn id(id: &Id) -> &str {
+    id.as_str()
+}
+
+fn name_str(name: &Name) -> String {
+    let (top, rest) = name.inner();
+    let mut s = top.as_str().to_string();
+    for sym in rest {
+        write!(s, ".{}", sym).unwrap();
+    }
+    s
+}
+
+fn key(key: &Key) -> String {
+    match key {
+        Key::Computed(c) => format!("[{}]", print_type(&c.ty)),
+        Key::Normal { sym, .. } => sym.to_string(),
+        Key::Num(n) => n.value.to_string(),
+        Key::BigInt(n) => format!("{}n", n.value),
+        Key::Private(p) => format!("#{}", id(&p.id)),
+    }
+}
+
+/// Like `tsc`, at most 4 keys are listed.
+fn missing_keys(keys: &[String]) -> String {
+    const MAX: usize = 4;
+
+    if keys.len() <= MAX {
+        return keys.join(", ");
+    }
+
+    format!("{}, and {} more.", keys[..MAX].join(", "), keys.len() - MAX)
+}
+
+fn count(range: &RangeInclusive<usize>) -> String {
+    if range.start() == range.end() {
+        range.start().to_string()
+    } else {
+        format!("{}-{}", range.start(), range.end())
+    }
+}
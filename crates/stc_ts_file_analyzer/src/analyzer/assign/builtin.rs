@@ -209,8 +209,13 @@ impl Analyzer<'_, '_> {
                     }
 
                     if done {
-                        return Some(Err(ErrorKind::SimpleAssignFailed { span, cause: None }
-                            .context("tried optimized assignment of `Promise<T>` to union")));
+                        return Some(Err(ErrorKind::SimpleAssignFailed {
+                            span,
+                            cause: None,
+                            left: None,
+                            right: None,
+                        }
+                        .context("tried optimized assignment of `Promise<T>` to union")));
                     }
                 }
             }
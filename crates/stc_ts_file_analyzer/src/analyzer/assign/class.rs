@@ -166,6 +166,8 @@ impl Analyzer<'_, '_> {
                     return Err(ErrorKind::SimpleAssignFailed {
                         span: opts.span,
                         cause: None,
+                        left: None,
+                        right: None,
                     }
                     .context("opts.disallow_different_classes is true"));
                 }
@@ -222,6 +224,8 @@ impl Analyzer<'_, '_> {
             return Err(ErrorKind::SimpleAssignFailed {
                 span: opts.span,
                 cause: None,
+                left: None,
+                right: None,
             }
             .context("cannot assign literal or keyword to a class"));
         }
@@ -296,7 +300,13 @@ impl Analyzer<'_, '_> {
                     return Ok(());
                 }
 
-                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context("failed to assign a class member to another one"));
+                return Err(ErrorKind::SimpleAssignFailed {
+                    span,
+                    cause: None,
+                    left: None,
+                    right: None,
+                }
+                .context("failed to assign a class member to another one"));
             }
             ClassMember::Property(lp) => {
                 for rm in r {
@@ -340,10 +350,22 @@ impl Analyzer<'_, '_> {
                 }
 
                 if opts.use_missing_fields_for_class {
-                    let err = ErrorKind::MissingFields { span, fields: vec![] }.into();
+                    let err = ErrorKind::MissingFields {
+                        span,
+                        fields: vec![],
+                        left: None,
+                        right: None,
+                    }
+                    .into();
                     return Err(ErrorKind::Errors { span, errors: vec![err] }.into());
                 } else {
-                    return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into());
+                    return Err(ErrorKind::SimpleAssignFailed {
+                        span,
+                        cause: None,
+                        left: None,
+                        right: None,
+                    }
+                    .into());
                 }
             }
             ClassMember::IndexSignature(_) => {}
@@ -470,7 +470,13 @@ impl Analyzer<'_, '_> {
             _ => {}
         }
 
-        Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into())
+        Err(ErrorKind::SimpleAssignFailed {
+            span,
+            cause: None,
+            left: None,
+            right: None,
+        }
+        .into())
     }
 
     ///
@@ -544,7 +550,13 @@ impl Analyzer<'_, '_> {
                 return Ok(());
             }
             Type::Lit(..) | Type::ClassDef(ClassDef { is_abstract: true, .. }) | Type::Function(..) => {
-                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into())
+                return Err(ErrorKind::SimpleAssignFailed {
+                    span,
+                    cause: None,
+                    left: None,
+                    right: None,
+                }
+                .into())
             }
 
             Type::TypeLit(rt) => {
@@ -579,7 +591,13 @@ impl Analyzer<'_, '_> {
                 }
 
                 if !errors.is_empty() {
-                    return Err(ErrorKind::SimpleAssignFailedWithCause { span, cause: errors }.into());
+                    return Err(ErrorKind::SimpleAssignFailedWithCause {
+                        span,
+                        cause: errors,
+                        left: None,
+                        right: None,
+                    }
+                    .into());
                 }
             }
             Type::Interface(..) => {
@@ -602,7 +620,13 @@ impl Analyzer<'_, '_> {
             _ => {}
         }
 
-        Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into())
+        Err(ErrorKind::SimpleAssignFailed {
+            span,
+            cause: None,
+            left: None,
+            right: None,
+        }
+        .into())
     }
 
     /// Assigns a parameter to another one.
@@ -776,12 +800,16 @@ impl Analyzer<'_, '_> {
             ErrorKind::MissingFields { span, .. } => ErrorKind::SimpleAssignFailed {
                 span,
                 cause: Some(box err.into()),
+                left: None,
+                right: None,
             },
             ErrorKind::Errors { ref errors, .. } => {
                 if errors.iter().all(|err| matches!(&**err, ErrorKind::MissingFields { .. })) {
                     ErrorKind::SimpleAssignFailed {
                         span,
                         cause: Some(box err.into()),
+                        left: None,
+                        right: None,
                     }
                 } else {
                     err
@@ -845,7 +873,13 @@ impl Analyzer<'_, '_> {
 
         if opts.for_overload {
             if required_li.clone().count() > required_ri.clone().count() {
-                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context("l.params.required.len > r.params.required.len"));
+                return Err(ErrorKind::SimpleAssignFailed {
+                    span,
+                    cause: None,
+                    left: None,
+                    right: None,
+                }
+                .context("l.params.required.len > r.params.required.len"));
             }
         }
 
@@ -858,7 +892,13 @@ impl Analyzer<'_, '_> {
                     return Ok(());
                 }
 
-                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context(format!(
+                return Err(ErrorKind::SimpleAssignFailed {
+                    span,
+                    cause: None,
+                    left: None,
+                    right: None,
+                }
+                .context(format!(
                     "!l_has_rest && l.params.required.len < r.params.required.len\nLeft: {:?}\nRight: {:?}\n",
                     required_non_void_li.collect_vec(),
                     required_non_void_ri.collect_vec()
@@ -3,7 +3,7 @@ use std::{borrow::Cow, cmp::min, collections::HashMap};
 use stc_ts_ast_rnode::{RBool, RExpr, RIdent, RLit, RNumber, RStr, RTsEntityName, RTsEnumMemberId, RTsLit};
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt, ErrorKind,
+    DebugExt, Error, ErrorKind,
 };
 use stc_ts_types::{
     Array, Conditional, EnumVariant, IdCtx, Instance, Interface, Intersection, IntrinsicKind, Key, KeywordType, KeywordTypeMetadata,
@@ -462,13 +462,13 @@ impl Analyzer<'_, '_> {
             _ => {}
         }
 
-        res.convert_err(|err| match err {
-            ErrorKind::AssignFailed { .. }
+        res.convert_err(|err| match fill_assign_types(err, left, right) {
+            err @ (ErrorKind::AssignFailed { .. }
             | ErrorKind::Errors { .. }
             | ErrorKind::Unimplemented { .. }
             | ErrorKind::TupleAssignError { .. }
-            | ErrorKind::ObjectAssignFailed { .. } => err,
-            _ => ErrorKind::AssignFailed {
+            | ErrorKind::ObjectAssignFailed { .. }) => err,
+            err => ErrorKind::AssignFailed {
                 span: opts.span,
                 left: box left.clone(),
                 right: box right.clone(),
@@ -773,6 +773,8 @@ impl Analyzer<'_, '_> {
             return Err(ErrorKind::SimpleAssignFailed {
                 span: opts.span,
                 cause: None,
+                left: None,
+                right: None,
             }
             .context("global this"));
         }
@@ -1365,6 +1367,8 @@ impl Analyzer<'_, '_> {
                         .convert_err(|err| ErrorKind::SimpleAssignFailed {
                             span: err.span(),
                             cause: Some(box err.into()),
+                            left: None,
+                            right: None,
                         }) {
                         Ok(..) => {}
                         Err(err) => errors.push(err),
@@ -1388,6 +1392,8 @@ impl Analyzer<'_, '_> {
                             .convert_err(|err| ErrorKind::SimpleAssignFailed {
                                 span: err.span(),
                                 cause: Some(box err.into()),
+                                left: None,
+                                right: None,
                             })?;
 
                         errors.retain(|err| !matches!(&**err, ErrorKind::UnknownPropertyInObjectLiteralAssignment { .. }));
@@ -2208,6 +2214,8 @@ impl Analyzer<'_, '_> {
                                 .convert_err(|err| ErrorKind::SimpleAssignFailed {
                                     span: err.span(),
                                     cause: Some(box err.into()),
+                                    left: None,
+                                    right: None,
                                 })
                                 .context("tried to assign a type literal to an expanded keyword");
                         }
@@ -3056,6 +3064,61 @@ pub(crate) enum Variance {
     Invariant,
 }
 
+/// Stores `left` and `right` in errors which do not have them yet, so the
+/// innermost assignment wins.
+fn fill_assign_types(err: ErrorKind, l: &Type, r: &Type) -> ErrorKind {
+    let fill = |errors: Vec<Error>| -> Vec<Error> {
+        errors
+            .into_iter()
+            .map(|err| err.convert(|err| fill_assign_types(err, l, r)))
+            .collect()
+    };
+    let fill_ty = |ty: Option<Box<Type>>, default: &Type| ty.or_else(|| Some(box default.clone()));
+
+    match err {
+        ErrorKind::MissingFields {
+            span,
+            fields,
+            left: None,
+            right: None,
+        } => ErrorKind::MissingFields {
+            span,
+            fields,
+            left: Some(box l.clone()),
+            right: Some(box r.clone()),
+        },
+        ErrorKind::Errors { span, errors } => ErrorKind::Errors {
+            span,
+            errors: fill(errors),
+        },
+        ErrorKind::ObjectAssignFailed { span, errors, left, right } => ErrorKind::ObjectAssignFailed {
+            span,
+            errors: fill(errors),
+            left: fill_ty(left, l),
+            right: fill_ty(right, r),
+        },
+        ErrorKind::SimpleAssignFailed { span, cause, left, right } => ErrorKind::SimpleAssignFailed {
+            span,
+            cause,
+            left: fill_ty(left, l),
+            right: fill_ty(right, r),
+        },
+        ErrorKind::SimpleAssignFailedWithCause { span, cause, left, right } => ErrorKind::SimpleAssignFailedWithCause {
+            span,
+            cause,
+            left: fill_ty(left, l),
+            right: fill_ty(right, r),
+        },
+        ErrorKind::ExactOptionalPropertyAssignFailed { span, cause, left, right } => ErrorKind::ExactOptionalPropertyAssignFailed {
+            span,
+            cause,
+            left: fill_ty(left, l),
+            right: fill_ty(right, r),
+        },
+        _ => err,
+    }
+}
+
 //fn type_of_ts_fn_param<'a>(p: &TsFnParam) -> Type {
 //    match p {
 //        RTsFnParam::Ident(Ident { type_ann, .. })
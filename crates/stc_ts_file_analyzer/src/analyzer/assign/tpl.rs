@@ -35,12 +35,26 @@ impl Analyzer<'_, '_> {
 
         let inference = match inference {
             Some(inference) => inference,
-            None => return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context("tried to infer")),
+            None => {
+                return Err(ErrorKind::SimpleAssignFailed {
+                    span,
+                    cause: None,
+                    left: None,
+                    right: None,
+                }
+                .context("tried to infer"))
+            }
         };
 
         for (i, ty) in inference.iter().enumerate() {
             if !self.is_valid_type_for_tpl_lit_placeholder(span, ty, &l.types[i])? {
-                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context(format!(
+                return Err(ErrorKind::SimpleAssignFailed {
+                    span,
+                    cause: None,
+                    left: None,
+                    right: None,
+                }
+                .context(format!(
                     "verified types:\nsource = {}\ntarget = {}",
                     force_dump_type_as_string(ty),
                     force_dump_type_as_string(&l.types[i])
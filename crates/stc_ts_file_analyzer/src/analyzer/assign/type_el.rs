@@ -5,7 +5,7 @@ use rnode::NodeId;
 use stc_ts_ast_rnode::{RIdent, RTsEntityName, RTsLit};
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt, ErrorKind, Errors,
+    DebugExt, Error, ErrorKind, Errors,
 };
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{
@@ -24,6 +24,7 @@ use crate::{
         util::ResultExt,
         Analyzer,
     },
+    type_facts::TypeFacts,
     VResult,
 };
 
@@ -228,7 +229,13 @@ impl Analyzer<'_, '_> {
                             .context("tried to assign to type elements by converting rhs to a type literal");
                     }
 
-                    return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into());
+                    return Err(ErrorKind::SimpleAssignFailed {
+                        span,
+                        cause: None,
+                        left: None,
+                        right: None,
+                    }
+                    .into());
                 }
 
                 Type::Tuple(..) | Type::Array(..) | Type::EnumVariant(..) if lhs.is_empty() => return Ok(()),
@@ -252,7 +259,13 @@ impl Analyzer<'_, '_> {
                                 | TypeElement::Method(MethodSignature { optional: true, .. })
                         )
                     }) {
-                        return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into());
+                        return Err(ErrorKind::SimpleAssignFailed {
+                            span,
+                            cause: None,
+                            left: None,
+                            right: None,
+                        }
+                        .into());
                     }
 
                     match rhs.normalize() {
@@ -392,10 +405,14 @@ impl Analyzer<'_, '_> {
                             ErrorKind::Errors { span, .. } => ErrorKind::SimpleAssignFailed {
                                 span,
                                 cause: Some(box err.into()),
+                                left: None,
+                                right: None,
                             },
                             ErrorKind::MissingFields { span, .. } => ErrorKind::SimpleAssignFailed {
                                 span,
                                 cause: Some(box err.into()),
+                                left: None,
+                                right: None,
                             },
                             _ => err,
                         })
@@ -502,10 +519,14 @@ impl Analyzer<'_, '_> {
                             ErrorKind::Errors { span, .. } => ErrorKind::SimpleAssignFailed {
                                 span,
                                 cause: Some(box err.into()),
+                                left: None,
+                                right: None,
                             },
                             ErrorKind::MissingFields { span, .. } => ErrorKind::SimpleAssignFailed {
                                 span,
                                 cause: Some(box err.into()),
+                                left: None,
+                                right: None,
                             },
                             _ => err,
                         })
@@ -574,6 +595,8 @@ impl Analyzer<'_, '_> {
                                 ErrorKind::MissingFields { .. } => ErrorKind::SimpleAssignFailed {
                                     span: err.span(),
                                     cause: Some(box err),
+                                    left: None,
+                                    right: None,
                                 }
                                 .into(),
                                 _ => err,
@@ -634,7 +657,15 @@ impl Analyzer<'_, '_> {
                 | Type::Keyword(KeywordType {
                     kind: TsKeywordTypeKind::TsVoidKeyword,
                     ..
-                }) => return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into()),
+                }) => {
+                    return Err(ErrorKind::SimpleAssignFailed {
+                        span,
+                        cause: None,
+                        left: None,
+                        right: None,
+                    }
+                    .into())
+                }
 
                 // TODO(kdy1): Strict mode
                 Type::Keyword(KeywordType {
@@ -688,13 +719,23 @@ impl Analyzer<'_, '_> {
                         let err = ErrorKind::MissingFields {
                             span,
                             fields: lhs.to_vec(),
+                            left: None,
+                            right: None,
                         }
                         .context("keyword `object` is not assignable to a non-empty type literal");
                         return Err(ErrorKind::Errors { span, errors: vec![err] }.into());
                     }
                 }
 
-                Type::EnumVariant(..) => return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into()),
+                Type::EnumVariant(..) => {
+                    return Err(ErrorKind::SimpleAssignFailed {
+                        span,
+                        cause: None,
+                        left: None,
+                        right: None,
+                    }
+                    .into())
+                }
 
                 Type::Keyword(..) => {
                     let rhs = self
@@ -709,13 +750,19 @@ impl Analyzer<'_, '_> {
                         .convert_err(|err| ErrorKind::SimpleAssignFailed {
                             span: err.span(),
                             cause: Some(box err.into()),
+                            left: None,
+                            right: None,
                         })
                         .context("failed to normalize")?;
 
                     if rhs.is_keyword() {
-                        return Err(
-                            ErrorKind::SimpleAssignFailed { span, cause: None }.context("failed to assign builtin type of a keyword")
-                        );
+                        return Err(ErrorKind::SimpleAssignFailed {
+                            span,
+                            cause: None,
+                            left: None,
+                            right: None,
+                        }
+                        .context("failed to assign builtin type of a keyword"));
                     }
 
                     return self
@@ -753,9 +800,21 @@ impl Analyzer<'_, '_> {
             }
 
             if !errors.is_empty() {
+                let errors = ErrorKind::flatten(errors);
+                // `exactOptionalPropertyTypes` has a dedicated error code for the whole
+                // assignment.
+                if errors
+                    .iter()
+                    .all(|err| matches!(&**err, ErrorKind::ExactOptionalPropertyAssignFailed { .. }))
+                {
+                    return Err(ErrorKind::Errors { span, errors }.into());
+                }
+
                 return Err(ErrorKind::ObjectAssignFailed {
                     span,
-                    errors: ErrorKind::flatten(errors),
+                    errors,
+                    left: None,
+                    right: None,
                 })?;
             }
 
@@ -853,6 +912,8 @@ impl Analyzer<'_, '_> {
                     ErrorKind::MissingFields {
                         span,
                         fields: missing_fields,
+                        left: None,
+                        right: None,
                     }
                     .into(),
                 );
@@ -860,7 +921,15 @@ impl Analyzer<'_, '_> {
                 errors.push(
                     ErrorKind::ObjectAssignFailed {
                         span,
-                        errors: vec![ErrorKind::SimpleAssignFailed { span, cause: None }.into()],
+                        errors: vec![ErrorKind::SimpleAssignFailed {
+                            span,
+                            cause: None,
+                            left: None,
+                            right: None,
+                        }
+                        .into()],
+                        left: None,
+                        right: None,
                     }
                     .into(),
                 )
@@ -1135,12 +1204,57 @@ impl Analyzer<'_, '_> {
                                                 }
                                             }
 
-                                            self.assign_inner(
-                                                data,
-                                                lp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
-                                                rp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
-                                                opts,
-                                            )
+                                            let mut r_ty = rp
+                                                .type_ann
+                                                .as_deref()
+                                                .cloned()
+                                                .unwrap_or_else(|| Type::any(span, Default::default()));
+                                            let l_ty = lp
+                                                .type_ann
+                                                .as_deref()
+                                                .cloned()
+                                                .unwrap_or_else(|| Type::any(span, Default::default()));
+                                            let exact = self.rule().exact_optional_property_types;
+                                            let is_optional_undefined = lp.optional && r_ty.contains_undefined();
+
+                                            // An optional property accepts `undefined` unless
+                                            // `exactOptionalPropertyTypes` is enabled.
+                                            if is_optional_undefined && !exact {
+                                                if r_ty.is_undefined() {
+                                                    return Ok(());
+                                                }
+                                                r_ty = self.apply_type_facts_to_type(TypeFacts::NEUndefined, r_ty);
+                                            }
+
+                                            let err: Error = match self.assign_inner(data, &l_ty, &r_ty, opts) {
+                                                Ok(()) => return Ok(()),
+                                                // An empty error means success.
+                                                Err(err) if matches!(&*err, ErrorKind::Errors { errors, .. } if errors.is_empty()) => {
+                                                    return Ok(())
+                                                }
+                                                Err(err) => ErrorKind::PropertyIncompatible {
+                                                    span,
+                                                    key: box l_key.clone(),
+                                                    cause: box err,
+                                                }
+                                                .into(),
+                                            };
+
+                                            // tsc reports a dedicated error if `undefined` is the only problem.
+                                            if is_optional_undefined && exact {
+                                                let defined = self.apply_type_facts_to_type(TypeFacts::NEUndefined, r_ty.clone());
+                                                if r_ty.is_undefined() || self.assign_inner(data, &l_ty, &defined, opts).is_ok() {
+                                                    return Err(ErrorKind::ExactOptionalPropertyAssignFailed {
+                                                        span,
+                                                        cause: box err,
+                                                        left: None,
+                                                        right: None,
+                                                    }
+                                                    .into());
+                                                }
+                                            }
+
+                                            Err(err)
                                         })()?;
 
                                         if let Some(pos) = unhandled_rhs.iter().position(|span| *span == rm.span()) {
@@ -1366,8 +1480,13 @@ impl Analyzer<'_, '_> {
                                     }
 
                                     errors.push(
-                                        ErrorKind::SimpleAssignFailed { span, cause: None }
-                                            .context("failed to assign to an index signature"),
+                                        ErrorKind::SimpleAssignFailed {
+                                            span,
+                                            cause: None,
+                                            left: None,
+                                            right: None,
+                                        }
+                                        .context("failed to assign to an index signature"),
                                     );
                                 }
                             }
@@ -1474,7 +1593,13 @@ impl Analyzer<'_, '_> {
                         }
 
                         if !opts.is_assigning_to_class_members {
-                            return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context("failed to assign to a constructor"));
+                            return Err(ErrorKind::SimpleAssignFailed {
+                                span,
+                                cause: None,
+                                left: None,
+                                right: None,
+                            }
+                            .context("failed to assign to a constructor"));
                         }
                     }
 
@@ -1493,6 +1618,8 @@ impl Analyzer<'_, '_> {
             return Err(ErrorKind::ObjectAssignFailed {
                 span,
                 errors: ErrorKind::flatten(errors),
+                left: None,
+                right: None,
             }
             .into());
         }
@@ -124,7 +124,13 @@ impl Analyzer<'_, '_> {
 
                 Ok(())
             }
-            _ => Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into()),
+            _ => Err(ErrorKind::SimpleAssignFailed {
+                span,
+                cause: None,
+                left: None,
+                right: None,
+            }
+            .into()),
         }
     }
 
@@ -169,7 +175,13 @@ impl Analyzer<'_, '_> {
 
                 Ok(())
             }
-            _ => Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into()),
+            _ => Err(ErrorKind::SimpleAssignFailed {
+                span,
+                cause: None,
+                left: None,
+                right: None,
+            }
+            .into()),
         }
     }
 
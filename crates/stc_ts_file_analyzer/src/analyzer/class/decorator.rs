@@ -0,0 +1,410 @@
+use rnode::NodeId;
+use stc_ts_ast_rnode::{
+    RBindingIdent, RClass, RClassMember, RDecorator, RIdent, RParam, RParamOrTsParamProp, RPat, RPropName, RStr, RTsEntityName, RTsLit,
+};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{
+    Class, ClassDef, ClassMember, ClassProperty, FnParam, Function, Key, KeywordType, LitType, Method, Ref, Type, TypeParamInstantiation,
+};
+use stc_utils::cache::Freeze;
+use swc_atoms::JsWord;
+use swc_common::{Span, DUMMY_SP};
+use swc_ecma_ast::{MethodKind, TsKeywordTypeKind};
+
+use crate::{
+    analyzer::{assign::AssignOpts, util::ResultExt, Analyzer},
+    validator,
+    validator::ValidateWith,
+};
+
+/// The kind of the declaration a decorator is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecoratorTarget {
+    Class,
+    Method,
+    Property,
+    Param,
+}
+
+#[validator]
+impl Analyzer<'_, '_> {
+    /// `expected` is the type of the decorator function expected by the
+    /// decorated declaration. If it's [None], only the expression is
+    /// validated.
+    fn validate(&mut self, d: &RDecorator, target: DecoratorTarget, expected: Option<&Type>) {
+        let ty = d.expr.validate_with_default(self)?.freezed();
+
+        let expected = match expected {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        self.assign_with_opts(
+            &mut Default::default(),
+            expected,
+            &ty,
+            AssignOpts {
+                span: d.span,
+                ..Default::default()
+            },
+        )
+        .map_err(|err| {
+            let span = d.span;
+            let cause = box err;
+
+            match target {
+                DecoratorTarget::Class => ErrorKind::InvalidClassDecorator { span, cause },
+                DecoratorTarget::Method => ErrorKind::InvalidMethodDecorator { span, cause },
+                DecoratorTarget::Property => ErrorKind::InvalidPropDecorator { span, cause },
+                DecoratorTarget::Param => ErrorKind::InvalidParamDecorator { span, cause },
+            }
+            .into()
+        })
+    }
+}
+
+impl Analyzer<'_, '_> {
+    /// Validates decorators of a class and its members against the decorated
+    /// declarations.
+    ///
+    /// With `experimentalDecorators`, decorators are checked using the legacy
+    /// semantics (`ClassDecorator`, `MethodDecorator`, ...). Otherwise they are
+    /// checked like ECMAScript decorators, which receive a context object
+    /// like `ClassMethodDecoratorContext`.
+    pub(super) fn validate_decorators(&mut self, c: &RClass, class: &ClassDef) {
+        if self.config.is_builtin {
+            return;
+        }
+
+        let static_ty = Type::ClassDef(class.clone()).freezed();
+        let instance_ty = Type::Class(Class {
+            span: class.span,
+            def: box class.clone(),
+            metadata: Default::default(),
+            tracker: Default::default(),
+        })
+        .freezed();
+
+        for d in &c.decorators {
+            let expected = self.expected_class_decorator(d.span, &static_ty);
+            d.validate_with_args(self, (DecoratorTarget::Class, Some(&expected)))
+                .report(&mut self.storage);
+        }
+
+        for member in &c.body {
+            match member {
+                RClassMember::Constructor(cons) => {
+                    for param in &cons.params {
+                        let decorators = match param {
+                            RParamOrTsParamProp::TsParamProp(p) => &p.decorators,
+                            RParamOrTsParamProp::Param(p) => &p.decorators,
+                        };
+
+                        self.validate_param_decorators(decorators, &static_ty, None);
+                    }
+                }
+
+                RClassMember::Method(m) => {
+                    let this_ty = if m.is_static { &static_ty } else { &instance_ty };
+                    let name = prop_name_sym(&m.key);
+
+                    for d in &m.function.decorators {
+                        let expected =
+                            name.and_then(|name| self.expected_method_decorator(d.span, class, this_ty, name, m.is_static, m.kind));
+                        d.validate_with_args(self, (DecoratorTarget::Method, expected.as_ref()))
+                            .report(&mut self.storage);
+                    }
+
+                    self.validate_params_of_method(&m.function.params, this_ty, name);
+                }
+
+                RClassMember::PrivateMethod(m) => {
+                    for d in &m.function.decorators {
+                        d.validate_with_args(self, (DecoratorTarget::Method, None))
+                            .report(&mut self.storage);
+                    }
+
+                    let this_ty = if m.is_static { &static_ty } else { &instance_ty };
+                    self.validate_params_of_method(&m.function.params, this_ty, None);
+                }
+
+                RClassMember::ClassProp(p) => {
+                    let this_ty = if p.is_static { &static_ty } else { &instance_ty };
+                    let name = prop_name_sym(&p.key);
+
+                    for d in &p.decorators {
+                        let expected = name.map(|name| self.expected_prop_decorator(d.span, class, this_ty, name, p.is_static));
+                        d.validate_with_args(self, (DecoratorTarget::Property, expected.as_ref()))
+                            .report(&mut self.storage);
+                    }
+                }
+
+                RClassMember::PrivateProp(p) => {
+                    for d in &p.decorators {
+                        d.validate_with_args(self, (DecoratorTarget::Property, None))
+                            .report(&mut self.storage);
+                    }
+                }
+
+                RClassMember::TsIndexSignature(..) | RClassMember::StaticBlock(..) | RClassMember::Empty(..) => {}
+            }
+        }
+    }
+
+    fn validate_params_of_method(&mut self, params: &[RParam], this_ty: &Type, name: Option<&JsWord>) {
+        for param in params {
+            self.validate_param_decorators(&param.decorators, this_ty, name);
+        }
+    }
+
+    /// `name` is [None] for parameters of constructors.
+    fn validate_param_decorators(&mut self, decorators: &[RDecorator], this_ty: &Type, name: Option<&JsWord>) {
+        for d in decorators {
+            if !self.rule().experimental_decorators {
+                // ECMAScript decorators cannot be applied to parameters.
+                self.storage.report(ErrorKind::DecoratorsNotValidHere { span: d.span }.into());
+                d.validate_with_args(self, (DecoratorTarget::Param, None)).report(&mut self.storage);
+                continue;
+            }
+
+            let span = d.span;
+            let key_ty = match name {
+                Some(name) => key_lit(span, name),
+                None => Type::undefined(span, Default::default()),
+            };
+            let expected = decorator_fn(
+                span,
+                vec![
+                    ("target", this_ty.clone()),
+                    ("propertyKey", key_ty),
+                    (
+                        "parameterIndex",
+                        Type::Keyword(KeywordType {
+                            span,
+                            kind: TsKeywordTypeKind::TsNumberKeyword,
+                            metadata: Default::default(),
+                            tracker: Default::default(),
+                        }),
+                    ),
+                ],
+                Type::void(span, Default::default()),
+            );
+
+            d.validate_with_args(self, (DecoratorTarget::Param, Some(&expected)))
+                .report(&mut self.storage);
+        }
+    }
+
+    fn expected_class_decorator(&mut self, span: Span, static_ty: &Type) -> Type {
+        let ret_ty = Type::new_union(span, vec![static_ty.clone(), Type::void(span, Default::default())]);
+
+        if self.rule().experimental_decorators {
+            return decorator_fn(span, vec![("target", static_ty.clone())], ret_ty);
+        }
+
+        decorator_fn(
+            span,
+            vec![
+                ("value", static_ty.clone()),
+                ("context", global_ref(span, "ClassDecoratorContext", vec![static_ty.clone()])),
+            ],
+            ret_ty,
+        )
+    }
+
+    fn expected_method_decorator(
+        &mut self,
+        span: Span,
+        class: &ClassDef,
+        this_ty: &Type,
+        name: &JsWord,
+        is_static: bool,
+        kind: MethodKind,
+    ) -> Option<Type> {
+        let void = Type::void(span, Default::default());
+
+        match (kind, find_member(class, name, is_static)?) {
+            (MethodKind::Method, ClassMember::Method(m)) => {
+                let fn_ty = method_to_fn(m);
+
+                if self.rule().experimental_decorators {
+                    let descriptor = global_ref(span, "TypedPropertyDescriptor", vec![fn_ty]);
+
+                    return Some(decorator_fn(
+                        span,
+                        vec![
+                            ("target", this_ty.clone()),
+                            ("propertyKey", key_lit(span, name)),
+                            ("descriptor", descriptor.clone()),
+                        ],
+                        Type::new_union(span, vec![descriptor, void]),
+                    ));
+                }
+
+                Some(decorator_fn(
+                    span,
+                    vec![
+                        ("value", fn_ty.clone()),
+                        (
+                            "context",
+                            global_ref(span, "ClassMethodDecoratorContext", vec![this_ty.clone(), fn_ty.clone()]),
+                        ),
+                    ],
+                    Type::new_union(span, vec![fn_ty, void]),
+                ))
+            }
+
+            (MethodKind::Getter | MethodKind::Setter, ClassMember::Property(p)) => {
+                let value_ty = property_value(span, p);
+
+                if self.rule().experimental_decorators {
+                    let descriptor = global_ref(span, "TypedPropertyDescriptor", vec![value_ty]);
+
+                    return Some(decorator_fn(
+                        span,
+                        vec![
+                            ("target", this_ty.clone()),
+                            ("propertyKey", key_lit(span, name)),
+                            ("descriptor", descriptor.clone()),
+                        ],
+                        Type::new_union(span, vec![descriptor, void]),
+                    ));
+                }
+
+                let (accessor_ty, context_name) = if kind == MethodKind::Getter {
+                    (decorator_fn(span, vec![], value_ty.clone()), "ClassGetterDecoratorContext")
+                } else {
+                    (
+                        decorator_fn(span, vec![("value", value_ty.clone())], void.clone()),
+                        "ClassSetterDecoratorContext",
+                    )
+                };
+
+                Some(decorator_fn(
+                    span,
+                    vec![
+                        ("value", accessor_ty.clone()),
+                        ("context", global_ref(span, context_name, vec![this_ty.clone(), value_ty])),
+                    ],
+                    Type::new_union(span, vec![accessor_ty, void]),
+                ))
+            }
+
+            _ => None,
+        }
+    }
+
+    fn expected_prop_decorator(&mut self, span: Span, class: &ClassDef, this_ty: &Type, name: &JsWord, is_static: bool) -> Type {
+        let void = Type::void(span, Default::default());
+
+        if self.rule().experimental_decorators {
+            return decorator_fn(span, vec![("target", this_ty.clone()), ("propertyKey", key_lit(span, name))], void);
+        }
+
+        let value_ty = match find_member(class, name, is_static) {
+            Some(ClassMember::Property(p)) => property_value(span, p),
+            _ => Type::any(span, Default::default()),
+        };
+        let initializer_ty = decorator_fn(span, vec![("value", value_ty.clone())], value_ty.clone());
+
+        decorator_fn(
+            span,
+            vec![
+                ("value", Type::undefined(span, Default::default())),
+                (
+                    "context",
+                    global_ref(span, "ClassFieldDecoratorContext", vec![this_ty.clone(), value_ty]),
+                ),
+            ],
+            Type::new_union(span, vec![initializer_ty, void]),
+        )
+    }
+}
+
+fn prop_name_sym(key: &RPropName) -> Option<&JsWord> {
+    match key {
+        RPropName::Ident(i) => Some(&i.sym),
+        RPropName::Str(s) => Some(&s.value),
+        _ => None,
+    }
+}
+
+/// Returns the last member, because the implementation of an overloaded
+/// method comes after its signatures.
+fn find_member<'a>(class: &'a ClassDef, name: &JsWord, is_static: bool) -> Option<&'a ClassMember> {
+    class.body.iter().rev().find(|member| match member {
+        ClassMember::Method(Method {
+            key: Key::Normal { sym, .. },
+            is_static: s,
+            ..
+        })
+        | ClassMember::Property(ClassProperty {
+            key: Key::Normal { sym, .. },
+            is_static: s,
+            ..
+        }) => *sym == *name && *s == is_static,
+        _ => false,
+    })
+}
+
+fn method_to_fn(m: &Method) -> Type {
+    Type::Function(Function {
+        span: m.span,
+        type_params: m.type_params.clone(),
+        params: m.params.clone(),
+        ret_ty: m.ret_ty.clone(),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn property_value(span: Span, p: &ClassProperty) -> Type {
+    p.value.as_deref().cloned().unwrap_or_else(|| Type::any(span, Default::default()))
+}
+
+fn key_lit(span: Span, name: &JsWord) -> Type {
+    Type::Lit(LitType {
+        span,
+        lit: RTsLit::Str(RStr {
+            span,
+            value: name.clone(),
+            raw: None,
+        }),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn global_ref(span: Span, name: &str, params: Vec<Type>) -> Type {
+    Type::Ref(Ref {
+        span,
+        type_name: RTsEntityName::Ident(RIdent::new(name.into(), DUMMY_SP)),
+        type_args: Some(box TypeParamInstantiation { span, params }),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn decorator_fn(span: Span, params: Vec<(&str, Type)>, ret_ty: Type) -> Type {
+    Type::Function(Function {
+        span,
+        type_params: None,
+        params: params
+            .into_iter()
+            .map(|(name, ty)| FnParam {
+                span,
+                required: true,
+                pat: RPat::Ident(RBindingIdent {
+                    node_id: NodeId::invalid(),
+                    id: RIdent::new(name.into(), span),
+                    type_ann: None,
+                }),
+                ty: box ty,
+            })
+            .collect(),
+        ret_ty: box ret_ty,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+    .freezed()
+}
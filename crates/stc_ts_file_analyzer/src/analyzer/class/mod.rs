@@ -39,6 +39,7 @@ use crate::{
     VResult,
 };
 
+mod decorator;
 mod order;
 mod type_param;
 
@@ -189,6 +190,10 @@ impl Analyzer<'_, '_> {
             });
         }
 
+        if p.type_ann.is_none() {
+            self.data.class_props_without_type_ann.insert(p.span);
+        }
+
         match p.accessibility {
             Some(Accessibility::Private) => {}
             _ => {
@@ -383,6 +388,7 @@ impl Analyzer<'_, '_> {
             RTsParamPropParam::Ident(ref i) => {
                 let ty: Option<Type> = i.type_ann.validate_with(self).transpose()?.freezed();
 
+                self.record_decl(&i.id);
                 self.declare_var(i.id.span, VarKind::Param, i.id.clone().into(), ty.clone(), None, true, false, false)?;
 
                 Ok(FnParam {
@@ -416,6 +422,7 @@ impl Analyzer<'_, '_> {
                     }
                 }
 
+                self.record_decl(&i.id);
                 self.declare_var(i.id.span, VarKind::Param, i.id.clone().into(), ty.clone(), None, true, false, false)?;
 
                 Ok(FnParam {
@@ -1536,7 +1543,6 @@ impl Analyzer<'_, '_> {
             has_body: !self.ctx.in_declare,
         };
 
-        c.decorators.visit_with(self);
         let name = self.scope.this_class_name.take();
         if let Some(i) = &name {
             match &**i.sym() {
@@ -1560,7 +1566,7 @@ impl Analyzer<'_, '_> {
         let mut additional_members = vec![];
 
         // Scope is required because of type parameters.
-        let c = self.with_child(ScopeKind::Class, Default::default(), |child: &mut Analyzer| -> VResult<_> {
+        let class = self.with_child(ScopeKind::Class, Default::default(), |child: &mut Analyzer| -> VResult<_> {
             child.ctx.super_references_super_class = true;
             child.ctx.in_class_with_super = c.super_class.is_some();
 
@@ -2016,7 +2022,9 @@ impl Analyzer<'_, '_> {
             self.register_type(i, ty);
         }
 
-        Ok(c)
+        self.validate_decorators(c, &class);
+
+        Ok(class)
     }
 }
 
@@ -2038,6 +2046,7 @@ impl Analyzer<'_, '_> {
         let c = self
             .with_child(ScopeKind::Block, Default::default(), |analyzer| {
                 if let Some(ref i) = c.ident {
+                    analyzer.record_decl(i);
                     let ty = analyzer.register_type(i.into(), ty);
 
                     match analyzer.declare_var(
@@ -2289,6 +2298,7 @@ impl Analyzer<'_, '_> {
 
     fn visit_class_decl_inner(&mut self, c: &RClassDecl) {
         c.ident.visit_with(self);
+        self.record_decl(&c.ident);
 
         self.scope.this_class_name = Some(c.ident.clone().into());
         let ty = match c.class.validate_with_args(self, None) {
@@ -0,0 +1,245 @@
+use std::borrow::Cow;
+
+use stc_ts_ast_rnode::{RIdent, RMemberProp, RStr, RTsLit};
+use stc_ts_types::{Class, Function, Id, Key, LitType, Type, TypeElement};
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned, TypeEq};
+use swc_ecma_ast::Accessibility;
+use tracing::debug;
+
+use crate::{
+    analyzer::{types::NormalizeTypeOpts, Analyzer},
+    recorder::{CompletionKind, Recorder},
+    VResult,
+};
+
+impl Analyzer<'_, '_> {
+    fn completion_recorder(&self, span: Span) -> Option<Recorder> {
+        match &self.recorder {
+            Some(recorder) if recorder.is_completion_target(span) => Some(recorder.clone()),
+            _ => None,
+        }
+    }
+
+    /// Records variables and types visible at `i`, if it's the completion
+    /// target.
+    pub(crate) fn complete_ident(&mut self, i: &RIdent) {
+        let recorder = match self.completion_recorder(i.span) {
+            Some(v) => v,
+            None => return,
+        };
+
+        for (id, var) in self.scope.visible_vars() {
+            recorder.record_completion(id.sym().clone(), CompletionKind::Var, var.ty.as_ref().or(var.actual_ty.as_ref()));
+        }
+
+        for (id, ty) in self.scope.visible_types() {
+            recorder.record_completion(id.sym().clone(), CompletionKind::Type, Some(ty));
+        }
+
+        // Imported items are stored in the storage instead of the scope.
+        let module_id = self.ctx.module_id;
+        let top_level_ctxt = self.storage.top_level_ctxt(module_id);
+        for id in self.data.imports_by_id.keys() {
+            if id.ctxt() != top_level_ctxt {
+                continue;
+            }
+
+            if let Some(ty) = self.storage.get_local_var(module_id, id.clone()) {
+                recorder.record_completion(id.sym().clone(), CompletionKind::Var, Some(&ty));
+            }
+            if let Some(ty) = self.storage.get_local_type(module_id, id.clone()) {
+                recorder.record_completion(id.sym().clone(), CompletionKind::Type, Some(&ty));
+            }
+        }
+
+        self.env
+            .for_each_global_var(|name, ty| recorder.record_completion(name.clone(), CompletionKind::Var, Some(ty)));
+
+        self.env
+            .for_each_global_type(|name, ty| recorder.record_completion(name.clone(), CompletionKind::Type, Some(ty)));
+    }
+
+    /// Records properties of `obj`, if `prop` is the completion target.
+    pub(crate) fn complete_member(&mut self, span: Span, obj: &Type, prop: &RMemberProp) {
+        let recorder = match self.completion_recorder(prop.span()) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let obj = match (obj.is_this(), self.scope.this()) {
+            (true, Some(this)) => this.into_owned(),
+            _ => obj.clone(),
+        };
+
+        match self.properties_for_completion(span, &obj) {
+            Ok(props) => {
+                for (name, kind, ty) in props {
+                    recorder.record_completion(name, kind, ty.as_ref());
+                }
+            }
+            Err(err) => {
+                debug!("Failed to collect properties for completion: {:?}", err);
+            }
+        }
+    }
+
+    /// For unions, only properties present in all members are returned.
+    fn properties_for_completion(&mut self, span: Span, ty: &Type) -> VResult<Vec<(JsWord, CompletionKind, Option<Type>)>> {
+        let ty = self.normalize(
+            Some(span),
+            Cow::Borrowed(ty),
+            NormalizeTypeOpts {
+                preserve_union: true,
+                ..Default::default()
+            },
+        )?;
+
+        if let Type::Union(u) = ty.normalize() {
+            let mut common: Option<Vec<_>> = None;
+
+            for member in &u.types {
+                let props = self.properties_for_completion(span, member)?;
+
+                common = Some(match common {
+                    Some(prev) => prev
+                        .into_iter()
+                        .filter(|(name, ..)| props.iter().any(|(other, ..)| name == other))
+                        .collect(),
+                    None => props,
+                });
+            }
+
+            return Ok(common.unwrap_or_default());
+        }
+
+        let classes = self.class_chain(span, &ty);
+        let lit = match self.convert_type_to_type_lit(span, ty)? {
+            Some(v) => v,
+            None => return Ok(vec![]),
+        };
+
+        // Private members are accessible only within the class declaring them, and
+        // protected members are also accessible within subclasses.
+        let enclosing = self
+            .scope
+            .this_class_name()
+            .and_then(|name| classes.iter().position(|(class, _)| class.as_ref() == Some(&name)));
+        let declaring = |key: &Key| classes.iter().position(|(_, keys)| keys.iter().any(|k| k.type_eq(key)));
+        let is_accessible = |key: &Key, accessibility: Option<Accessibility>| match accessibility {
+            Some(Accessibility::Private) => enclosing.is_some() && declaring(key) == enclosing,
+            Some(Accessibility::Protected) => {
+                matches!((enclosing, declaring(key)), (Some(enclosing), Some(declaring)) if declaring >= enclosing)
+            }
+            _ => true,
+        };
+
+        let mut props = vec![];
+
+        for el in &lit.members {
+            match el {
+                TypeElement::Property(p) => {
+                    if !is_accessible(&p.key, p.accessibility) {
+                        continue;
+                    }
+
+                    if let Some(name) = key_to_name(&p.key, is_accessible(&p.key, Some(Accessibility::Private))) {
+                        props.push((name, CompletionKind::Property, p.type_ann.as_deref().cloned()));
+                    }
+                }
+
+                TypeElement::Method(m) => {
+                    if !is_accessible(&m.key, m.accessibility) {
+                        continue;
+                    }
+
+                    if let Some(name) = key_to_name(&m.key, is_accessible(&m.key, Some(Accessibility::Private))) {
+                        let ty = m.ret_ty.as_ref().map(|ret_ty| {
+                            Type::Function(Function {
+                                span: m.span,
+                                type_params: m.type_params.clone(),
+                                params: m.params.clone(),
+                                ret_ty: ret_ty.clone(),
+                                metadata: Default::default(),
+                                tracker: Default::default(),
+                            })
+                        });
+                        props.push((name, CompletionKind::Method, ty));
+                    }
+                }
+
+                // Index signatures with literal keys are created by mapped types.
+                TypeElement::Index(i) => {
+                    if let Some(param) = i.params.first() {
+                        let keys = match param.ty.normalize() {
+                            Type::Union(u) => u.types.iter().collect(),
+                            ty => vec![ty],
+                        };
+
+                        for key in keys {
+                            if let Type::Lit(LitType {
+                                lit: RTsLit::Str(RStr { value, .. }),
+                                ..
+                            }) = key.normalize()
+                            {
+                                props.push((value.clone(), CompletionKind::Property, i.type_ann.as_deref().cloned()));
+                            }
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(props)
+    }
+
+    /// Returns the class of instances of `ty` and its super classes, with keys
+    /// of members they declare. Returns an empty list if `ty` is not a class.
+    fn class_chain(&mut self, span: Span, ty: &Type) -> Vec<(Option<Id>, Vec<Key>)> {
+        let mut def = match ty.normalize() {
+            Type::Class(Class { def, .. }) => Some((**def).clone()),
+            Type::ClassDef(def) => Some(def.clone()),
+            _ => None,
+        };
+
+        let mut chain: Vec<(Option<Id>, Vec<Key>)> = vec![];
+        while let Some(cur) = def.take() {
+            // A class cannot extend itself, but the code may be invalid.
+            if cur.name.is_some() && chain.iter().any(|(name, _)| *name == cur.name) {
+                break;
+            }
+
+            let keys = cur.body.iter().filter_map(|member| member.key().map(Cow::into_owned)).collect();
+            chain.push((cur.name.clone(), keys));
+
+            if let Some(super_class) = &cur.super_class {
+                if let Ok(super_class) = self.normalize(Some(span), Cow::Borrowed(&**super_class), Default::default()) {
+                    if let Type::ClassDef(super_def) = super_class.normalize() {
+                        def = Some(super_def.clone());
+                    }
+                }
+            }
+        }
+
+        chain
+    }
+}
+
+/// Private names like `#foo` are returned only if `is_private_accessible` is
+/// true.
+fn key_to_name(key: &Key, is_private_accessible: bool) -> Option<JsWord> {
+    match key {
+        Key::Normal { sym, .. } => Some(sym.clone()),
+        Key::Computed(c) => match c.ty.normalize() {
+            Type::Lit(LitType {
+                lit: RTsLit::Str(RStr { value, .. }),
+                ..
+            }) => Some(value.clone()),
+            _ => None,
+        },
+        Key::Private(p) if is_private_accessible => Some(format!("#{}", p.id.sym()).into()),
+        _ => None,
+    }
+}
@@ -375,6 +375,7 @@ impl Analyzer<'_, '_> {
         let false_facts = facts_from_test.false_facts;
 
         let mut cons_ends_with_unreachable = false;
+        let mut cons_reachability = Reachability::default();
 
         let cons_ends_with_ret = stmt.cons.ends_with_ret();
 
@@ -384,12 +385,14 @@ impl Analyzer<'_, '_> {
                 stmt.cons.visit_with(child);
 
                 cons_ends_with_unreachable = child.ctx.in_unreachable;
+                cons_reachability = child.reachability();
 
                 Ok(child.cur_facts.true_facts.take())
             })
             .report(&mut self.storage);
 
         let mut alt_ends_with_unreachable = None;
+        let mut alt_reachability = None;
 
         let facts_from_alt = if let Some(alt) = &stmt.alt {
             self.cur_facts = prev_facts.clone();
@@ -397,6 +400,7 @@ impl Analyzer<'_, '_> {
                 alt.visit_with(child);
 
                 alt_ends_with_unreachable = Some(child.ctx.in_unreachable);
+                alt_reachability = Some(child.reachability());
 
                 Ok(child.cur_facts.true_facts.take())
             })
@@ -407,6 +411,11 @@ impl Analyzer<'_, '_> {
 
         self.cur_facts = prev_facts;
 
+        // Without `else`, the end is reachable if the test is falsy.
+        if let Some(alt_reachability) = alt_reachability {
+            self.ctx.after_jump |= cons_reachability.join(alt_reachability).after_jump;
+        }
+
         if cons_ends_with_ret {
             self.cur_facts.true_facts += false_facts;
             return Ok(());
@@ -626,6 +635,10 @@ impl Analyzer<'_, '_> {
         let len = stmt.cases.len();
         let stmt_span = stmt.span();
 
+        let breaks = self.scope.return_values.breaks.len();
+        // Reachability of the end of each case.
+        let mut case_reachability = vec![];
+
         let mut errored = false;
         // Check cases *in order*
         for (i, case) in stmt.cases.iter().enumerate() {
@@ -672,10 +685,10 @@ impl Analyzer<'_, '_> {
             let mut facts_for_body = base_true_facts.clone();
             facts_for_body += true_facts_created_by_case;
 
-            self.with_child(ScopeKind::Flow, facts_for_body, |child| {
+            case_reachability.push(self.with_child(ScopeKind::Flow, facts_for_body, |child| {
                 cons.visit_with(child);
-                Ok(())
-            })?;
+                Ok(child.reachability())
+            })?);
 
             if ends_with_ret || last {
                 false_facts += false_facts_created_by_case.clone();
@@ -690,6 +703,25 @@ impl Analyzer<'_, '_> {
                 .all(|case| self.is_switch_case_body_unconditional_termination(&case.cons));
         }
 
+        if !errored {
+            // If there's no default case, no case may match.
+            let has_default = stmt.cases.iter().any(|case| case.test.is_none());
+            let has_break = self.scope.return_values.breaks[breaks..].contains(&None);
+
+            if has_default && !has_break && case_reachability.last().map_or(false, |r| !r.is_reachable()) {
+                self.ctx.after_jump = true;
+            }
+        }
+
+        if self.rule().no_fallthrough_cases_in_switch && !self.ctx.reevaluating() {
+            // Empty cases are allowed to fall through, and the last case cannot.
+            for (case, reachability) in stmt.cases.iter().zip(&case_reachability).take(len.saturating_sub(1)) {
+                if !case.cons.is_empty() && reachability.is_reachable() {
+                    self.storage.report(ErrorKind::FallthroughCaseInSwitch { span: case.span }.into());
+                }
+            }
+        }
+
         if ends_with_ret {
             self.cur_facts.true_facts += false_facts;
         }
@@ -698,6 +730,45 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Reachability of the current statement, which is tracked using
+/// `Ctx::in_unreachable` and `Ctx::after_jump`.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct Reachability {
+    pub in_unreachable: bool,
+    pub after_jump: bool,
+}
+
+impl Reachability {
+    pub fn is_reachable(self) -> bool {
+        !self.in_unreachable && !self.after_jump
+    }
+
+    /// Reachability after two branches, which is unreachable only if both of
+    /// them are.
+    pub fn join(self, other: Self) -> Self {
+        let in_unreachable = self.in_unreachable && other.in_unreachable;
+
+        Reachability {
+            in_unreachable,
+            after_jump: !in_unreachable && !self.is_reachable() && !other.is_reachable(),
+        }
+    }
+}
+
+impl Analyzer<'_, '_> {
+    pub(super) fn reachability(&self) -> Reachability {
+        Reachability {
+            in_unreachable: self.ctx.in_unreachable,
+            after_jump: self.ctx.after_jump,
+        }
+    }
+
+    pub(super) fn set_reachability(&mut self, reachability: Reachability) {
+        self.ctx.in_unreachable = reachability.in_unreachable;
+        self.ctx.after_jump = reachability.after_jump;
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct PatAssignOpts {
     pub assign: AssignOpts,
@@ -732,6 +803,28 @@ impl Analyzer<'_, '_> {
         false
     }
 
+    /// Returns `true` if `rhs_ty` can be assigned to the property `lhs` only
+    /// because the property is optional, which is not allowed with
+    /// `exactOptionalPropertyTypes`.
+    fn is_undefined_written_to_optional_prop(&mut self, span: Span, lhs: &RExpr, rhs_ty: &Type) -> bool {
+        if !self.rule().exact_optional_property_types || !rhs_ty.contains_undefined() || !matches!(lhs, RExpr::Member(..)) {
+            return false;
+        }
+
+        // Reads of optional properties include `undefined`.
+        let ctx = Ctx {
+            ignore_errors: true,
+            ..self.ctx
+        };
+        let mut a = self.with_ctx(ctx);
+        let read_ty = match lhs.validate_with_args(&mut *a, (TypeOfMode::RValue, None, None)) {
+            Ok(ty) => ty,
+            Err(..) => return false,
+        };
+
+        a.assign(span, &mut Default::default(), &read_ty, rhs_ty).is_ok()
+    }
+
     pub(super) fn try_assign(&mut self, span: Span, op: AssignOp, lhs: &RPatOrExpr, rhs_ty: &Type) -> Type {
         rhs_ty.assert_valid();
 
@@ -746,7 +839,7 @@ impl Analyzer<'_, '_> {
                     lhs_ty.freeze();
 
                     if op == op!("=") {
-                        self.assign_with_opts(
+                        let res = self.assign_with_opts(
                             &mut Default::default(),
                             &lhs_ty,
                             rhs_ty,
@@ -755,7 +848,15 @@ impl Analyzer<'_, '_> {
                                 left_ident_span: Some(lhs.span()),
                                 ..Default::default()
                             },
-                        )?
+                        );
+                        if res.is_err() && self.is_undefined_written_to_optional_prop(span, expr, rhs_ty) {
+                            Err(ErrorKind::ExactOptionalPropertyWriteFailed {
+                                span,
+                                left: box lhs_ty.clone(),
+                                right: box rhs_ty.clone(),
+                            })?;
+                        }
+                        res?
                     } else {
                         self.assign_with_operator(span, op, &lhs_ty, rhs_ty)?;
                     }
@@ -871,6 +972,8 @@ impl Analyzer<'_, '_> {
             }
 
             RPat::Ident(i) => {
+                self.record_usage(&i.id);
+
                 // Verify using immutable references.
                 if let Some(var_info) = self.scope.get_var(&i.id.clone().into()) {
                     if let Some(mut var_ty) = var_info.ty.clone() {
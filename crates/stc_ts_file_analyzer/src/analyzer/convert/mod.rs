@@ -121,6 +121,8 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, p: &RTsTypeParam) -> VResult<TypeParam> {
+        self.record_decl(&p.name);
+
         let ctx = Ctx {
             in_actual_type: true,
             ..self.ctx
@@ -183,6 +185,8 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, d: &RTsTypeAliasDecl) -> VResult<Type> {
         let span = d.span;
 
+        self.record_decl(&d.id);
+
         let alias = {
             self.with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| -> VResult<_> {
                 let type_params = try_opt!(d.type_params.validate_with(child)).map(Box::new);
@@ -265,6 +269,8 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, d: &RTsInterfaceDecl) -> VResult<Type> {
+        self.record_decl(&d.id);
+
         let ty = self.with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| -> VResult<_> {
             match &*d.id.sym {
                 "any" | "void" | "never" | "unknown" | "string" | "number" | "bigint" | "boolean" | "null" | "undefined" | "symbol" => {
@@ -763,6 +769,7 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, t: &RTsTypeRef) -> VResult<Type> {
         let span = t.span;
+        self.record_entity_name(&t.type_name);
         let type_args = try_opt!(t.type_params.validate_with(self)).map(Box::new).freezed();
         let mut contains_infer = false;
 
@@ -883,7 +890,10 @@ impl Analyzer<'_, '_> {
         let span = t.span();
 
         Ok(match t {
-            RTsTypeQueryExpr::TsEntityName(t) => t.clone().into(),
+            RTsTypeQueryExpr::TsEntityName(t) => {
+                self.record_entity_name(t);
+                t.clone().into()
+            }
             RTsTypeQueryExpr::Import(i) => i.validate_with(self)?.into(),
         })
     }
@@ -1015,6 +1025,7 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, ty: &RTsType) -> VResult<Type> {
+        let span = ty.span();
         let is_topmost_type = !self.ctx.is_not_topmost_type;
         let ctx = Ctx {
             is_not_topmost_type: true,
@@ -1093,11 +1104,11 @@ impl Analyzer<'_, '_> {
             Ok(ty)
         })?;
 
-        if is_topmost_type {
-            Ok(ty.freezed())
-        } else {
-            Ok(ty)
-        }
+        let ty = if is_topmost_type { ty.freezed() } else { ty };
+
+        self.record_type(span, &ty);
+
+        Ok(ty)
     }
 }
 
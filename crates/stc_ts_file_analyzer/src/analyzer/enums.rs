@@ -41,6 +41,8 @@ type EnumValues = FxHashMap<JsWord, RTsLit>;
 impl Analyzer<'_, '_> {
     #[inline(never)]
     fn validate(&mut self, e: &RTsEnumDecl) -> VResult<Enum> {
+        self.record_decl(&e.id);
+
         for m in &e.members {
             self.validate_with(|a| a.validate_enum_member_name(&m.id));
         }
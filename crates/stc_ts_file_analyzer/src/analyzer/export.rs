@@ -65,6 +65,10 @@ impl Analyzer<'_, '_> {
                     let ty = ty.unwrap_or_else(|| Type::any(span, Default::default()));
                     a.register_type(e.id.clone().into(), ty);
 
+                    if let Some(recorder) = &a.recorder {
+                        recorder.record_export(a.ctx.module_id, e.id.sym.clone(), e.id.clone().into());
+                    }
+
                     a.storage.export_type(span, a.ctx.module_id, e.id.clone().into());
                     a.storage
                         .export_var(span, a.ctx.module_id, e.id.clone().into(), e.id.clone().into());
@@ -73,6 +77,10 @@ impl Analyzer<'_, '_> {
                     RTsModuleName::Ident(id) => {
                         module.visit_with(a);
 
+                        if let Some(recorder) = &a.recorder {
+                            recorder.record_export(a.ctx.module_id, id.sym.clone(), id.clone().into());
+                        }
+
                         a.storage.export_type(span, a.ctx.module_id, id.clone().into());
                     }
                     RTsModuleName::Str(..) => {
@@ -111,6 +119,10 @@ impl Analyzer<'_, '_> {
 
         match export.decl {
             RDefaultDecl::Fn(ref f) => {
+                if let Some(i) = &f.ident {
+                    self.record_decl(i);
+                }
+
                 let i = f.ident.as_ref().map(|v| v.into()).unwrap_or_else(|| Id::word(js_word!("default")));
                 let fn_ty = match f.function.validate_with_args(self, f.ident.as_ref()) {
                     Ok(ty) => ty,
@@ -133,6 +145,10 @@ impl Analyzer<'_, '_> {
                 self.export_var(f.span(), Id::word(js_word!("default")), Some(i), f.function.body.is_some());
             }
             RDefaultDecl::Class(ref c) => {
+                if let Some(i) = &c.ident {
+                    self.record_decl(i);
+                }
+
                 let id: Option<Id> = c.ident.as_ref().map(|v| v.into());
                 let orig_name = id.clone();
 
@@ -205,8 +221,13 @@ impl Analyzer<'_, '_> {
             self.report_errors_for_duplicated_exports_of_var(span, name.sym().clone());
         }
 
-        self.storage
-            .export_var(span, self.ctx.module_id, name.clone(), orig_name.unwrap_or(name));
+        let orig_name = orig_name.unwrap_or_else(|| name.clone());
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record_export(self.ctx.module_id, name.sym().clone(), orig_name.clone());
+        }
+
+        self.storage.export_var(span, self.ctx.module_id, name, orig_name);
     }
 
     /// Exports a type.
@@ -222,6 +243,10 @@ impl Analyzer<'_, '_> {
 
         let orig_name = orig_name.unwrap_or_else(|| name.clone());
 
+        if let Some(recorder) = &self.recorder {
+            recorder.record_export(self.ctx.module_id, name.sym().clone(), orig_name.clone());
+        }
+
         let types = match self.find_type(&orig_name) {
             Ok(v) => v,
             Err(err) => {
@@ -324,6 +349,15 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RExportNamedSpecifier) {
+        if let RModuleExportName::Ident(orig) = &node.orig {
+            self.record_usage(orig);
+
+            // `b` in `export { a as b }` is not resolved, but it's the same symbol.
+            if let Some(RModuleExportName::Ident(exported)) = &node.exported {
+                self.record_ident(exported, orig.into(), false);
+            }
+        }
+
         let ctx = Ctx {
             report_error_for_non_local_vars: true,
             ..self.ctx
@@ -358,7 +392,7 @@ impl Analyzer<'_, '_> {
         let span = node.span;
         let ctxt = self.ctx.module_id;
 
-        let (dep, data) = self.get_imported_items(span, &node.src.value);
+        let (dep, data) = self.get_imported_items(node.src.span, &node.src.value);
 
         if ctxt != dep {
             match data.normalize() {
@@ -392,24 +426,26 @@ impl Analyzer<'_, '_> {
             node.specifiers.visit_with(self);
         }
 
+        // The source is resolved only once, so an unresolved module is reported
+        // only once.
+        let dep = node.src.as_ref().map(|src| self.get_imported_items(src.span, &src.value).0);
+
         for specifier in &node.specifiers {
             match specifier {
-                RExportSpecifier::Namespace(_) => {
-                    // We need
-                    match &node.src {
-                        Some(src) => {
-                            let (dep, data) = self.get_imported_items(node.span, &src.value);
-                        }
-                        None => {}
-                    }
-                }
+                RExportSpecifier::Namespace(_) => {}
                 RExportSpecifier::Default(_) => {}
                 RExportSpecifier::Named(named) => {
                     //
 
-                    match &node.src {
-                        Some(src) => {
-                            let (dep, data) = self.get_imported_items(node.span, &src.value);
+                    match dep {
+                        Some(dep) => {
+                            if let Some(recorder) = &self.recorder {
+                                if dep != base {
+                                    let orig = Id::from(&named.orig);
+                                    let exported = named.exported.as_ref().map(Id::from).unwrap_or_else(|| orig.clone());
+                                    recorder.record_reexport(base, exported.sym().clone(), dep, orig.sym().clone());
+                                }
+                            }
 
                             self.reexport(
                                 span,
@@ -438,6 +474,10 @@ impl Analyzer<'_, '_> {
 
 impl Analyzer<'_, '_> {
     fn export_named(&mut self, span: Span, ctxt: ModuleId, orig: Id, id: Id) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_export(ctxt, id.sym().clone(), orig.clone());
+        }
+
         if self.storage.get_local_var(ctxt, orig.clone()).is_some() {
             self.report_errors_for_duplicated_exports_of_var(span, id.sym().clone());
 
@@ -280,7 +280,11 @@ impl Analyzer<'_, '_> {
                         .get_element_from_iterator(span, Cow::Borrowed(iterator_elem), n)
                         .with_context(|| format!("failed to get element type from {}th element", idx))
                         .convert_err(|err| match err {
-                            ErrorKind::TupleIndexError { span, .. } => ErrorKind::TupleTooShort { span },
+                            ErrorKind::TupleIndexError { span, .. } => ErrorKind::TupleTooShort {
+                                span,
+                                index: n,
+                                ty: box iterator.clone().into_owned(),
+                            },
                             _ => err,
                         })
                         .map(Cow::into_owned);
@@ -6,8 +6,8 @@ use itertools::Itertools;
 use rnode::{Fold, FoldWith, NodeId, VisitMut, VisitMutWith, VisitWith};
 use stc_ts_ast_rnode::{
     RArrayPat, RBindingIdent, RCallExpr, RCallee, RComputedPropName, RExpr, RExprOrSpread, RIdent, RInvalid, RLit, RMemberExpr,
-    RMemberProp, RNewExpr, RObjectPat, RPat, RStr, RTaggedTpl, RTsAsExpr, RTsEntityName, RTsLit, RTsThisTypeOrIdent, RTsType,
-    RTsTypeParamInstantiation, RTsTypeRef,
+    RMemberProp, RNewExpr, RObjectPat, RParenExpr, RPat, RStr, RSuperProp, RSuperPropExpr, RTaggedTpl, RTsAsExpr, RTsEntityName, RTsLit,
+    RTsThisTypeOrIdent, RTsType, RTsTypeParamInstantiation, RTsTypeRef,
 };
 use stc_ts_env::MarkExt;
 use stc_ts_errors::{
@@ -18,13 +18,13 @@ use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_generics::type_param::finder::TypeParamUsageFinder;
 use stc_ts_type_ops::{generalization::prevent_generalize, is_str_lit_or_union, Fix};
 use stc_ts_types::{
-    type_id::SymbolId, Alias, Array, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, Function, Id, IdCtx,
-    IndexedAccessType, Instance, Interface, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, QueryExpr, QueryType, Ref,
-    StaticThis, Symbol, TypeParamDecl, Union, UnionMetadata,
+    name::Name, type_id::SymbolId, Alias, Array, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, Function, Id, IdCtx,
+    IndexedAccessType, Instance, Interface, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, Predicate, QueryExpr, QueryType,
+    Ref, StaticThis, Symbol, TypeParamDecl, Union, UnionMetadata,
 };
 use stc_ts_utils::PatExt;
 use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt};
-use swc_atoms::js_word;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::{Accessibility, TsKeywordTypeKind};
 use tracing::{debug, info, warn};
@@ -44,6 +44,7 @@ use crate::{
         CallSignature, ConstructorSignature, FnParam, Method, MethodSignature, Type, TypeElement, TypeOrSpread, TypeParam,
         TypeParamInstantiation,
     },
+    type_facts::TypeFacts,
     validator,
     validator::ValidateWith,
     VResult,
@@ -113,6 +114,7 @@ impl Analyzer<'_, '_> {
             RCallee::Import(callee) => {
                 let base = self.storage.path(self.ctx.module_id);
 
+                let src_span = args.first().map(|arg| arg.expr.span()).unwrap_or(span);
                 let src = args.iter().next();
                 let src = match src {
                     Some(src) => src.expr.validate_with_default(self)?,
@@ -131,10 +133,13 @@ impl Analyzer<'_, '_> {
                     _ => return Err(ErrorKind::NonStringDynamicImport { span: callee.span }.into()),
                 };
 
-                let dep_id = self.loader.module_id(&base, &src);
+                let dep_id = match self.loader.module_id(&base, &src) {
+                    Some(v) => v,
+                    None => return Err(ErrorKind::ModuleNotFound { span: src_span, name: src }.into()),
+                };
 
-                if let Some(dep_id) = dep_id {
-                    if let Some(dep) = self.data.imports.get(&(self.ctx.module_id, dep_id)) {
+                match self.data.imports.get(&(self.ctx.module_id, dep_id)) {
+                    Some(dep) => {
                         return Ok(Type::Ref(Ref {
                             span,
                             type_name: RTsEntityName::Ident(RIdent::new("Promise".into(), span.with_ctxt(SyntaxContext::empty()))),
@@ -145,16 +150,9 @@ impl Analyzer<'_, '_> {
                             metadata: Default::default(),
                             tracker: Default::default(),
                         }));
-                    } else {
-                        return Err(ErrorKind::ModuleNotFound { span: callee.span }.into());
                     }
+                    None => return Err(ErrorKind::ModuleNotFound { span: src_span, name: src }.into()),
                 }
-
-                return Err(ErrorKind::Unimplemented {
-                    span: e.span,
-                    msg: "validation of dynamic import".to_string(),
-                }
-                .into());
             }
         };
 
@@ -2359,6 +2357,7 @@ impl Analyzer<'_, '_> {
                 span,
                 min: min_param,
                 max: max_param,
+                actual: args.len(),
             }
             .into())
         }
@@ -2857,7 +2856,7 @@ impl Analyzer<'_, '_> {
             ty.freeze();
 
             if kind == ExtractKind::Call {
-                self.add_call_facts(&expanded_param_types, args, &mut ty);
+                self.add_call_facts(expr, &expanded_param_types, args, arg_types, &mut ty);
             }
 
             return Ok(ty);
@@ -2878,7 +2877,7 @@ impl Analyzer<'_, '_> {
         ret_ty.freeze();
 
         if kind == ExtractKind::Call {
-            self.add_call_facts(&params, args, &mut ret_ty);
+            self.add_call_facts(expr, &params, args, arg_types, &mut ret_ty);
         }
 
         Ok(ret_ty)
@@ -3169,7 +3168,7 @@ impl Analyzer<'_, '_> {
                                 ErrorKind::TupleAssignError { span, errors } if !arg.ty.metadata().resolved_from_var => {
                                     return ErrorKind::Errors { span, errors }
                                 }
-                                ErrorKind::ObjectAssignFailed { span, errors } if !arg.ty.metadata().resolved_from_var => {
+                                ErrorKind::ObjectAssignFailed { span, errors, .. } if !arg.ty.metadata().resolved_from_var => {
                                     return ErrorKind::Errors { span, errors }
                                 }
                                 ErrorKind::Errors { span, ref errors } => {
@@ -3225,7 +3224,23 @@ impl Analyzer<'_, '_> {
     ///
     /// should make type of `subscriber` `SafeSubscriber`, not `Subscriber`.
     /// I (kdy1) don't know why.
-    fn add_call_facts(&mut self, params: &[FnParam], args: &[RExprOrSpread], ret_ty: &mut Type) {
+    fn add_call_facts(
+        &mut self,
+        expr: ReEvalMode,
+        params: &[FnParam],
+        args: &[RExprOrSpread],
+        arg_types: &[TypeOrSpread],
+        ret_ty: &mut Type,
+    ) {
+        if let (ReEvalMode::Call(e), Type::Predicate(p)) = (expr, ret_ty.normalize()) {
+            if p.asserts {
+                if self.ctx.call_stmt_span == Some(e.span) {
+                    self.add_assertion_facts(e, params, arg_types, p);
+                }
+                return;
+            }
+        }
+
         if !self.ctx.in_cond {
             return;
         }
@@ -3265,6 +3280,161 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Narrows types for the rest of the enclosing flow after a call to an
+    /// assertion function, like `asserts x is T` or `asserts x`.
+    fn add_assertion_facts(&mut self, e: &RCallExpr, params: &[FnParam], arg_types: &[TypeOrSpread], p: &Predicate) {
+        let callee = match &e.callee {
+            RCallee::Expr(callee) => &**callee,
+            _ => return,
+        };
+
+        if let Err(err) = self.validate_assertion_call_target(callee.span(), callee) {
+            self.storage.report(err);
+            return;
+        }
+
+        match &p.param_name {
+            RTsThisTypeOrIdent::TsThisType(..) => {
+                let ty = match &p.ty {
+                    Some(ty) => ty.clone().freezed(),
+                    None => return,
+                };
+
+                if let RExpr::Member(RMemberExpr { obj, .. }) = callee {
+                    self.store_assertion_fact(obj, &ty);
+                }
+            }
+            RTsThisTypeOrIdent::Ident(arg_id) => {
+                let idx = match params
+                    .iter()
+                    .position(|param| matches!(&param.pat, RPat::Ident(i) if i.id.sym == arg_id.sym))
+                {
+                    Some(idx) => idx,
+                    None => return,
+                };
+                let (arg, arg_ty) = match (e.args.get(idx), arg_types.get(idx)) {
+                    (Some(arg), Some(arg_ty)) if arg.spread.is_none() => (arg, arg_ty),
+                    _ => return,
+                };
+
+                let ty = match &p.ty {
+                    Some(ty) => ty.clone().freezed(),
+                    // `asserts x` works like `if (!x) throw ...`, so `x` is narrowed to the truthy
+                    // part of its type.
+                    None => self.apply_type_facts_to_type(TypeFacts::Truthy, (*arg_ty.ty).clone()).freezed(),
+                };
+                self.store_assertion_fact(&arg.expr, &ty);
+            }
+        }
+    }
+
+    /// Narrows `expr` to `ty` for `asserts x is T`. Properties like `obj.prop`
+    /// and `this.x` are narrowed like variables.
+    fn store_assertion_fact(&mut self, expr: &RExpr, ty: &Type) {
+        match expr {
+            RExpr::Ident(var_name) => {
+                let ctx = Ctx {
+                    is_type_predicate: true,
+                    ..self.ctx
+                };
+                self.with_ctx(ctx).store_call_fact_for_var(var_name.span, var_name.into(), ty);
+            }
+            RExpr::This(this) => self.store_call_fact_for_var(this.span, Id::word("this".into()), ty),
+            _ => {
+                let name = match Name::try_from(expr) {
+                    Ok(name) => name,
+                    Err(..) => return,
+                };
+                let span = expr.span();
+
+                let narrowed = self
+                    .type_of_name(span, &name, TypeOfMode::RValue, None)
+                    .and_then(|orig_ty| self.narrow_with_predicate(span, &orig_ty, ty.clone()))
+                    .report(&mut self.storage);
+
+                if let Some(narrowed) = narrowed {
+                    self.add_deep_type_fact(span, name, narrowed.fixed().freezed(), true);
+                }
+            }
+        }
+    }
+
+    /// Assertion functions require the call target to be a dotted name, and
+    /// every name in it to be declared with an explicit type annotation.
+    fn validate_assertion_call_target(&mut self, span: Span, callee: &RExpr) -> VResult<()> {
+        match callee {
+            RExpr::Paren(RParenExpr { expr, .. }) => self.validate_assertion_call_target(span, expr),
+
+            RExpr::This(..)
+            | RExpr::SuperProp(RSuperPropExpr {
+                prop: RSuperProp::Ident(..),
+                ..
+            }) => Ok(()),
+
+            RExpr::Ident(i) => {
+                if self.data.vars_without_type_ann.contains(&Id::from(i)) {
+                    return Err(ErrorKind::AssertionCalleeWithoutTypeAnn { span }.into());
+                }
+
+                Ok(())
+            }
+
+            RExpr::Member(RMemberExpr {
+                obj,
+                prop: RMemberProp::Ident(prop),
+                ..
+            }) => {
+                if self.is_class_prop_without_type_ann(obj, &prop.sym) {
+                    return Err(ErrorKind::AssertionCalleeWithoutTypeAnn { span }.into());
+                }
+
+                self.validate_assertion_call_target(span, obj)
+            }
+
+            _ => Err(ErrorKind::AssertionCalleeNotDottedName { span }.into()),
+        }
+    }
+
+    /// Returns `true` if `obj.prop` is a class property declared without a
+    /// type annotation, like `assert = (v: unknown): asserts v => {}`.
+    fn is_class_prop_without_type_ann(&mut self, obj: &RExpr, prop: &JsWord) -> bool {
+        let span_of_prop = |member: &ClassMember| match member {
+            ClassMember::Property(p) if matches!(&p.key, Key::Normal { sym, .. } if sym == prop) => Some(p.span),
+            _ => None,
+        };
+
+        if let RExpr::This(..) = obj {
+            return self
+                .scope
+                .class_members()
+                .iter()
+                .filter_map(|(_, member)| span_of_prop(member))
+                .any(|span| self.data.class_props_without_type_ann.contains(&span));
+        }
+
+        let ctx = Ctx {
+            ignore_errors: true,
+            ..self.ctx
+        };
+        let obj_ty = match obj.validate_with_default(&mut *self.with_ctx(ctx)) {
+            Ok(ty) => ty,
+            Err(..) => return false,
+        };
+        let obj_ty = match self.normalize(Some(obj.span()), Cow::Owned(obj_ty), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return false,
+        };
+
+        match obj_ty.normalize() {
+            Type::Class(Class { def, .. }) => def
+                .body
+                .iter()
+                .filter_map(span_of_prop)
+                .any(|span| self.data.class_props_without_type_ann.contains(&span)),
+            _ => false,
+        }
+    }
+
     fn narrow_with_predicate(&mut self, span: Span, orig_ty: &Type, new_ty: Type) -> VResult<Type> {
         let _tracing = dev_span!("narrow_with_predicate");
 
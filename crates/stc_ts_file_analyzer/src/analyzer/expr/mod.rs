@@ -326,6 +326,7 @@ impl Analyzer<'_, '_> {
         if !span.is_dummy() & !matches!(e, RExpr::Lit(..)) {
             self.dump_type(span, &ty);
         }
+        self.record_type(span, &ty);
 
         Ok(ty)
     }
@@ -362,6 +363,7 @@ impl Analyzer<'_, '_> {
                     };
 
                     left_i = Some(i.clone());
+                    analyzer.record_usage(i);
 
                     ty_of_left = analyzer
                         .type_of_var(i, TypeOfMode::LValue, None)
@@ -602,6 +604,9 @@ pub(crate) struct AccessPropertyOpts {
     /// `true` means that the provided [Key] is crated from a computed key.
     pub is_key_computed: bool,
 
+    /// `true` if the property is accessed like `a.b`.
+    pub is_dot_access: bool,
+
     /// `true` means parent type is union
     pub is_in_union: bool,
 
@@ -875,6 +880,44 @@ impl Analyzer<'_, '_> {
         false
     }
 
+    /// Returns the type of a property read using an index signature of which
+    /// type is `ty`.
+    fn access_index_signature(&mut self, span: Span, prop: &Key, ty: Type, type_mode: TypeOfMode, opts: AccessPropertyOpts) -> Type {
+        if self.rule().no_property_access_from_index_signature && opts.is_dot_access {
+            if let Key::Normal { span, sym } = prop {
+                self.storage.report(
+                    ErrorKind::PropertyAccessFromIndexSignature {
+                        span: *span,
+                        prop: sym.clone(),
+                    }
+                    .into(),
+                );
+            }
+        }
+
+        self.add_undefined_for_unchecked_index(span, ty, type_mode)
+    }
+
+    /// Adds `undefined` to the type of an element read using an index, if
+    /// `noUncheckedIndexedAccess` is enabled.
+    ///
+    /// This is idempotent, because a read of a rest element of a tuple is also
+    /// a read of an array.
+    fn add_undefined_for_unchecked_index(&self, span: Span, ty: Type, type_mode: TypeOfMode) -> Type {
+        if !self.rule().no_unchecked_indexed_access || type_mode != TypeOfMode::RValue || ty.is_any() || ty.is_undefined() {
+            return ty;
+        }
+        if let Type::Union(u) = ty.normalize() {
+            if u.types.iter().any(|ty| ty.is_undefined()) {
+                return ty;
+            }
+        }
+
+        let mut types = vec![ty, Type::undefined(span, Default::default())];
+        types.dedup_type();
+        Type::new_union(span, types)
+    }
+
     fn access_property_of_type_elements(
         &mut self,
         span: Span,
@@ -898,7 +941,9 @@ impl Analyzer<'_, '_> {
                             }
 
                             if let Some(ref type_ann) = p.type_ann {
-                                if p.optional {
+                                // With `exactOptionalPropertyTypes`, `undefined` cannot be written to
+                                // an optional property.
+                                if p.optional && !(type_mode == TypeOfMode::LValue && self.rule().exact_optional_property_types) {
                                     let mut types = vec![Type::undefined(span, Default::default()), *type_ann.clone()];
                                     types.dedup_type();
                                     matching_elements.push(Type::new_union(span, types));
@@ -1030,7 +1075,7 @@ impl Analyzer<'_, '_> {
                         }
 
                         if let Some(type_ann) = type_ann {
-                            return Ok(Some(*type_ann.clone()));
+                            return Ok(Some(self.access_index_signature(span, prop, *type_ann.clone(), type_mode, opts)));
                         }
                     }
                 }
@@ -1075,16 +1120,17 @@ impl Analyzer<'_, '_> {
                         return Err(ErrorKind::ReadOnly { span }.into());
                     }
                     if let Some(type_ann) = type_ann {
-                        return Ok(Some(*type_ann.clone()));
+                        return Ok(Some(self.access_index_signature(span, prop, *type_ann.clone(), type_mode, opts)));
                     }
 
                     return Ok(Some(Type::any(span, Default::default())));
                 }
 
                 if (**index_ty).type_eq(&*prop_ty) {
-                    return Ok(Some(
-                        type_ann.clone().map(|v| *v).unwrap_or_else(|| Type::any(span, Default::default())),
-                    ));
+                    return Ok(Some(match type_ann {
+                        Some(type_ann) => self.access_index_signature(span, prop, *type_ann.clone(), type_mode, opts),
+                        None => Type::any(span, Default::default()),
+                    }));
                 }
 
                 if let Type::EnumVariant(..) = prop_ty.normalize() {
@@ -2314,13 +2360,13 @@ impl Analyzer<'_, '_> {
                         })
                         | Type::Lit(LitType {
                             lit: RTsLit::Number(..), ..
-                        }) => return Ok(*elem_type),
+                        }) => return Ok(self.add_undefined_for_unchecked_index(span, *elem_type, type_mode)),
 
                         _ => {}
                     }
                 }
                 if let Key::Num(n) = prop {
-                    return Ok(*elem_type.clone());
+                    return Ok(self.add_undefined_for_unchecked_index(span, *elem_type.clone(), type_mode));
                 }
 
                 let array_ty = self.env.get_global_type(span, &js_word!("Array"))?;
@@ -2673,13 +2719,14 @@ impl Analyzer<'_, '_> {
                                             ),
                                             _ => Err(err),
                                         });
+                                    // Rest elements may not exist, like elements of arrays.
                                     // dbg!(&inner_result);
                                     if let Ok(ty) = inner_result {
-                                        return Ok(ty);
+                                        return Ok(self.add_undefined_for_unchecked_index(span, ty, type_mode));
                                     }
 
                                     // debug_assert!(rest_ty.ty.is_clone_cheap());
-                                    return Ok(*rest_ty.ty.clone());
+                                    return Ok(self.add_undefined_for_unchecked_index(span, *rest_ty.ty.clone(), type_mode));
                                 } else {
                                     unreachable!()
                                 }
@@ -2700,6 +2747,7 @@ impl Analyzer<'_, '_> {
                                 self.storage.report(
                                     ErrorKind::TupleIndexError {
                                         span: n.span(),
+                                        ty: box obj.clone(),
                                         index: v,
                                         len: elems.len() as u64,
                                     }
@@ -2715,6 +2763,7 @@ impl Analyzer<'_, '_> {
 
                             return Err(ErrorKind::TupleIndexError {
                                 span: n.span(),
+                                ty: box obj.clone(),
                                 index: v,
                                 len: elems.len() as u64,
                             }
@@ -3783,7 +3832,8 @@ impl Analyzer<'_, '_> {
                     }
                 }
 
-                self.storage.report(ErrorKind::BlockScopedVarUsedBeforeInit { span }.into())
+                self.storage
+                    .report(ErrorKind::BlockScopedVarUsedBeforeInit { span, name: i.into() }.into())
             })();
 
             if self.scope.can_access_declaring_regardless_of_context(&i.into()) {
@@ -4201,6 +4251,8 @@ impl Analyzer<'_, '_> {
 
         self.storage.report_all(errors);
 
+        self.complete_member(span, &obj_ty, prop);
+
         let mut prop = self
             .validate_key(
                 &match prop {
@@ -4237,6 +4289,7 @@ impl Analyzer<'_, '_> {
                 IdCtx::Var,
                 AccessPropertyOpts {
                     check_for_undefined_or_null: true,
+                    is_dot_access: !computed,
                     ..Default::default()
                 },
             )
@@ -4613,6 +4666,9 @@ impl Analyzer<'_, '_> {
         type_args: Option<&TypeParamInstantiation>,
         type_ann: Option<&Type>,
     ) -> VResult<Type> {
+        self.complete_ident(i);
+        self.record_usage(i);
+
         if i.sym == js_word!("undefined") {
             return Ok(Type::Keyword(KeywordType {
                 span: i.span.with_ctxt(SyntaxContext::empty()),
@@ -335,6 +335,10 @@ impl Analyzer<'_, '_> {
 
     /// TODO(kdy1): Handle recursive function
     fn visit_fn(&mut self, name: Option<&RIdent>, f: &RFunction, type_ann: Option<&Type>) -> Type {
+        if let Some(name) = name {
+            self.record_decl(name);
+        }
+
         let fn_ty: Result<_, _> = try {
             let no_implicit_any_span = name.as_ref().map(|name| name.span);
 
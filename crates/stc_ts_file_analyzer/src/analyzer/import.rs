@@ -1,7 +1,8 @@
 use rayon::prelude::*;
 use rnode::{Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RCallExpr, RCallee, RExportAll, RExpr, RImportDecl, RImportSpecifier, RLit, RModuleItem, RNamedExport, RStr, RTsExternalModuleRef,
+    RCallExpr, RCallee, RExportAll, RExpr, RImportDecl, RImportSpecifier, RLit, RModuleExportName, RModuleItem, RNamedExport, RStr,
+    RTsExternalModuleRef,
 };
 use stc_ts_errors::ErrorKind;
 use stc_ts_file_analyzer_macros::extra_validator;
@@ -17,9 +18,20 @@ use crate::{
     validator, DepInfo, VResult,
 };
 
+/// A name imported from another module.
+#[derive(Debug, Clone)]
+pub(super) struct ImportedName {
+    pub module: ModuleInfo,
+    /// The name exported by [ImportedName::module], which differs from the
+    /// local name for `import { a as b }`.
+    pub orig: JsWord,
+}
+
 impl Analyzer<'_, '_> {
     /// Returns `(dep_module, dep_types)` if an import is valid, and returns
-    /// `(cur_mod_id, empty_data)` on import errors.
+    /// `(cur_mod_id, any)` on import errors.
+    ///
+    /// `span` should be the span of the module specifier.
     ///
     /// TODO: Make this returns None when import failed
     pub(crate) fn get_imported_items(&mut self, span: Span, dst: &JsWord) -> (ModuleId, Type) {
@@ -29,7 +41,7 @@ impl Analyzer<'_, '_> {
         let dep_id = match dep_id {
             Some(v) => v,
             None => {
-                self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                self.storage.report(ErrorKind::ModuleNotFound { span, name: dst.clone() }.into());
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -37,7 +49,7 @@ impl Analyzer<'_, '_> {
         let data = match self.data.imports.get(&(ctxt, dep_id)).cloned() {
             Some(v) => v,
             None => {
-                self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                self.storage.report(ErrorKind::ModuleNotFound { span, name: dst.clone() }.into());
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -47,10 +59,10 @@ impl Analyzer<'_, '_> {
     }
 
     pub(super) fn find_imported_var(&self, id: &Id) -> VResult<Option<Type>> {
-        if let Some(ModuleInfo { module_id, data }) = self.data.imports_by_id.get(id) {
-            match data.normalize() {
+        if let Some(ImportedName { module, orig }) = self.data.imports_by_id.get(id) {
+            match module.data.normalize() {
                 Type::Module(data) => {
-                    if let Some(dep) = data.exports.vars.get(id.sym()).cloned() {
+                    if let Some(dep) = data.exports.vars.get(orig).cloned() {
                         debug_assert!(dep.is_clone_cheap());
 
                         return Ok(Some(dep));
@@ -82,16 +94,12 @@ impl Analyzer<'_, '_> {
         let loader = self.loader;
         let mut normal_imports = vec![];
         for (ctxt, import) in imports {
-            let span = import.span;
-
             let base = self.storage.path(ctxt);
             let dep_id = self.loader.module_id(&base, &import.src);
             let dep_id = match dep_id {
                 Some(v) => v,
-                None => {
-                    self.storage.report(ErrorKind::ModuleNotFound { span }.into());
-                    continue;
-                }
+                // Reported while validating the import.
+                None => continue,
             };
 
             if loader.is_in_same_circular_group(&base, &import.src) {
@@ -146,7 +154,7 @@ impl Analyzer<'_, '_> {
 
         // Check for entry only if import was successful.
         if ctxt != target {
-            if let Some(data) = self.data.imports.get(&(ctxt, target)) {
+            if let Some(data) = self.data.imports.get(&(ctxt, target)).cloned() {
                 match data.normalize() {
                     Type::Module(data) => {
                         for (i, ty) in &data.exports.vars {
@@ -169,6 +177,16 @@ impl Analyzer<'_, '_> {
                         unreachable!()
                     }
                 }
+
+                if found_entry {
+                    self.data.imports_by_id.insert(
+                        id.clone(),
+                        ImportedName {
+                            module: ModuleInfo { module_id: target, data },
+                            orig: orig.sym().clone(),
+                        },
+                    );
+                }
             }
         }
 
@@ -200,15 +218,20 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RImportDecl) {
-        let span = node.span;
         let base = self.ctx.module_id;
 
-        let (dep, data) = self.get_imported_items(span, &node.src.value);
+        let (dep, data) = self.get_imported_items(node.src.span, &node.src.value);
 
         for specifier in &node.specifiers {
             match specifier {
                 RImportSpecifier::Named(named) => {
-                    //
+                    self.record_decl(&named.local);
+
+                    // `a` in `import { a as b }` is not resolved, but it's the same symbol.
+                    if let Some(RModuleExportName::Ident(imported)) = &named.imported {
+                        self.record_ident(imported, Id::from(&named.local), false);
+                    }
+
                     match &named.imported {
                         Some(imported) => {
                             self.handle_import(named.span, base, dep, Id::from(imported), Id::from(&named.local));
@@ -219,9 +242,12 @@ impl Analyzer<'_, '_> {
                     }
                 }
                 RImportSpecifier::Default(default) => {
+                    self.record_decl(&default.local);
                     self.handle_import(default.span, base, dep, Id::word(js_word!("default")), Id::from(&default.local));
                 }
                 RImportSpecifier::Namespace(ns) => {
+                    self.record_decl(&ns.local);
+
                     if base == dep {
                         // Import failed
                         self.declare_var(
@@ -276,8 +302,10 @@ where
         let ctxt = self.cur_ctxt;
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.to
-            .extend(deps.into_iter().map(|src| (ctxt, DepInfo { span, src: src.to_path() })));
+        self.to.extend(
+            deps.into_iter()
+                .filter_map(|src| Some((ctxt, DepInfo { span, src: src.to_path()? }))),
+        );
     }
 
     pub fn find_imports<T>(comments: C, module_span: Vec<(ModuleId, Span)>, storage: &'a Storage<'a>, node: &T) -> Vec<(ModuleId, DepInfo)>
@@ -333,16 +361,15 @@ where
 
         match &expr.callee {
             RCallee::Expr(box RExpr::Ident(i)) if i.sym == js_word!("require") => {
-                let src = expr
-                    .args
-                    .iter()
-                    .map(|v| match *v.expr {
-                        RExpr::Lit(RLit::Str(RStr { ref value, .. })) => value.clone(),
-                        _ => unimplemented!("error reporting for dynamic require"),
-                    })
-                    .next()
-                    .unwrap();
-                self.to.push((self.cur_ctxt, DepInfo { span, src }));
+                // Dynamic requires are not followed.
+                let src = expr.args.first().and_then(|v| match *v.expr {
+                    RExpr::Lit(RLit::Str(RStr { ref value, .. })) => Some(value.clone()),
+                    _ => None,
+                });
+
+                if let Some(src) = src {
+                    self.to.push((self.cur_ctxt, DepInfo { span, src }));
+                }
             }
             RCallee::Import(import) => {
                 let src = expr.args.first().and_then(|v| match *v.expr {
@@ -10,7 +10,7 @@ use std::{
 use fxhash::{FxHashMap, FxHashSet};
 use rnode::VisitWith;
 use stc_ts_ast_rnode::{
-    RDecorator, RModule, RModuleDecl, RModuleItem, RScript, RStmt, RStr, RTsImportEqualsDecl, RTsModuleBlock, RTsModuleDecl, RTsModuleName,
+    RModule, RModuleDecl, RModuleItem, RScript, RStmt, RStr, RTsImportEqualsDecl, RTsModuleBlock, RTsModuleDecl, RTsModuleName,
     RTsModuleRef, RTsNamespaceDecl,
 };
 use stc_ts_base_type_ops::bindings::Bindings;
@@ -28,20 +28,13 @@ use swc_ecma_ast::*;
 
 use self::{
     control_flow::{CondFacts, Facts},
+    import::ImportedName,
     pat::PatMode,
     props::ComputedPropMode,
     scope::{Scope, VarKind},
-    util::ResultExt,
 };
 pub(crate) use self::{scope::ScopeKind, types::NormalizeTypeOpts};
-use crate::{
-    loader::{Load, ModuleInfo},
-    ty,
-    ty::Type,
-    validator,
-    validator::ValidateWith,
-    VResult,
-};
+use crate::{loader::Load, recorder::Recorder, ty, ty::Type, validator, VResult};
 
 macro_rules! try_opt {
     ($e:expr) => {{
@@ -54,6 +47,7 @@ macro_rules! try_opt {
 
 mod assign;
 mod class;
+mod completion;
 mod control_flow;
 mod convert;
 mod decl_merging;
@@ -184,11 +178,20 @@ pub(crate) struct Ctx {
     /// Should be modified directly instead of using `with_ctx`.
     in_unreachable: bool,
 
+    /// `true` after `return`, `throw`, `break` or `continue`.
+    ///
+    /// Unlike `in_unreachable`, this is not used to report unreachable code.
+    /// Should be modified directly instead of using `with_ctx`.
+    after_jump: bool,
+
     /// `true` for top-level type annotations.
     is_not_topmost_type: bool,
 
     is_fn_param: bool,
 
+    /// `true` while validating the parameter of a catch clause.
+    is_catch_param: bool,
+
     in_module: bool,
 
     checking_switch_discriminant_as_bin: bool,
@@ -201,6 +204,12 @@ pub(crate) struct Ctx {
     is_type_ann_for_call_reeval_chosen_from_overload: bool,
 
     is_type_predicate: bool,
+
+    /// Span of the call expression used as an expression statement.
+    ///
+    /// Assertion functions narrow types only if they are called as a
+    /// statement.
+    call_stmt_span: Option<Span>,
 }
 
 impl Ctx {
@@ -246,6 +255,8 @@ pub struct Analyzer<'scope, 'b> {
 
     debugger: Option<Debugger>,
 
+    recorder: Option<Recorder>,
+
     data: Box<AnalyzerData>,
 
     destructure_count: Rc<Cell<DestructureId>>,
@@ -254,7 +265,8 @@ pub struct Analyzer<'scope, 'b> {
 /// This type **should be boxed** for performance.
 #[derive(Debug, Default)]
 struct AnalyzerData {
-    imports_by_id: FxHashMap<Id, ModuleInfo>,
+    /// Local names of imports, like `b` in `import { a as b } from './foo'`.
+    imports_by_id: FxHashMap<Id, ImportedName>,
 
     /// Value should [Type::Arc] of [Type::Module]
     imports: FxHashMap<(ModuleId, ModuleId), Type>,
@@ -287,6 +299,16 @@ struct AnalyzerData {
     /// Spans of functions **with body**.
     fn_impl_spans: FxHashMap<Id, Vec<Span>>,
 
+    /// Variables and parameters declared without a type annotation.
+    ///
+    /// Used to check call targets of assertion functions.
+    vars_without_type_ann: FxHashSet<Id>,
+
+    /// Spans of class properties declared without a type annotation.
+    ///
+    /// Used to check call targets of assertion functions.
+    class_props_without_type_ann: FxHashSet<Span>,
+
     /// One instance of each module (typescript `module` keyword).
     for_module: PerModuleData,
 
@@ -417,10 +439,16 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             Scope::root(),
             false,
             debugger,
+            None,
             Default::default(),
         )
     }
 
+    /// Records types and imports to `recorder`, for editor integrations.
+    pub fn set_recorder(&mut self, recorder: Option<Recorder>) {
+        self.recorder = recorder;
+    }
+
     pub(crate) fn for_builtin(env: StableEnv, storage: &'b mut Builtin) -> Self {
         Self::new_inner(
             Env::new(env, Default::default(), EsVersion::latest(), ModuleConfig::None, Default::default()),
@@ -432,6 +460,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             Scope::root(),
             true,
             None,
+            None,
             Default::default(),
         )
     }
@@ -448,6 +477,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             scope,
             self.config.is_builtin,
             self.debugger.clone(),
+            self.recorder.clone(),
             data,
         )
     }
@@ -462,6 +492,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         scope: Scope<'scope>,
         is_builtin: bool,
         debugger: Option<Debugger>,
+        recorder: Option<Recorder>,
         data: Box<AnalyzerData>,
     ) -> Self {
         let is_dts = storage.is_dts();
@@ -526,19 +557,23 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 allow_new_target: false,
                 disallow_suggesting_property_on_no_var: false,
                 in_unreachable: false,
+                after_jump: false,
                 is_not_topmost_type: false,
                 is_fn_param: false,
+                is_catch_param: false,
                 in_module: false,
                 checking_switch_discriminant_as_bin: false,
                 obj_is_super: false,
                 use_properties_of_this_implicitly: false,
                 is_type_ann_for_call_reeval_chosen_from_overload: false,
                 is_type_predicate: false,
+                call_stmt_span: None,
             },
             loader,
             cur_facts: Default::default(),
             mapped_type_param_name: vec![],
             debugger,
+            recorder,
             data,
             destructure_count: Default::default(),
         }
@@ -723,7 +758,7 @@ impl Load for NoopLoader {
         unreachable!()
     }
 
-    fn declare_module(&self, name: &JsWord, module: Type) {
+    fn declare_module(&self, base: &Arc<FileName>, name: &JsWord, module: Type) {
         unreachable!()
     }
 }
@@ -744,6 +779,12 @@ impl Analyzer<'_, '_> {
 
         self.validate_stmts_with_hoisting(&items);
 
+        for m in modules {
+            self.report_unused_decls(m);
+        }
+
+        self.record_imports();
+
         Ok(())
     }
 }
@@ -806,6 +847,8 @@ impl Analyzer<'_, '_> {
             m.body.visit_children_with(self);
         } else {
             self.validate_stmts_and_collect(&items_ref);
+            self.report_unused_decls(m);
+            self.record_imports();
         }
 
         Ok(())
@@ -825,16 +868,6 @@ impl Analyzer<'_, '_> {
     }
 }
 
-/// Done
-#[validator]
-impl Analyzer<'_, '_> {
-    fn validate(&mut self, d: &RDecorator) {
-        d.expr.validate_with_default(self).report(&mut self.storage);
-
-        Ok(())
-    }
-}
-
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RTsImportEqualsDecl) {
@@ -868,7 +901,7 @@ impl Analyzer<'_, '_> {
                     })
                     .freezed(),
                 RTsModuleRef::TsExternalModuleRef(ref e) => {
-                    let (dep, data) = analyzer.get_imported_items(e.span, &e.expr.value);
+                    let (dep, data) = analyzer.get_imported_items(e.expr.span, &e.expr.value);
 
                     // Import successful
                     if ctxt != dep {
@@ -886,6 +919,8 @@ impl Analyzer<'_, '_> {
             ty.assert_clone_cheap();
             ty.assert_valid();
 
+            analyzer.record_decl(&node.id);
+
             let (is_type, is_var) = match ty.normalize() {
                 Type::Module(..) | Type::Namespace(..) | Type::Interface(..) => (true, false),
                 Type::ClassDef(..) => (true, true),
@@ -978,6 +1013,10 @@ impl Analyzer<'_, '_> {
         let ctxt = self.ctx.module_id;
         let global = decl.global;
 
+        if let RTsModuleName::Ident(i) = &decl.id {
+            self.record_decl(i);
+        }
+
         let ctx = Ctx {
             in_global: global,
             in_declare: self.ctx.in_declare || decl.declare,
@@ -1048,7 +1087,8 @@ impl Analyzer<'_, '_> {
                         }
                     }
 
-                    self.loader.declare_module(&s.value, ty.clone());
+                    let base = self.storage.path(ctxt);
+                    self.loader.declare_module(&base, &s.value, ty.clone());
                 }
             }
         }
@@ -8,7 +8,7 @@ use stc_ts_types::{
     Array, ArrayMetadata, CommonTypeMetadata, Instance, Key, KeywordType, PropertySignature, RestType, Tuple, TupleElement, TypeElMetadata,
     TypeElement, TypeLit, TypeLitMetadata,
 };
-use stc_ts_utils::PatExt;
+use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt};
 use swc_atoms::js_word;
 use swc_common::{Spanned, TypeEq, DUMMY_SP};
@@ -166,10 +166,16 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RParam) -> VResult<ty::FnParam> {
-        node.decorators.visit_with(self);
-
         self.default_any_pat(&node.pat);
 
+        let type_ann = node.pat.get_ty().or_else(|| match &node.pat {
+            RPat::Assign(p) => p.left.get_ty(),
+            _ => None,
+        });
+        if type_ann.is_none() {
+            self.data.vars_without_type_ann.extend(find_ids_in_pat(&node.pat));
+        }
+
         let ctx = Ctx {
             pat_mode: PatMode::Decl,
             ..self.ctx
@@ -242,6 +248,13 @@ impl Analyzer<'_, '_> {
             .transpose()?
             .freezed();
 
+        // https://www.typescriptlang.org/tsconfig#useUnknownInCatchVariables
+        if ty.is_none() && self.ctx.is_catch_param && self.rule().use_unknown_in_catch_variables {
+            if let RPat::Ident(i) = p {
+                ty = Some(Type::unknown(i.id.span, Default::default()));
+            }
+        }
+
         let prev_declaring_len = self.scope.declaring.len();
         // Declaring names
         let mut names = vec![];
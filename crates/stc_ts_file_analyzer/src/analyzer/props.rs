@@ -330,6 +330,8 @@ impl Analyzer<'_, '_> {
 
         let shorthand_type_ann = match prop {
             RProp::Shorthand(i) => {
+                self.record_usage(i);
+
                 // TODO(kdy1): Check if RValue is correct
                 self.type_of_var(i, TypeOfMode::RValue, None)
                     .report(&mut self.storage)
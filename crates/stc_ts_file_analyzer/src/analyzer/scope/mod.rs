@@ -46,7 +46,6 @@ use crate::{
         stmt::return_type::ReturnValues,
         Analyzer, Ctx, ResultExt,
     },
-    loader::ModuleInfo,
     ty::{self, Alias, Interface, Ref, Tuple, Type, TypeLit, Union},
     type_facts::TypeFacts,
     util::contains_infer_type,
@@ -55,6 +54,7 @@ use crate::{
 
 mod this;
 mod type_param;
+mod unused;
 pub(crate) mod vars;
 
 macro_rules! no_ref {
@@ -666,6 +666,26 @@ impl Scope<'_> {
         None
     }
 
+    /// Returns variables declared in this scope and its parents, starting from
+    /// the innermost scope.
+    pub fn visible_vars(&self) -> Vec<(&Id, &VarInfo)> {
+        let mut vars = self.vars.iter().collect::<Vec<_>>();
+        if let Some(parent) = self.parent {
+            vars.extend(parent.visible_vars());
+        }
+        vars
+    }
+
+    /// Returns types declared in this scope and its parents, starting from the
+    /// innermost scope.
+    pub fn visible_types(&self) -> Vec<(&Id, &Type)> {
+        let mut types = self.types.iter().chain(self.type_params.iter()).collect::<Vec<_>>();
+        if let Some(parent) = self.parent {
+            types.extend(parent.visible_types());
+        }
+        types
+    }
+
     pub fn mark_as_super_called(&self) {
         if self.kind == ScopeKind::Class {
             *self.class.need_super_call.borrow_mut() = false;
@@ -1052,10 +1072,10 @@ impl Analyzer<'_, '_> {
 
             {
                 // Imported variables
-                if let Some(info) = self.data.imports_by_id.get(name) {
-                    match info.data.normalize() {
+                if let Some(import) = self.data.imports_by_id.get(name) {
+                    match import.module.data.normalize() {
                         Type::Module(data) => {
-                            if let Some(var_ty) = data.exports.vars.get(name.sym()) {
+                            if let Some(var_ty) = data.exports.vars.get(&import.orig) {
                                 if cfg!(debug_assertions) {
                                     debug!("Scope.find_var_type({}): Handled with imports", name);
                                 }
@@ -1137,10 +1157,10 @@ impl Analyzer<'_, '_> {
             return Ok(Some(v));
         }
 
-        if let Some(ModuleInfo { data, .. }) = self.data.imports_by_id.get(name) {
-            match data.normalize() {
+        if let Some(import) = self.data.imports_by_id.get(name) {
+            match import.module.data.normalize() {
                 Type::Module(data) => {
-                    if let Some(types) = data.exports.types.get(name.sym()) {
+                    if let Some(types) = data.exports.types.get(&import.orig) {
                         let types = types.clone();
                         return Ok(Some(ItemRef::Owned(types.into_iter())));
                     }
@@ -1434,6 +1454,10 @@ impl Analyzer<'_, '_> {
             ty.assert_valid();
         }
 
+        if let Some(ty) = ty.as_ref().or(actual_ty.as_ref()) {
+            self.record_type(span, ty);
+        }
+
         if self.ctx.in_global {
             match kind {
                 VarKind::Var(_) | VarKind::Class | VarKind::Fn | VarKind::Enum => {
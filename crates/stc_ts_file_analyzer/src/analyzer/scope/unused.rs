@@ -0,0 +1,975 @@
+//! Usage tracking for `noUnusedLocals` and `noUnusedParameters`.
+//!
+//! The resolver of swc gives a binding and all references to it the same
+//! [Id], so usages are tracked syntactically. This runs once per module and
+//! does not depend on how many times the analyzer visits a node.
+//!
+//! Reads are not recorded on the variables of [Scope](super::Scope), because
+//! - the analyzer visits a node multiple times, for loop bodies and while
+//!   selecting overloads, and some of the visits happen in scopes which are
+//!   dropped;
+//! - interfaces, type aliases, enums, namespaces, imports and private class
+//!   members are not variables of a scope.
+
+use std::mem::{replace, take};
+
+use fxhash::{FxHashMap, FxHashSet};
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RAssignExpr, RClass, RClassMember, RConstructor, RDecl, RDefaultDecl, RExportSpecifier, RExpr, RExprStmt, RForInStmt,
+    RForOfStmt, RFunction, RGetterProp, RIdent, RImportDecl, RImportSpecifier, RJSXElementName, RJSXFragment, RJSXObject, RLit,
+    RMemberExpr, RMemberProp, RModule, RModuleDecl, RModuleExportName, RModuleItem, RNamedExport, RObjectPatProp, RParamOrTsParamProp,
+    RPat, RPatOrExpr, RProp, RPropName, RSetterProp, RStaticBlock, RTsEntityName, RTsGetterSignature, RTsImportEqualsDecl, RTsImportType,
+    RTsInterfaceDecl, RTsMethodSignature, RTsModuleDecl, RTsModuleName, RTsParamPropParam, RTsPropertySignature, RTsSetterSignature,
+    RTsTypeAliasDecl, RTsTypeParamDecl, RVarDecl, RVarDeclOrPat,
+};
+use stc_ts_env::{JsxMode, Rule};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::Id;
+use swc_atoms::{js_word, JsWord};
+use swc_common::Span;
+use swc_ecma_ast::{Accessibility, AssignOp, MethodKind};
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    /// Reports declarations of `m` which are never read, if `noUnusedLocals`
+    /// or `noUnusedParameters` is enabled.
+    pub(crate) fn report_unused_decls(&mut self, m: &RModule) {
+        let rule = self.rule();
+        if self.config.is_builtin || self.config.is_dts || !(rule.no_unused_locals || rule.no_unused_parameters) {
+            return;
+        }
+
+        // Top-level declarations of a script are globals.
+        let is_module = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
+
+        let mut finder = UsageFinder {
+            is_global: !is_module,
+            ..Default::default()
+        };
+        m.body.visit_with(&mut finder);
+
+        for err in finder.into_errors(rule) {
+            self.storage.report(err);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclKind {
+    /// Variables, functions and namespaces.
+    Value,
+    /// Classes, interfaces, type aliases and enums.
+    Type,
+    Import {
+        type_only: bool,
+    },
+    Param,
+    TypeParam,
+}
+
+impl DeclKind {
+    fn is_enabled(self, rule: Rule) -> bool {
+        match self {
+            DeclKind::Param | DeclKind::TypeParam => rule.no_unused_parameters,
+            _ => rule.no_unused_locals,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Decl {
+    id: Id,
+    span: Span,
+    kind: DeclKind,
+    group: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GroupKind {
+    Imports,
+    Vars,
+    Destructuring,
+    TypeParams,
+}
+
+/// Declarations which are reported with one error if all of them are unused.
+#[derive(Debug)]
+struct Group {
+    kind: GroupKind,
+    span: Span,
+    decl_kind: DeclKind,
+    /// The number of elements, including ones which are not declarations.
+    len: usize,
+    /// The variable declaration of a top-level binding pattern.
+    parent: Option<usize>,
+    /// The interface owning type parameters.
+    owner: Option<Id>,
+    unused: Vec<Unused>,
+}
+
+#[derive(Debug)]
+struct Unused {
+    name: JsWord,
+    span: Span,
+    kind: DeclKind,
+}
+
+impl Unused {
+    fn into_error(self) -> Error {
+        match self.kind {
+            DeclKind::Type | DeclKind::Import { type_only: true } => ErrorKind::UnusedTypeDecl {
+                span: self.span,
+                name: self.name,
+            }
+            .into(),
+            _ => ErrorKind::UnusedDecl {
+                span: self.span,
+                name: self.name,
+            }
+            .into(),
+        }
+    }
+}
+
+/// Private members of a class.
+#[derive(Debug, Default)]
+struct ClassUsage {
+    members: Vec<PrivateMember>,
+    used: FxHashSet<JsWord>,
+    used_private_names: FxHashSet<JsWord>,
+}
+
+#[derive(Debug)]
+struct PrivateMember {
+    name: JsWord,
+    span: Span,
+    is_private_name: bool,
+    /// The kind of a method.
+    method_kind: Option<MethodKind>,
+    /// The binding of a parameter property.
+    param: Option<Id>,
+}
+
+#[derive(Debug, Default)]
+struct UsageFinder {
+    is_global: bool,
+    is_ambient: bool,
+
+    decls: Vec<Decl>,
+    groups: Vec<Group>,
+    used: FxHashSet<Id>,
+
+    /// Declarations being visited. References in their bodies are not
+    /// usages.
+    declaring: Vec<Id>,
+    interfaces: FxHashMap<Id, usize>,
+
+    classes: Vec<ClassUsage>,
+    unused_members: Vec<Error>,
+
+    has_jsx: bool,
+}
+
+impl UsageFinder {
+    fn declare(&mut self, i: &RIdent, kind: DeclKind, group: Option<usize>) {
+        self.decls.push(Decl {
+            id: i.into(),
+            span: i.span,
+            kind,
+            group,
+        });
+    }
+
+    fn new_group(&mut self, kind: GroupKind, span: Span, decl_kind: DeclKind, len: usize, parent: Option<usize>) -> usize {
+        self.groups.push(Group {
+            kind,
+            span,
+            decl_kind,
+            len,
+            parent,
+            owner: None,
+            unused: vec![],
+        });
+        self.groups.len() - 1
+    }
+
+    fn use_id(&mut self, i: &RIdent) {
+        let id = Id::from(i);
+        if self.declaring.contains(&id) {
+            return;
+        }
+        self.used.insert(id);
+    }
+
+    fn use_member(&mut self, name: &JsWord, is_private_name: bool) {
+        for class in &mut self.classes {
+            if is_private_name {
+                class.used_private_names.insert(name.clone());
+            } else {
+                class.used.insert(name.clone());
+            }
+        }
+    }
+
+    fn with_declaring(&mut self, i: &RIdent, op: impl FnOnce(&mut Self)) {
+        self.declaring.push(i.into());
+        op(self);
+        self.declaring.pop();
+    }
+
+    fn with_ambient(&mut self, ambient: bool, op: impl FnOnce(&mut Self)) {
+        let is_ambient = self.is_ambient;
+        self.is_ambient |= ambient;
+        op(self);
+        self.is_ambient = is_ambient;
+    }
+
+    fn in_local_scope(&mut self, op: impl FnOnce(&mut Self)) {
+        let is_global = replace(&mut self.is_global, false);
+        op(self);
+        self.is_global = is_global;
+    }
+
+    fn visit_decl(&mut self, decl: &RDecl, exported: bool) {
+        let declares = !exported && !self.is_global && !self.is_ambient;
+
+        match decl {
+            RDecl::Class(c) => {
+                if declares && !c.declare {
+                    self.declare(&c.ident, DeclKind::Type, None);
+                }
+                self.with_ambient(c.declare, |v| v.with_declaring(&c.ident, |v| c.class.visit_with(v)));
+            }
+            RDecl::Fn(f) => {
+                if declares && !f.declare {
+                    self.declare(&f.ident, DeclKind::Value, None);
+                }
+                self.with_ambient(f.declare, |v| v.with_declaring(&f.ident, |v| f.function.visit_with(v)));
+            }
+            RDecl::Var(var) => {
+                self.with_ambient(var.declare, |v| v.visit_var_decl(var, declares, false));
+            }
+            RDecl::TsInterface(i) => self.visit_interface(i, declares),
+            RDecl::TsTypeAlias(a) => self.visit_type_alias(a, declares),
+            RDecl::TsEnum(e) => {
+                if declares && !e.declare {
+                    self.declare(&e.id, DeclKind::Type, None);
+                }
+                self.with_declaring(&e.id, |v| e.members.visit_with(v));
+            }
+            RDecl::TsModule(m) => self.visit_ts_module_decl(m, declares),
+        }
+    }
+
+    fn visit_var_decl(&mut self, var: &RVarDecl, declares: bool, is_for_in_of: bool) {
+        let declares = declares && !var.declare && !self.is_global && !self.is_ambient;
+        let group = declares.then(|| self.new_group(GroupKind::Vars, var.span, DeclKind::Value, var.decls.len(), None));
+
+        for decl in &var.decls {
+            if declares {
+                self.declare_binding(&decl.name, group, DeclKind::Value, is_for_in_of);
+            }
+            decl.visit_children_with(self);
+        }
+    }
+
+    fn visit_interface(&mut self, i: &RTsInterfaceDecl, declares: bool) {
+        *self.interfaces.entry(Id::from(&i.id)).or_default() += 1;
+
+        if declares && !i.declare {
+            self.declare(&i.id, DeclKind::Type, None);
+        }
+        if !i.declare && !self.is_ambient {
+            self.declare_type_params(i.type_params.as_deref(), Some(Id::from(&i.id)));
+        }
+
+        self.with_declaring(&i.id, |v| {
+            i.type_params.visit_with(v);
+            i.extends.visit_with(v);
+            i.body.visit_with(v);
+        });
+    }
+
+    fn visit_type_alias(&mut self, a: &RTsTypeAliasDecl, declares: bool) {
+        if declares && !a.declare {
+            self.declare(&a.id, DeclKind::Type, None);
+        }
+        if !a.declare && !self.is_ambient {
+            self.declare_type_params(a.type_params.as_deref(), None);
+        }
+
+        self.with_declaring(&a.id, |v| {
+            a.type_params.visit_with(v);
+            a.type_ann.visit_with(v);
+        });
+    }
+
+    fn visit_ts_module_decl(&mut self, m: &RTsModuleDecl, declares: bool) {
+        let ambient = m.declare || m.global || matches!(m.id, RTsModuleName::Str(..));
+
+        match &m.id {
+            RTsModuleName::Ident(i) => {
+                if declares && !ambient {
+                    self.declare(i, DeclKind::Value, None);
+                }
+                self.with_ambient(ambient, |v| v.with_declaring(i, |v| v.in_local_scope(|v| m.body.visit_with(v))));
+            }
+            RTsModuleName::Str(..) => {
+                self.with_ambient(ambient, |v| v.in_local_scope(|v| m.body.visit_with(v)));
+            }
+        }
+    }
+
+    /// Declares a variable or a parameter.
+    fn declare_binding(&mut self, pat: &RPat, group: Option<usize>, kind: DeclKind, is_for_in_of: bool) {
+        match pat {
+            RPat::Ident(i) => {
+                let exempt = (kind == DeclKind::Param || is_for_in_of) && i.id.sym.starts_with('_');
+                if !exempt {
+                    self.declare(&i.id, kind, group);
+                }
+            }
+            RPat::Assign(p) => self.declare_binding(&p.left, group, kind, is_for_in_of),
+            RPat::Rest(p) => self.declare_binding(&p.arg, group, kind, is_for_in_of),
+            RPat::Array(..) | RPat::Object(..) => self.declare_destructuring(pat, group, kind),
+            RPat::Invalid(..) | RPat::Expr(..) => {}
+        }
+    }
+
+    fn declare_destructuring(&mut self, pat: &RPat, parent: Option<usize>, kind: DeclKind) {
+        match pat {
+            RPat::Array(a) => {
+                let group = self.new_group(GroupKind::Destructuring, a.span, kind, a.elems.len(), parent);
+                for elem in a.elems.iter().flatten() {
+                    self.declare_element(elem, group, kind, true);
+                }
+            }
+            RPat::Object(o) => {
+                let group = self.new_group(GroupKind::Destructuring, o.span, kind, o.props.len(), parent);
+                // Elements are used to exclude properties from the rest element.
+                let has_rest = matches!(o.props.last(), Some(RObjectPatProp::Rest(..)));
+
+                for prop in &o.props {
+                    match prop {
+                        RObjectPatProp::KeyValue(p) => match &*p.value {
+                            RPat::Array(..) | RPat::Object(..) => self.declare_destructuring(&p.value, None, kind),
+                            _ if has_rest => {}
+                            value => self.declare_element(value, group, kind, true),
+                        },
+                        RObjectPatProp::Assign(p) => {
+                            if !has_rest && !(kind == DeclKind::Param && p.key.sym.starts_with('_')) {
+                                self.declare(&p.key, kind, Some(group));
+                            }
+                        }
+                        RObjectPatProp::Rest(p) => self.declare_element(&p.arg, group, kind, false),
+                    }
+                }
+            }
+            _ => self.declare_binding(pat, parent, kind, false),
+        }
+    }
+
+    fn declare_element(&mut self, pat: &RPat, group: usize, kind: DeclKind, allow_underscore: bool) {
+        match pat {
+            RPat::Ident(i) => {
+                let exempt = (allow_underscore || kind == DeclKind::Param) && i.id.sym.starts_with('_');
+                if !exempt {
+                    self.declare(&i.id, kind, Some(group));
+                }
+            }
+            RPat::Assign(p) => self.declare_element(&p.left, group, kind, allow_underscore),
+            RPat::Rest(p) => self.declare_element(&p.arg, group, kind, allow_underscore),
+            RPat::Array(..) | RPat::Object(..) => self.declare_destructuring(pat, None, kind),
+            RPat::Invalid(..) | RPat::Expr(..) => {}
+        }
+    }
+
+    fn declare_param(&mut self, pat: &RPat) {
+        if self.is_ambient {
+            return;
+        }
+        if let RPat::Ident(i) = pat {
+            if i.id.sym == js_word!("this") {
+                return;
+            }
+        }
+
+        self.declare_binding(pat, None, DeclKind::Param, false);
+    }
+
+    fn declare_type_params(&mut self, params: Option<&RTsTypeParamDecl>, owner: Option<Id>) {
+        let params = match params {
+            Some(v) if !self.is_ambient => v,
+            _ => return,
+        };
+
+        let group = self.new_group(GroupKind::TypeParams, params.span, DeclKind::TypeParam, params.params.len(), None);
+        self.groups[group].owner = owner;
+
+        for param in &params.params {
+            if !param.name.sym.starts_with('_') {
+                self.declare(&param.name, DeclKind::TypeParam, Some(group));
+            }
+        }
+    }
+
+    /// Visits the target of an assignment, which is not a read.
+    fn visit_write_target(&mut self, e: &RExpr) {
+        match e {
+            RExpr::Ident(..) => {}
+            RExpr::Paren(p) => self.visit_write_target(&p.expr),
+            RExpr::Member(m) => {
+                m.obj.visit_with(self);
+                if let RMemberProp::Computed(c) = &m.prop {
+                    c.visit_with(self);
+                }
+            }
+            _ => e.visit_with(self),
+        }
+    }
+
+    fn visit_assign_target(&mut self, left: &RPatOrExpr, is_write_only: bool) {
+        match left {
+            RPatOrExpr::Expr(e) if is_write_only => self.visit_write_target(e),
+            RPatOrExpr::Expr(e) => e.visit_with(self),
+            RPatOrExpr::Pat(p) if is_write_only => p.visit_with(self),
+            RPatOrExpr::Pat(p) => match &**p {
+                RPat::Ident(i) => self.use_id(&i.id),
+                RPat::Expr(e) => e.visit_with(self),
+                _ => p.visit_with(self),
+            },
+        }
+    }
+
+    fn collect_private_members(&self, c: &RClass) -> Vec<PrivateMember> {
+        let mut members = vec![];
+
+        for member in &c.body {
+            match member {
+                RClassMember::ClassProp(p) if p.accessibility == Some(Accessibility::Private) => {
+                    if let Some((name, span)) = prop_name(&p.key) {
+                        members.push(PrivateMember {
+                            name,
+                            span,
+                            is_private_name: false,
+                            method_kind: None,
+                            param: None,
+                        });
+                    }
+                }
+                RClassMember::Method(m) if m.accessibility == Some(Accessibility::Private) => {
+                    if let Some((name, span)) = prop_name(&m.key) {
+                        members.push(PrivateMember {
+                            name,
+                            span,
+                            is_private_name: false,
+                            method_kind: Some(m.kind),
+                            param: None,
+                        });
+                    }
+                }
+                RClassMember::PrivateProp(p) => members.push(PrivateMember {
+                    name: p.key.id.sym.clone(),
+                    span: p.key.span,
+                    is_private_name: true,
+                    method_kind: None,
+                    param: None,
+                }),
+                RClassMember::PrivateMethod(m) => members.push(PrivateMember {
+                    name: m.key.id.sym.clone(),
+                    span: m.key.span,
+                    is_private_name: true,
+                    method_kind: Some(m.kind),
+                    param: None,
+                }),
+                RClassMember::Constructor(c) if c.body.is_some() => {
+                    for param in &c.params {
+                        let param = match param {
+                            RParamOrTsParamProp::TsParamProp(p) if p.accessibility == Some(Accessibility::Private) => &p.param,
+                            _ => continue,
+                        };
+                        let i = match param {
+                            RTsParamPropParam::Ident(i) => &i.id,
+                            RTsParamPropParam::Assign(p) => match &*p.left {
+                                RPat::Ident(i) => &i.id,
+                                _ => continue,
+                            },
+                        };
+                        members.push(PrivateMember {
+                            name: i.sym.clone(),
+                            span: i.span,
+                            is_private_name: false,
+                            method_kind: None,
+                            param: Some(i.into()),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        members
+    }
+
+    /// Called when the scope of a class is exited.
+    ///
+    /// Like `tsc`, each declaration of a member, including overload signatures,
+    /// is reported. A setter is reported with its getter.
+    fn check_private_members(&mut self, class: ClassUsage) {
+        let getters = class
+            .members
+            .iter()
+            .filter(|member| member.method_kind == Some(MethodKind::Getter))
+            .map(|member| (member.name.clone(), member.is_private_name))
+            .collect::<FxHashSet<_>>();
+
+        for member in class.members {
+            if member.method_kind == Some(MethodKind::Setter) && getters.contains(&(member.name.clone(), member.is_private_name)) {
+                continue;
+            }
+
+            let used = match &member.param {
+                Some(id) => self.used.contains(id) || class.used.contains(&member.name),
+                None if member.is_private_name => class.used_private_names.contains(&member.name),
+                None => class.used.contains(&member.name),
+            };
+            if used {
+                continue;
+            }
+
+            let err = match member.param {
+                Some(..) => ErrorKind::UnusedProperty {
+                    span: member.span,
+                    name: member.name,
+                },
+                None => ErrorKind::UnusedDecl {
+                    span: member.span,
+                    name: if member.is_private_name {
+                        format!("#{}", member.name).into()
+                    } else {
+                        member.name
+                    },
+                },
+            };
+            self.unused_members.push(err.into());
+        }
+    }
+
+    fn into_errors(mut self, rule: Rule) -> Vec<Error> {
+        let mut errors = vec![];
+        if rule.no_unused_locals {
+            errors.append(&mut self.unused_members);
+        }
+
+        // The factory of classic JSX runtimes is used by JSX elements.
+        let uses_react = self.has_jsx && !matches!(rule.jsx, JsxMode::ReactJsx | JsxMode::ReactJsxdev);
+
+        // Like `tsc`, each declaration of merged declarations or overloads is reported.
+        for decl in take(&mut self.decls) {
+            if self.used.contains(&decl.id) || (uses_react && &**decl.id.sym() == "React") {
+                continue;
+            }
+
+            let unused = Unused {
+                name: decl.id.sym().clone(),
+                span: decl.span,
+                kind: decl.kind,
+            };
+            match decl.group {
+                Some(group) => self.groups[group].unused.push(unused),
+                None if decl.kind.is_enabled(rule) => errors.push(unused.into_error()),
+                None => {}
+            }
+        }
+
+        // Patterns are created after their variable declarations.
+        for idx in (0..self.groups.len()).rev() {
+            let mut unused = take(&mut self.groups[idx].unused);
+            let group = &self.groups[idx];
+            if unused.is_empty() || !group.decl_kind.is_enabled(rule) {
+                continue;
+            }
+            if let Some(owner) = &group.owner {
+                // Type parameters of merged interfaces may be used by other declarations.
+                if self.interfaces.get(owner).copied().unwrap_or_default() > 1 {
+                    continue;
+                }
+            }
+
+            if unused.len() != group.len {
+                errors.extend(unused.into_iter().map(Unused::into_error));
+                continue;
+            }
+
+            let (kind, len, span, parent) = (group.kind, group.len, group.span, group.parent);
+            let err: Error = match (kind, len) {
+                (GroupKind::Destructuring, 1) => {
+                    let name = unused.pop().unwrap().name;
+                    if let Some(parent) = parent {
+                        self.groups[parent].unused.push(Unused {
+                            name,
+                            span,
+                            kind: DeclKind::Value,
+                        });
+                        continue;
+                    }
+                    ErrorKind::UnusedDecl { span, name }.into()
+                }
+                (GroupKind::Vars, 1) => ErrorKind::UnusedDecl {
+                    span: unused[0].span,
+                    name: unused.pop().unwrap().name,
+                }
+                .into(),
+                (_, 1) => ErrorKind::UnusedDecl {
+                    span,
+                    name: unused.pop().unwrap().name,
+                }
+                .into(),
+                (GroupKind::Imports, _) => ErrorKind::AllImportsUnused { span }.into(),
+                (GroupKind::Vars, _) => ErrorKind::AllVarsUnused { span }.into(),
+                (GroupKind::Destructuring, _) => ErrorKind::AllDestructuredElementsUnused { span }.into(),
+                (GroupKind::TypeParams, _) => ErrorKind::AllTypeParamsUnused { span }.into(),
+            };
+            errors.push(err);
+        }
+
+        errors
+    }
+}
+
+fn prop_name(key: &RPropName) -> Option<(JsWord, Span)> {
+    match key {
+        RPropName::Ident(i) => Some((i.sym.clone(), i.span)),
+        RPropName::Str(s) => Some((s.value.clone(), s.span)),
+        _ => None,
+    }
+}
+
+impl Visit<RModuleItem> for UsageFinder {
+    fn visit(&mut self, item: &RModuleItem) {
+        match item {
+            RModuleItem::ModuleDecl(RModuleDecl::ExportDecl(e)) => self.visit_decl(&e.decl, true),
+            RModuleItem::ModuleDecl(RModuleDecl::ExportDefaultDecl(e)) => match &e.decl {
+                RDefaultDecl::TsInterfaceDecl(i) => self.visit_interface(i, false),
+                _ => e.visit_children_with(self),
+            },
+            _ => item.visit_children_with(self),
+        }
+    }
+}
+
+impl Visit<RDecl> for UsageFinder {
+    fn visit(&mut self, decl: &RDecl) {
+        self.visit_decl(decl, false);
+    }
+}
+
+impl Visit<RVarDecl> for UsageFinder {
+    fn visit(&mut self, var: &RVarDecl) {
+        self.visit_var_decl(var, true, false);
+    }
+}
+
+impl Visit<RForInStmt> for UsageFinder {
+    fn visit(&mut self, s: &RForInStmt) {
+        match &s.left {
+            RVarDeclOrPat::VarDecl(var) => self.visit_var_decl(var, true, true),
+            RVarDeclOrPat::Pat(p) => p.visit_with(self),
+        }
+        s.right.visit_with(self);
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<RForOfStmt> for UsageFinder {
+    fn visit(&mut self, s: &RForOfStmt) {
+        match &s.left {
+            RVarDeclOrPat::VarDecl(var) => self.visit_var_decl(var, true, true),
+            RVarDeclOrPat::Pat(p) => p.visit_with(self),
+        }
+        s.right.visit_with(self);
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<RImportDecl> for UsageFinder {
+    fn visit(&mut self, import: &RImportDecl) {
+        if self.is_ambient || import.specifiers.is_empty() {
+            return;
+        }
+
+        let kind = DeclKind::Import {
+            type_only: import.type_only,
+        };
+        let group = self.new_group(GroupKind::Imports, import.span, kind, import.specifiers.len(), None);
+
+        for specifier in &import.specifiers {
+            let local = match specifier {
+                RImportSpecifier::Named(s) => &s.local,
+                RImportSpecifier::Default(s) => &s.local,
+                RImportSpecifier::Namespace(s) => &s.local,
+            };
+            if !local.sym.starts_with('_') {
+                self.declare(local, kind, Some(group));
+            }
+        }
+    }
+}
+
+impl Visit<RTsImportEqualsDecl> for UsageFinder {
+    fn visit(&mut self, import: &RTsImportEqualsDecl) {
+        if !import.is_export && !self.is_global && !self.is_ambient && !import.id.sym.starts_with('_') {
+            let kind = DeclKind::Import {
+                type_only: import.is_type_only,
+            };
+            self.declare(&import.id, kind, None);
+        }
+        import.module_ref.visit_with(self);
+    }
+}
+
+impl Visit<RNamedExport> for UsageFinder {
+    fn visit(&mut self, export: &RNamedExport) {
+        if export.src.is_some() {
+            return;
+        }
+
+        for specifier in &export.specifiers {
+            if let RExportSpecifier::Named(s) = specifier {
+                if let RModuleExportName::Ident(i) = &s.orig {
+                    self.use_id(i);
+                }
+            }
+        }
+    }
+}
+
+impl Visit<RFunction> for UsageFinder {
+    fn visit(&mut self, f: &RFunction) {
+        if f.body.is_some() {
+            self.declare_type_params(f.type_params.as_deref(), None);
+            for param in &f.params {
+                self.declare_param(&param.pat);
+            }
+        }
+
+        self.in_local_scope(|v| f.visit_children_with(v));
+    }
+}
+
+impl Visit<RArrowExpr> for UsageFinder {
+    fn visit(&mut self, f: &RArrowExpr) {
+        self.declare_type_params(f.type_params.as_deref(), None);
+        for param in &f.params {
+            self.declare_param(param);
+        }
+
+        self.in_local_scope(|v| f.visit_children_with(v));
+    }
+}
+
+impl Visit<RConstructor> for UsageFinder {
+    fn visit(&mut self, c: &RConstructor) {
+        if c.body.is_some() {
+            for param in &c.params {
+                if let RParamOrTsParamProp::Param(p) = param {
+                    self.declare_param(&p.pat);
+                }
+            }
+        }
+
+        self.in_local_scope(|v| c.visit_children_with(v));
+    }
+}
+
+impl Visit<RGetterProp> for UsageFinder {
+    fn visit(&mut self, p: &RGetterProp) {
+        self.in_local_scope(|v| p.visit_children_with(v));
+    }
+}
+
+impl Visit<RSetterProp> for UsageFinder {
+    fn visit(&mut self, p: &RSetterProp) {
+        if p.body.is_some() {
+            self.declare_param(&p.param);
+        }
+
+        self.in_local_scope(|v| p.visit_children_with(v));
+    }
+}
+
+impl Visit<RStaticBlock> for UsageFinder {
+    fn visit(&mut self, b: &RStaticBlock) {
+        self.in_local_scope(|v| b.visit_children_with(v));
+    }
+}
+
+impl Visit<RClass> for UsageFinder {
+    fn visit(&mut self, c: &RClass) {
+        self.declare_type_params(c.type_params.as_deref(), None);
+
+        let members = if self.is_ambient { vec![] } else { self.collect_private_members(c) };
+        self.classes.push(ClassUsage {
+            members,
+            ..Default::default()
+        });
+
+        c.visit_children_with(self);
+
+        let class = self.classes.pop().unwrap();
+        self.check_private_members(class);
+    }
+}
+
+impl Visit<RExprStmt> for UsageFinder {
+    fn visit(&mut self, s: &RExprStmt) {
+        // The result of an assignment or an update is not read.
+        match &*s.expr {
+            RExpr::Assign(e) => {
+                self.visit_assign_target(&e.left, true);
+                e.right.visit_with(self);
+            }
+            RExpr::Update(e) => self.visit_write_target(&e.arg),
+            _ => s.expr.visit_with(self),
+        }
+    }
+}
+
+impl Visit<RAssignExpr> for UsageFinder {
+    fn visit(&mut self, e: &RAssignExpr) {
+        self.visit_assign_target(&e.left, e.op == AssignOp::Assign);
+        e.right.visit_with(self);
+    }
+}
+
+impl Visit<RPat> for UsageFinder {
+    fn visit(&mut self, p: &RPat) {
+        match p {
+            RPat::Expr(e) => self.visit_write_target(e),
+            _ => p.visit_children_with(self),
+        }
+    }
+}
+
+impl Visit<RExpr> for UsageFinder {
+    fn visit(&mut self, e: &RExpr) {
+        match e {
+            RExpr::Ident(i) => self.use_id(i),
+            RExpr::PrivateName(n) => self.use_member(&n.id.sym, true),
+            _ => e.visit_children_with(self),
+        }
+    }
+}
+
+impl Visit<RMemberExpr> for UsageFinder {
+    fn visit(&mut self, e: &RMemberExpr) {
+        e.obj.visit_with(self);
+
+        match &e.prop {
+            RMemberProp::Ident(i) => self.use_member(&i.sym, false),
+            RMemberProp::PrivateName(n) => self.use_member(&n.id.sym, true),
+            RMemberProp::Computed(c) => {
+                if let RExpr::Lit(RLit::Str(s)) = &*c.expr {
+                    self.use_member(&s.value, false);
+                }
+                c.visit_with(self);
+            }
+        }
+    }
+}
+
+impl Visit<RProp> for UsageFinder {
+    fn visit(&mut self, p: &RProp) {
+        match p {
+            RProp::Shorthand(i) => self.use_id(i),
+            _ => p.visit_children_with(self),
+        }
+    }
+}
+
+impl Visit<RTsEntityName> for UsageFinder {
+    fn visit(&mut self, n: &RTsEntityName) {
+        match n {
+            RTsEntityName::Ident(i) => self.use_id(i),
+            RTsEntityName::TsQualifiedName(q) => q.left.visit_with(self),
+        }
+    }
+}
+
+impl Visit<RTsImportType> for UsageFinder {
+    fn visit(&mut self, n: &RTsImportType) {
+        // The qualifier refers to exports of the module.
+        n.type_args.visit_with(self);
+    }
+}
+
+impl Visit<RTsPropertySignature> for UsageFinder {
+    fn visit(&mut self, n: &RTsPropertySignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.init.visit_with(self);
+        n.params.visit_with(self);
+        n.type_ann.visit_with(self);
+        n.type_params.visit_with(self);
+    }
+}
+
+impl Visit<RTsMethodSignature> for UsageFinder {
+    fn visit(&mut self, n: &RTsMethodSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.params.visit_with(self);
+        n.type_ann.visit_with(self);
+        n.type_params.visit_with(self);
+    }
+}
+
+impl Visit<RTsGetterSignature> for UsageFinder {
+    fn visit(&mut self, n: &RTsGetterSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.type_ann.visit_with(self);
+    }
+}
+
+impl Visit<RTsSetterSignature> for UsageFinder {
+    fn visit(&mut self, n: &RTsSetterSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.param.visit_with(self);
+    }
+}
+
+impl Visit<RJSXElementName> for UsageFinder {
+    fn visit(&mut self, n: &RJSXElementName) {
+        self.has_jsx = true;
+
+        match n {
+            RJSXElementName::Ident(i) => self.use_id(i),
+            RJSXElementName::JSXMemberExpr(e) => e.obj.visit_with(self),
+            RJSXElementName::JSXNamespacedName(..) => {}
+        }
+    }
+}
+
+impl Visit<RJSXObject> for UsageFinder {
+    fn visit(&mut self, n: &RJSXObject) {
+        match n {
+            RJSXObject::Ident(i) => self.use_id(i),
+            RJSXObject::JSXMemberExpr(e) => e.obj.visit_with(self),
+        }
+    }
+}
+
+impl Visit<RJSXFragment> for UsageFinder {
+    fn visit(&mut self, n: &RJSXFragment) {
+        self.has_jsx = true;
+        n.visit_children_with(self);
+    }
+}
@@ -106,6 +106,8 @@ impl Analyzer<'_, '_> {
 
         match pat {
             RPat::Ident(i) => {
+                self.record_decl(&i.id);
+
                 if let Some(ty) = &ty {
                     if cfg!(debug_assertions) {
                         debug!("[vars]: Declaring {} as {}", i.id.sym, dump_type_as_string(ty));
@@ -436,7 +436,10 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RDoWhileStmt) {
+        // The end of the loop is reachable even if the end of the body is not.
+        let reachability = self.reachability();
         node.body.visit_with(self);
+        self.set_reachability(reachability);
 
         self.validate_loop_body_with_scope(Some(&node.test), &node.body)
             .report(&mut self.storage);
@@ -1,7 +1,10 @@
-use std::time::Instant;
+use std::{mem::replace, time::Instant};
 
 use rnode::VisitWith;
-use stc_ts_ast_rnode::{RBlockStmt, RBool, RDecl, RExpr, RExprStmt, RForStmt, RModuleItem, RStmt, RTsExprWithTypeArgs, RTsLit, RWithStmt};
+use stc_ts_ast_rnode::{
+    RBlockStmt, RBool, RDecl, RExpr, RExprStmt, RForStmt, RLabeledStmt, RLit, RModuleItem, RStmt, RTryStmt, RTsExprWithTypeArgs, RTsLit,
+    RWithStmt,
+};
 use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_types::{LitType, Type};
 use stc_utils::{dev_span, stack};
@@ -52,11 +55,37 @@ impl Analyzer<'_, '_> {
 
         let old_in_conditional = self.scope.return_values.in_conditional;
         self.scope.return_values.in_conditional |= matches!(s, RStmt::If(_) | RStmt::Switch(_));
+        let breaks = self.scope.return_values.breaks.len();
 
         s.visit_children_with(self);
 
         self.scope.return_values.in_conditional = old_in_conditional;
 
+        match s {
+            RStmt::Return(..) | RStmt::Throw(..) | RStmt::Continue(..) => {
+                self.ctx.after_jump = true;
+            }
+            RStmt::Break(s) => {
+                self.scope
+                    .return_values
+                    .breaks
+                    .push(s.label.as_ref().map(|label| label.sym.clone()));
+                self.ctx.after_jump = true;
+            }
+            // Unlabeled `break` statements in them do not exit the enclosing statement.
+            RStmt::Switch(..) | RStmt::For(..) | RStmt::ForIn(..) | RStmt::ForOf(..) | RStmt::While(..) | RStmt::DoWhile(..) => {
+                let nested = self.scope.return_values.breaks.split_off(breaks);
+                let has_break = nested.contains(&None);
+                self.scope.return_values.breaks.extend(nested.into_iter().filter(Option::is_some));
+
+                // Infinite loops are exited only by `break`, `return` or `throw`.
+                if !has_break && is_infinite_loop(s) {
+                    self.ctx.after_jump = true;
+                }
+            }
+            _ => {}
+        }
+
         let end = Instant::now();
 
         warn!(
@@ -71,6 +100,18 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Returns `true` for loops like `while (true)` or `for (;;)`.
+fn is_infinite_loop(s: &RStmt) -> bool {
+    let is_true = |e: &RExpr| matches!(e, RExpr::Lit(RLit::Bool(RBool { value: true, .. })));
+
+    match s {
+        RStmt::While(s) => is_true(&s.test),
+        RStmt::DoWhile(s) => is_true(&s.test),
+        RStmt::For(s) => s.test.as_deref().map_or(true, is_true),
+        _ => false,
+    }
+}
+
 impl Analyzer<'_, '_> {
     fn check_for_infinite_loop(&mut self, test: &Type, body: &RStmt) {
         trace!("Checking for infinite loop");
@@ -107,7 +148,11 @@ impl Analyzer<'_, '_> {
         });
 
         node.update.visit_with(self);
+
+        // The end of the loop is reachable even if the end of the body is not.
+        let reachability = self.reachability();
         node.body.validate_with(self)?;
+        self.set_reachability(reachability);
 
         self.check_for_infinite_loop(test.as_ref().unwrap_or(&always_true), &node.body);
 
@@ -130,11 +175,60 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, s: &RBlockStmt) {
-        self.with_child(ScopeKind::Block, Default::default(), |analyzer| {
+        let reachability = self.with_child(ScopeKind::Block, Default::default(), |analyzer| {
             s.stmts.visit_with(analyzer);
-            Ok(())
+            Ok(analyzer.reachability())
         })?;
 
+        self.set_reachability(reachability);
+
+        Ok(())
+    }
+}
+
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, s: &RLabeledStmt) {
+        let breaks = self.scope.return_values.breaks.len();
+
+        s.body.visit_with(self);
+
+        let nested = self.scope.return_values.breaks.split_off(breaks);
+        let label = Some(s.label.sym.clone());
+        if nested.contains(&label) {
+            self.ctx.after_jump = false;
+        }
+        self.scope.return_values.breaks.extend(nested.into_iter().filter(|l| *l != label));
+
+        Ok(())
+    }
+}
+
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, s: &RTryStmt) {
+        let reachability = self.reachability();
+
+        s.block.visit_with(self);
+
+        // Any statement of the block may throw.
+        let mut end = self.reachability();
+        if let Some(handler) = &s.handler {
+            self.set_reachability(reachability);
+            handler.visit_with(self);
+            end = end.join(self.reachability());
+        }
+
+        if let Some(finalizer) = &s.finalizer {
+            self.set_reachability(reachability);
+            finalizer.visit_with(self);
+            if self.reachability().is_reachable() {
+                self.set_reachability(end);
+            }
+        } else {
+            self.set_reachability(end);
+        }
+
         Ok(())
     }
 }
@@ -178,8 +272,17 @@ impl Analyzer<'_, '_> {
 
         let prev_cond_facts = self.cur_facts.clone();
 
+        let call_stmt_span = match &*node.expr {
+            RExpr::Call(e) => Some(e.span),
+            _ => None,
+        };
+        // Modified directly to preserve `in_unreachable`.
+        let prev_call_stmt_span = replace(&mut self.ctx.call_stmt_span, call_stmt_span);
+
         node.expr.visit_with(self);
 
+        self.ctx.call_stmt_span = prev_call_stmt_span;
+
         if preserve_cond_facts {
             self.cur_facts = prev_cond_facts;
         }
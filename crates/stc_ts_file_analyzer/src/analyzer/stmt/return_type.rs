@@ -15,6 +15,7 @@ use stc_utils::{
     dev_span,
     ext::{SpanExt, TypeVecExt},
 };
+use swc_atoms::JsWord;
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 use tracing::debug;
@@ -28,6 +29,7 @@ use crate::{
         Analyzer, Ctx,
     },
     ty::{Array, Type, TypeExt},
+    util::unwrap_builtin_with_single_arg,
     validator,
     validator::ValidateWith,
     VResult,
@@ -40,6 +42,11 @@ pub(in crate::analyzer) struct ReturnValues {
     should_generalize: bool,
     pub return_types: Vec<Type>,
     yield_types: Vec<Type>,
+    /// Spans of `return` statements without an argument.
+    bare_returns: Vec<Span>,
+    /// Labels of `break` statements, or `None` for unlabeled ones. Statements
+    /// targeted by them remove them.
+    pub(in crate::analyzer) breaks: Vec<Option<JsWord>>,
     /// Are we in if or switch statement?
     pub(super) in_conditional: bool,
 }
@@ -51,6 +58,8 @@ impl AddAssign for ReturnValues {
 
         self.return_types.extend(rhs.return_types);
         self.yield_types.extend(rhs.yield_types);
+        self.bare_returns.extend(rhs.bare_returns);
+        self.breaks.extend(rhs.breaks);
     }
 }
 
@@ -90,19 +99,32 @@ impl Analyzer<'_, '_> {
         // let mut old_ret_tys = self.scope.return_types.take();
 
         let mut is_unreachable = false;
+        let mut end_reachable = true;
+        let mut has_explicit_return = false;
+        let mut returns_value = false;
+        let mut bare_returns = vec![];
         let mut ret_ty = (|| -> VResult<_> {
             let mut values: ReturnValues = {
                 let ctx = Ctx {
                     cannot_fallback_to_iterable_iterator,
+                    after_jump: false,
                     ..self.ctx
                 };
                 self.with_ctx(ctx).with(|analyzer: &mut Analyzer| {
                     analyzer.validate_stmts_and_collect(&stmts.iter().collect::<Vec<_>>());
                     is_unreachable = analyzer.ctx.in_unreachable;
+                    end_reachable = analyzer.reachability().is_reachable();
                     take(&mut analyzer.scope.return_values)
                 })
             };
 
+            bare_returns = take(&mut values.bare_returns);
+            has_explicit_return = !values.return_types.is_empty();
+            returns_value = values
+                .return_types
+                .iter()
+                .any(|ty| !bare_returns.contains(&ty.span()) && !is_void_or_any(ty, true));
+
             {
                 //  Expand return types if no element references a type parameter
                 let can_expand = !values.return_types.iter().any(should_preserve_ref);
@@ -296,6 +318,10 @@ impl Analyzer<'_, '_> {
             return Ok(ret_ty);
         }
 
+        if self.rule().no_implicit_returns && !is_generator && !self.ctx.reevaluating() {
+            self.report_implicit_returns(span, is_async, has_explicit_return, returns_value, bare_returns, end_reachable);
+        }
+
         if let Some(declared) = self.scope.declared_return_type().cloned() {
             if !is_async && !is_generator {
                 if ret_ty.is_none() && !is_unreachable {
@@ -344,6 +370,89 @@ impl Analyzer<'_, '_> {
 
         Ok(ret_ty)
     }
+
+    /// Reports `TS7030` for `return;` and for the reachable end of a function,
+    /// if the function returns a value.
+    ///
+    /// Like `tsc`, a function with a declared return type which does not
+    /// contain `void` or `any` returns a value. Otherwise, a function returns a
+    /// value if a `return` statement returns a value.
+    fn report_implicit_returns(
+        &mut self,
+        span: Span,
+        is_async: bool,
+        has_explicit_return: bool,
+        returns_value: bool,
+        bare_returns: Vec<Span>,
+        end_reachable: bool,
+    ) {
+        let declared = self.scope.declared_return_type().cloned().map(|ty| {
+            if is_async {
+                unwrap_builtin_with_single_arg(&ty, "Promise").cloned().unwrap_or(ty)
+            } else {
+                ty
+            }
+        });
+
+        let returns_value = match &declared {
+            Some(declared) => !is_void_or_any(declared, true),
+            None => returns_value,
+        };
+        if returns_value {
+            for span in bare_returns {
+                self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+            }
+        }
+
+        if !end_reachable || !has_explicit_return {
+            return;
+        }
+
+        match declared {
+            Some(declared) => {
+                if is_void_or_any(&declared, false) {
+                    return;
+                }
+                if self.rule().strict_null_checks {
+                    // `TS2366` is used if `undefined` is not assignable to the return type.
+                    let undefined = Type::undefined(span, Default::default());
+                    if self
+                        .assign_with_opts(
+                            &mut Default::default(),
+                            &declared,
+                            &undefined,
+                            AssignOpts {
+                                span,
+                                ..Default::default()
+                            },
+                        )
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+
+                let span = declared.span().or_else(|| span);
+                self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+            }
+            None => {
+                if returns_value {
+                    self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if `ty` is `void` or `any`, or a union containing them. If
+/// `with_unknown` is `true`, `unknown` is also checked.
+fn is_void_or_any(ty: &Type, with_unknown: bool) -> bool {
+    match ty.normalize() {
+        Type::Union(u) => u.types.iter().any(|ty| is_void_or_any(ty, with_unknown)),
+        ty => {
+            ty.is_kwd(TsKeywordTypeKind::TsVoidKeyword) || ty.is_any() || (with_unknown && ty.is_kwd(TsKeywordTypeKind::TsUnknownKeyword))
+        }
+    }
 }
 
 #[validator]
@@ -364,6 +473,8 @@ impl Analyzer<'_, '_> {
         } {
             res?
         } else {
+            self.scope.return_values.bare_returns.push(node.span);
+
             Type::Keyword(KeywordType {
                 span: node.span,
                 kind: TsKeywordTypeKind::TsVoidKeyword,
@@ -552,6 +663,8 @@ impl Analyzer<'_, '_> {
                             ErrorKind::SimpleAssignFailed {
                                 span,
                                 cause: Some(box err),
+                                left: None,
+                                right: None,
                             }
                             .into(),
                         );
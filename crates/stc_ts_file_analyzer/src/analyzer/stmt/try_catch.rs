@@ -14,18 +14,27 @@ impl Analyzer<'_, '_> {
             pat_mode: PatMode::Decl,
             ..self.ctx
         };
-        self.with_ctx(ctx)
+        let reachability = self
+            .with_ctx(ctx)
             .with_child(ScopeKind::Block, Default::default(), |child: &mut Analyzer| {
                 match &s.param {
                     Some(pat) => {
-                        pat.validate_with(child)?;
+                        let ctx = Ctx {
+                            is_catch_param: true,
+                            ..child.ctx
+                        };
+                        pat.validate_with(&mut *child.with_ctx(ctx))?;
                     }
                     None => {}
                 }
 
                 s.body.visit_with(child);
 
-                Ok(())
-            })
+                Ok(child.reachability())
+            })?;
+
+        self.set_reachability(reachability);
+
+        Ok(())
     }
 }
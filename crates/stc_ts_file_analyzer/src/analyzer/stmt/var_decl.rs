@@ -97,6 +97,9 @@ impl Analyzer<'_, '_> {
                 None
             };
             let ids: Vec<Id> = find_ids_in_pat(&v.name);
+            if v.name.get_ty().is_none() {
+                self.data.vars_without_type_ann.extend(ids.iter().cloned());
+            }
             let prev_declaring_len = self.scope.declaring.len();
             self.scope.declaring.extend(ids);
 
@@ -698,6 +701,8 @@ impl Analyzer<'_, '_> {
                             }
                         }
 
+                        self.record_decl(&i.id);
+
                         match self.declare_var(
                             i.id.span,
                             VarKind::Var(kind),
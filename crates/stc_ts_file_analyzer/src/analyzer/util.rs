@@ -41,6 +41,54 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Records the type of a node, if a [crate::recorder::Recorder] is
+    /// set.
+    pub(crate) fn record_type(&self, span: Span, ty: &Type) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_type(span, ty);
+        }
+    }
+
+    /// Records an identifier resolved by the scope, if a
+    /// [crate::recorder::Recorder] is set.
+    ///
+    /// `id` is the resolved name, which differs from `i` for aliases like `b`
+    /// in `export { a as b }`.
+    pub(crate) fn record_ident(&self, i: &RIdent, id: Id, is_decl: bool) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_ident(i.span, id, is_decl);
+        }
+    }
+
+    /// Records the declaration of `i`.
+    pub(crate) fn record_decl(&self, i: &RIdent) {
+        self.record_ident(i, i.into(), true);
+    }
+
+    /// Records a usage of `i`.
+    pub(crate) fn record_usage(&self, i: &RIdent) {
+        self.record_ident(i, i.into(), false);
+    }
+
+    /// Records a usage of the leftmost identifier of `name`, like `a` in
+    /// `a.b.C`.
+    pub(crate) fn record_entity_name(&self, name: &RTsEntityName) {
+        match name {
+            RTsEntityName::Ident(i) => self.record_usage(i),
+            RTsEntityName::TsQualifiedName(q) => self.record_entity_name(&q.left),
+        }
+    }
+
+    /// Records resolved imports, so identifiers can be followed across
+    /// modules.
+    pub(crate) fn record_imports(&self) {
+        if let Some(recorder) = &self.recorder {
+            for (local, import) in &self.data.imports_by_id {
+                recorder.record_import(local.clone(), import.module.module_id, import.orig.clone());
+            }
+        }
+    }
+
     /// `span` and `callee` is used only for error reporting.
     fn make_instance_from_type_elements(&mut self, span: Span, callee: &Type, elements: &[TypeElement]) -> VResult<Type> {
         let _tracing = dev_span!("make_instance_from_type_elements");
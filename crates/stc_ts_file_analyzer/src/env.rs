@@ -282,29 +282,47 @@ impl BuiltInGen for BuiltIn {
     }
 }
 
+static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
+
+/// Builds the builtin types of `libs`, or returns the cached one.
+fn builtin_of(libs: &[Lib]) -> Arc<BuiltIn> {
+    static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+
+    // TODO(kdy1): Include `env` in cache
+    let mut libs = libs.to_vec();
+    libs.sort();
+    libs.dedup();
+
+    let cell = CACHE.entry(libs.clone()).or_default().clone();
+
+    let builtin = cell.get_or_init(|| {
+        let builtin = BuiltIn::from_ts_libs(&STABLE_ENV, &libs).with_libs(libs.clone());
+        Arc::new(builtin)
+    });
+    (*builtin).clone()
+}
+
 pub trait EnvFactory {
     #[allow(clippy::new_ret_no_self)]
     fn new(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Arc<BuiltIn>) -> Env;
     fn simple(rule: Rule, target: EsVersion, module: ModuleConfig, libs: &[Lib]) -> Env {
-        static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
-        static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
-
-        // TODO(kdy1): Include `env` in cache
-        let mut libs = libs.to_vec();
-        libs.sort();
-        libs.dedup();
-
-        let cell = CACHE.entry(libs.clone()).or_default().clone();
-
-        let builtin = {
-            let builtin = cell.get_or_init(|| {
-                let builtin = BuiltIn::from_ts_libs(&STABLE_ENV, &libs);
-                Arc::new(builtin)
-            });
-            (*builtin).clone()
-        };
+        Self::new(STABLE_ENV.clone(), rule, target, module, builtin_of(libs))
+    }
+
+    /// Returns a copy of `env` with `libs` added to the builtin types, for
+    /// `/// <reference lib="..." />`. `env` should be created by
+    /// [EnvFactory::simple].
+    ///
+    /// Returns [None] if `libs` are already loaded.
+    fn with_libs(env: &Env, libs: &[Lib]) -> Option<Env> {
+        let builtin = env.builtin();
+        if libs.iter().all(|lib| builtin.libs().contains(lib)) {
+            return None;
+        }
+
+        let all = builtin.libs().iter().chain(libs).copied().collect::<Vec<_>>();
 
-        Self::new(STABLE_ENV.clone(), rule, target, module, builtin)
+        Some(env.with_builtin(builtin_of(&all)))
     }
 }
 
@@ -27,6 +27,7 @@ use swc_common::Span;
 pub mod analyzer;
 pub mod env;
 pub mod loader;
+pub mod recorder;
 #[cfg(test)]
 mod tests;
 pub mod ty;
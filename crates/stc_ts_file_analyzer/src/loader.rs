@@ -42,5 +42,8 @@ pub trait Load: 'static + Send + Sync {
     fn load_non_circular_dep(&self, base: &Arc<FileName>, src: &str) -> VResult<Type>;
 
     /// `module` should be [Type::Arc] of [Type::Module].
-    fn declare_module(&self, name: &JsWord, module: Type);
+    ///
+    /// `base` is the file declaring the module, which is used to resolve
+    /// module augmentations like `declare module "./foo"`.
+    fn declare_module(&self, base: &Arc<FileName>, name: &JsWord, module: Type);
 }
@@ -0,0 +1,221 @@
+//! Information about nodes, recorded for editor integrations like hover and
+//! rename.
+
+use std::sync::Arc;
+
+use fxhash::{FxHashMap, FxHashSet};
+use parking_lot::Mutex;
+use stc_ts_types::{Id, ModuleId, Type};
+use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
+use swc_atoms::JsWord;
+use swc_common::{BytePos, Span, SyntaxContext};
+
+/// Imports and reexports are followed up to this depth.
+const MAX_LINK_DEPTH: usize = 32;
+
+/// Records types of nodes and identifiers.
+///
+/// This is opt-in because recording slows down batch type checking. Cloning
+/// this is cheap and clones share the recorded data.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    types: Vec<(Span, Type)>,
+
+    /// Keyed by the start of the identifier.
+    idents: FxHashMap<BytePos, Occurrence>,
+
+    /// Local name to the imported module and the imported name.
+    imports: FxHashMap<Id, (ModuleId, JsWord)>,
+
+    /// Exported name to the local name.
+    exports: FxHashMap<(ModuleId, JsWord), Id>,
+
+    /// Reexported name to the module and name it's exported from.
+    reexports: FxHashMap<(ModuleId, JsWord), (ModuleId, JsWord)>,
+
+    /// Completions are collected only for the identifier containing this
+    /// position.
+    completion_target: Option<BytePos>,
+
+    completions: Vec<Completion>,
+}
+
+/// An identifier resolved by the analyzer.
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    pub span: Span,
+    pub id: Id,
+    /// `true` if this is the declaration of [Occurrence::id].
+    pub is_decl: bool,
+}
+
+/// A name which can be inserted at the completion target.
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub name: JsWord,
+    pub kind: CompletionKind,
+    /// The declared type.
+    pub ty: Option<Type>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Var,
+    Type,
+    Property,
+    Method,
+}
+
+impl Recorder {
+    pub fn record_type(&self, span: Span, ty: &Type) {
+        if span.is_dummy() {
+            return;
+        }
+
+        let ty = ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed();
+
+        self.inner.lock().types.push((span, ty));
+    }
+
+    /// Returns the type of the innermost node containing `pos`.
+    ///
+    /// If a node is validated multiple times, the last type wins.
+    pub fn type_at(&self, pos: BytePos) -> Option<(Span, Type)> {
+        let inner = self.inner.lock();
+
+        inner
+            .types
+            .iter()
+            .rev()
+            .filter(|(span, _)| span.lo <= pos && pos <= span.hi)
+            .min_by_key(|(span, _)| span.hi - span.lo)
+            .cloned()
+    }
+
+    pub fn record_import(&self, local: Id, dep: ModuleId, imported: JsWord) {
+        self.inner.lock().imports.insert(local, (dep, imported));
+    }
+
+    pub fn record_export(&self, module: ModuleId, exported: JsWord, local: Id) {
+        self.inner.lock().exports.insert((module, exported), local);
+    }
+
+    pub fn record_reexport(&self, module: ModuleId, exported: JsWord, dep: ModuleId, orig: JsWord) {
+        self.inner.lock().reexports.insert((module, exported), (dep, orig));
+    }
+
+    /// Records an identifier resolved by the analyzer.
+    ///
+    /// Identifiers with an empty context are not resolved, like property names.
+    pub fn record_ident(&self, span: Span, id: Id, is_decl: bool) {
+        if span.is_dummy() || id.ctxt() == SyntaxContext::empty() {
+            return;
+        }
+
+        let o = Occurrence { span, id, is_decl };
+        let mut inner = self.inner.lock();
+        if is_decl {
+            inner.idents.insert(span.lo, o);
+        } else {
+            inner.idents.entry(span.lo).or_insert(o);
+        }
+    }
+
+    /// Returns the identifier at `pos`.
+    pub fn ident_at(&self, pos: BytePos) -> Option<Occurrence> {
+        let inner = self.inner.lock();
+
+        inner.idents.values().find(|o| o.span.lo <= pos && pos <= o.span.hi).cloned()
+    }
+
+    pub fn set_completion_target(&self, pos: Option<BytePos>) {
+        let mut inner = self.inner.lock();
+        inner.completion_target = pos;
+        inner.completions.clear();
+    }
+
+    /// Returns `true` if completions should be collected for a node at
+    /// `span`.
+    pub fn is_completion_target(&self, span: Span) -> bool {
+        match self.inner.lock().completion_target {
+            Some(pos) => !span.is_dummy() && span.lo <= pos && pos <= span.hi,
+            None => false,
+        }
+    }
+
+    pub fn record_completion(&self, name: JsWord, kind: CompletionKind, ty: Option<&Type>) {
+        let ty = ty.map(|ty| ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed());
+
+        self.inner.lock().completions.push(Completion { name, kind, ty });
+    }
+
+    /// Returns the recorded completions, without duplicate names.
+    ///
+    /// If a name is recorded multiple times, the first one wins.
+    pub fn completions(&self) -> Vec<Completion> {
+        let inner = self.inner.lock();
+
+        let mut seen = FxHashSet::default();
+        inner
+            .completions
+            .iter()
+            .filter(|c| seen.insert((c.name.clone(), c.kind == CompletionKind::Type)))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the local name in the module which declares the symbol of `id`,
+    /// following imports and exports across modules.
+    pub fn resolve(&self, id: &Id) -> Id {
+        self.inner.lock().resolve(id)
+    }
+
+    /// Returns all occurrences of the symbol of `id`, following imports and
+    /// exports across modules.
+    pub fn occurrences(&self, id: &Id) -> Vec<Occurrence> {
+        let inner = self.inner.lock();
+        let symbol = inner.resolve(id);
+
+        let mut occurrences = inner
+            .idents
+            .values()
+            .filter(|o| inner.resolve(&o.id) == symbol)
+            .cloned()
+            .collect::<Vec<_>>();
+        occurrences.sort_by_key(|o| o.span.lo);
+        occurrences
+    }
+}
+
+impl Inner {
+    /// Returns the local name in the module which declares `id`.
+    fn resolve(&self, id: &Id) -> Id {
+        let mut id = id.clone();
+
+        for _ in 0..MAX_LINK_DEPTH {
+            let mut key = match self.imports.get(&id) {
+                Some(v) => v.clone(),
+                None => return id,
+            };
+
+            for _ in 0..MAX_LINK_DEPTH {
+                match self.reexports.get(&key) {
+                    Some(v) => key = v.clone(),
+                    None => break,
+                }
+            }
+
+            match self.exports.get(&key) {
+                Some(local) => id = local.clone(),
+                None => return id,
+            }
+        }
+
+        id
+    }
+}
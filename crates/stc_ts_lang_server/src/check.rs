@@ -0,0 +1,144 @@
+//! Type checking of open documents.
+
+use std::{
+    collections::HashMap,
+    io::sink,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use parking_lot::Mutex;
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::recorder::Recorder;
+use stc_ts_type_checker::{loader::ModuleLoader, type_roots, Checker};
+use swc_common::{errors::Handler, FileName, SourceMap, Spanned};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+use tracing::debug;
+
+use crate::{
+    overlay::{Overlay, OverlayFileLoader, OverlayResolver},
+    position::to_lsp_range,
+    workspace::Workspace,
+};
+
+/// The result of type checking a snapshot of open documents.
+pub struct Checked {
+    /// The documents which are checked.
+    pub overlay: Overlay,
+
+    pub cm: Arc<SourceMap>,
+
+    pub recorder: Recorder,
+
+    /// Contains an entry for every open document, so documents without errors
+    /// get their old diagnostics cleared.
+    pub diagnostics: HashMap<PathBuf, Vec<Diagnostic>>,
+
+    /// Reused by completions, which check edited documents again.
+    pub(crate) incremental: Mutex<Incremental>,
+}
+
+/// The checker of a [Checked] and documents it reads.
+///
+/// Documents are copied from [Checked::overlay], so they can be edited
+/// without affecting [Checked::overlay].
+pub(crate) struct Incremental {
+    pub checker: OverlayChecker,
+    pub overlay: Overlay,
+}
+
+/// Type checks all documents of `overlay` in `workspace`.
+///
+/// This blocks the current thread. Returns [None] if `cancelled` is set before
+/// all documents are checked.
+pub fn check(workspace: &Workspace, overlay: Overlay, cancelled: &AtomicBool) -> Option<Checked> {
+    let paths = overlay.paths();
+
+    let cm = Arc::new(SourceMap::default());
+    let recorder = Recorder::default();
+    let checker_overlay = overlay.snapshot();
+    let mut checker = new_checker(cm.clone(), workspace, checker_overlay.clone(), recorder.clone());
+
+    for path in &paths {
+        if cancelled.load(Ordering::Relaxed) {
+            debug!("Type checking is cancelled");
+            return None;
+        }
+
+        checker.check(Arc::new(FileName::Real(path.clone())));
+    }
+
+    let mut diagnostics = paths.into_iter().map(|path| (path, vec![])).collect::<HashMap<_, _>>();
+
+    for err in ErrorKind::flatten(checker.take_errors()) {
+        let span = err.span();
+        if span.is_dummy() {
+            debug!("Dropping an error without a span: {:?}", err);
+            continue;
+        }
+
+        let file = cm.lookup_byte_offset(span.lo).sf;
+        let path = match &file.name {
+            FileName::Real(path) => path,
+            _ => continue,
+        };
+
+        // Errors of files not opened in the editor are not reported.
+        if let Some(diagnostics) = diagnostics.get_mut(path) {
+            diagnostics.push(to_lsp(&cm, &err));
+        }
+    }
+
+    Some(Checked {
+        overlay,
+        cm,
+        recorder,
+        diagnostics,
+        incremental: Mutex::new(Incremental {
+            checker,
+            overlay: checker_overlay,
+        }),
+    })
+}
+
+pub type OverlayChecker = Checker<ModuleLoader<OverlayFileLoader, OverlayResolver>>;
+
+/// Creates a checker which reads open documents from `overlay`.
+pub fn new_checker(cm: Arc<SourceMap>, workspace: &Workspace, overlay: Overlay, recorder: Recorder) -> OverlayChecker {
+    // Errors are published to the client instead.
+    let handler = Arc::new(Handler::with_emitter_writer(Box::new(sink()), None));
+    // Globals declared by documents should not leak into later checks.
+    let env = workspace.env.without_globals();
+
+    let mut loader = ModuleLoader::new(
+        cm.clone(),
+        env.clone(),
+        OverlayResolver::new(overlay.clone()),
+        OverlayFileLoader { overlay },
+    );
+    if let Some(dir) = &workspace.dir {
+        loader = loader.with_type_roots(type_roots(dir, workspace.type_roots.as_deref()));
+    }
+
+    let mut checker = Checker::new(cm, handler, env, None, loader);
+    if let Some(dir) = &workspace.dir {
+        // Typings declare globals, so they are loaded into `env` of this checker.
+        checker.load_typings(dir, workspace.type_roots.as_deref(), workspace.types.as_deref());
+    }
+    checker.set_recorder(Some(recorder));
+    checker
+}
+
+fn to_lsp(cm: &SourceMap, err: &Error) -> Diagnostic {
+    Diagnostic {
+        range: to_lsp_range(cm, err.span()),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::Number(ErrorKind::normalize_error_code(err.code()) as _)),
+        source: Some("stc".into()),
+        message: err.message_chain(),
+        ..Default::default()
+    }
+}
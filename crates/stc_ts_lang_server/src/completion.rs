@@ -0,0 +1,99 @@
+use std::{path::Path, sync::Arc};
+
+use stc_ts_errors::print_type;
+use stc_ts_file_analyzer::recorder::{Completion, CompletionKind, Recorder};
+use swc_common::{BytePos, FileName, SourceMap};
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, Position};
+use tracing::debug;
+
+use crate::{
+    check::{Checked, Incremental, OverlayChecker},
+    overlay::{source_file, Overlay},
+    position::to_byte_pos,
+};
+
+/// Inserted at the cursor, so incomplete code like `foo.` can be parsed.
+const PLACEHOLDER: &str = "__stc_completion__";
+
+/// Lists properties after `.`, and visible variables and types elsewhere.
+///
+/// The checker of `checked` is reused, so only documents of `overlay` edited
+/// after `checked` are checked again. This blocks the current thread.
+pub fn complete(checked: &Checked, overlay: &Overlay, path: &Path, pos: Position) -> Option<Vec<CompletionItem>> {
+    let doc = overlay.get(path)?;
+    let filename = Arc::new(FileName::Real(path.to_path_buf()));
+
+    let offset = {
+        let fm = SourceMap::default().new_source_file((*filename).clone(), (*doc.text).clone());
+        (to_byte_pos(&fm, pos)? - fm.start_pos).0 as usize
+    };
+
+    let mut text = (*doc.text).clone();
+    text.insert_str(offset, PLACEHOLDER);
+
+    let mut incremental = checked.incremental.lock();
+    let Incremental { checker, overlay: docs } = &mut *incremental;
+
+    for other in overlay.paths() {
+        let other_doc = match overlay.get(&other) {
+            Some(v) if other != path => v,
+            _ => continue,
+        };
+        if docs.get(&other).map_or(false, |prev| prev.text == other_doc.text) {
+            continue;
+        }
+
+        docs.insert(other.clone(), other_doc.uri, other_doc.version, (*other_doc.text).clone());
+        invalidate(checker, &Arc::new(FileName::Real(other)));
+    }
+
+    docs.insert(path.to_path_buf(), doc.uri, doc.version, text.clone());
+
+    // The document is loaded before type checking to know the position of the
+    // placeholder. Files cannot be removed from the source map, so requests at
+    // the same position of a version reuse the file, and the source map is
+    // dropped with `checked` after the next check of all documents.
+    let fm = source_file(&checked.cm, &filename, &text);
+    invalidate(checker, &filename);
+
+    let recorder = Recorder::default();
+    recorder.set_completion_target(Some(fm.start_pos + BytePos(offset as u32)));
+
+    checker.set_recorder(Some(recorder.clone()));
+    checker.check(filename);
+    checker.set_recorder(Some(checked.recorder.clone()));
+
+    // Diagnostics are published by type checks of all documents instead.
+    checker.take_errors();
+
+    Some(
+        recorder
+            .completions()
+            .into_iter()
+            .filter(|c| &*c.name != PLACEHOLDER)
+            .map(to_lsp)
+            .collect(),
+    )
+}
+
+/// Drops the analysis results of `filename`, so it's loaded again with the
+/// new content.
+fn invalidate(checker: &OverlayChecker, filename: &Arc<FileName>) {
+    if let Err(err) = checker.invalidate(filename) {
+        debug!("Failed to invalidate `{}`: {:?}", filename, err);
+    }
+}
+
+fn to_lsp(c: Completion) -> CompletionItem {
+    CompletionItem {
+        label: c.name.to_string(),
+        kind: Some(match c.kind {
+            CompletionKind::Var => CompletionItemKind::VARIABLE,
+            CompletionKind::Type => CompletionItemKind::CLASS,
+            CompletionKind::Property => CompletionItemKind::PROPERTY,
+            CompletionKind::Method => CompletionItemKind::METHOD,
+        }),
+        detail: c.ty.as_ref().map(print_type),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use stc_ts_errors::print_type;
+use swc_common::FileName;
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+use crate::{
+    check::Checked,
+    position::{to_byte_pos, to_lsp_range},
+};
+
+/// Shows the type of the innermost node at `pos`.
+pub fn hover(checked: &Checked, path: &Path, pos: Position) -> Option<Hover> {
+    let fm = checked.cm.get_source_file(&FileName::Real(path.to_path_buf()))?;
+    let pos = to_byte_pos(&fm, pos)?;
+
+    let (span, ty) = checked.recorder.type_at(pos)?;
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("```typescript\n{}\n```", print_type(&ty)),
+        }),
+        range: Some(to_lsp_range(&checked.cm, span)),
+    })
+}
@@ -1,23 +1,55 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
 use clap::Args;
+use parking_lot::Mutex;
+use tokio::task::{spawn_blocking, JoinHandle};
 use tower_lsp::{
     async_trait,
     jsonrpc::{self},
     lsp_types::*,
     Client, LanguageServer, LspService, Server,
 };
-use tracing::info;
+use tracing::{error, info};
+
+pub use crate::workspace::{DefaultWorkspaceLoader, LoadWorkspace, Workspace};
+use crate::{check::Checked, overlay::Overlay};
+
+mod check;
+mod completion;
+mod hover;
+mod navigation;
+mod overlay;
+mod position;
+mod workspace;
+
+/// Edits made within this duration are type checked at once.
+const DEBOUNCE: Duration = Duration::from_millis(200);
 
 #[derive(Debug, Args)]
 pub struct LspCommand {}
 
 impl LspCommand {
     pub async fn run(self) -> anyhow::Result<()> {
+        self.run_with(DefaultWorkspaceLoader).await
+    }
+
+    /// Runs the server, which uses `loader` to load the configuration of the
+    /// workspace.
+    pub async fn run_with(self, loader: impl 'static + LoadWorkspace) -> anyhow::Result<()> {
         info!("Starting server");
 
         let stdin = tokio::io::stdin();
         let stdout = tokio::io::stdout();
 
-        let (service, socket) = LspService::new(|client| StcLangServer { client });
+        let loader = Arc::new(loader);
+        let (service, socket) = LspService::new(|client| StcLangServer::new(client, loader));
         Server::new(stdin, stdout, socket).serve(service).await;
 
         Ok(())
@@ -25,15 +57,159 @@ impl LspCommand {
 }
 
 pub struct StcLangServer {
-    #[allow(unused)]
     client: Client,
+
+    loader: Arc<dyn LoadWorkspace>,
+
+    /// The configuration of the workspace, which is loaded on `initialize`.
+    workspace: Mutex<Option<Workspace>>,
+
+    /// Open documents.
+    overlay: Overlay,
+
+    /// The scheduled type check. This is cancelled if documents are modified
+    /// before it publishes diagnostics.
+    pending: Mutex<Option<PendingCheck>>,
+
+    /// The result of the last type check.
+    checked: Arc<Mutex<Option<Arc<Checked>>>>,
+}
+
+struct PendingCheck {
+    task: JoinHandle<()>,
+    /// Aborting [PendingCheck::task] does not stop the type check running in a
+    /// blocking thread, so it checks this flag instead.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PendingCheck {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.task.abort();
+    }
+}
+
+impl StcLangServer {
+    pub fn new(client: Client, loader: Arc<dyn LoadWorkspace>) -> Self {
+        Self {
+            client,
+            loader,
+            workspace: Default::default(),
+            overlay: Default::default(),
+            pending: Default::default(),
+            checked: Default::default(),
+        }
+    }
+
+    /// Returns the configuration of the workspace, or the default one if the
+    /// workspace is not loaded.
+    fn workspace(&self) -> Workspace {
+        self.workspace.lock().get_or_insert_with(|| Workspace::new(None)).clone()
+    }
+
+    /// Returns the result of the last type check, if it's up to date with the
+    /// document at `path`.
+    fn checked(&self, path: &Path) -> Option<Arc<Checked>> {
+        let checked = self.checked.lock().clone()?;
+
+        let version = checked.overlay.get(path)?.version;
+        if self.overlay.get(path)?.version != version {
+            return None;
+        }
+
+        Some(checked)
+    }
+
+    /// Type checks open documents after [DEBOUNCE], and publishes
+    /// diagnostics.
+    ///
+    /// The previous check is cancelled, even if it's already running.
+    fn schedule_check(&self) {
+        let client = self.client.clone();
+        let workspace = self.workspace();
+        let overlay = self.overlay.clone();
+        let last = self.checked.clone();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let task = tokio::spawn({
+            let cancelled = cancelled.clone();
+
+            async move {
+                tokio::time::sleep(DEBOUNCE).await;
+
+                let snapshot = overlay.snapshot();
+                let checked = match spawn_blocking(move || check::check(&workspace, snapshot, &cancelled)).await {
+                    Ok(Some(v)) => Arc::new(v),
+                    Ok(None) => return,
+                    Err(err) => {
+                        error!("Type checking failed: {:?}", err);
+                        return;
+                    }
+                };
+
+                *last.lock() = Some(checked.clone());
+
+                for (path, diagnostics) in &checked.diagnostics {
+                    if let Some(doc) = checked.overlay.get(path) {
+                        client.publish_diagnostics(doc.uri, diagnostics.clone(), Some(doc.version)).await;
+                    }
+                }
+            }
+        });
+
+        if let Some(prev) = self.pending.lock().replace(PendingCheck { task, cancelled }) {
+            prev.cancel();
+        }
+    }
+}
+
+/// Returns [None] for documents which are not files, like untitled ones.
+fn to_path(uri: &Url) -> Option<PathBuf> {
+    uri.to_file_path().ok()
 }
 
 #[async_trait]
 impl LanguageServer for StcLangServer {
-    async fn initialize(&self, _params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        let root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| &folder.uri)
+            .or(params.root_uri.as_ref())
+            .and_then(to_path);
+
+        if let Some(root) = root {
+            let loader = self.loader.clone();
+            let workspace = spawn_blocking(move || loader.load(&root))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|res| res)
+                .unwrap_or_else(|err| {
+                    error!("Failed to load the workspace: {:?}", err);
+                    Workspace::new(None)
+                });
+            *self.workspace.lock() = Some(workspace);
+        }
+
         Ok(InitializeResult {
-            capabilities: ServerCapabilities { ..Default::default() },
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions { include_text: Some(true) })),
+                    ..Default::default()
+                })),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Left(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
             server_info: Some(ServerInfo {
                 name: "stc-ts-lsp".to_string(),
                 version: Some(env!("CARGO_PKG_VERSION").to_string()),
@@ -42,13 +218,141 @@ impl LanguageServer for StcLangServer {
     }
 
     async fn shutdown(&self) -> jsonrpc::Result<()> {
+        if let Some(pending) = self.pending.lock().take() {
+            pending.cancel();
+        }
+
         Ok(())
     }
 
-    async fn hover(&self, _params: HoverParams) -> jsonrpc::Result<Option<Hover>> {
-        Ok(Some(Hover {
-            contents: HoverContents::Scalar(MarkedString::String("hover test".to_string())),
-            range: None,
-        }))
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let doc = params.text_document;
+        if let Some(path) = to_path(&doc.uri) {
+            self.overlay.insert(path, doc.uri, doc.version, doc.text);
+            self.schedule_check();
+        }
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let doc = params.text_document;
+        // We use full synchronization, so the last change is the whole text.
+        let text = match params.content_changes.into_iter().last() {
+            Some(change) => change.text,
+            None => return,
+        };
+
+        if let Some(path) = to_path(&doc.uri) {
+            self.overlay.insert(path, doc.uri, doc.version, text);
+            self.schedule_check();
+        }
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+        if let Some(path) = to_path(&uri) {
+            if let (Some(text), Some(doc)) = (params.text, self.overlay.get(&path)) {
+                self.overlay.insert(path, uri, doc.version, text);
+            }
+            self.schedule_check();
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        if let Some(path) = to_path(&uri) {
+            if self.overlay.remove(&path).is_some() {
+                self.client.publish_diagnostics(uri, vec![], None).await;
+                self.schedule_check();
+            }
+        }
+    }
+
+    async fn hover(&self, params: HoverParams) -> jsonrpc::Result<Option<Hover>> {
+        let params = params.text_document_position_params;
+        let path = match to_path(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .checked(&path)
+            .and_then(|checked| hover::hover(&checked, &path, params.position)))
+    }
+
+    async fn goto_definition(&self, params: GotoDefinitionParams) -> jsonrpc::Result<Option<GotoDefinitionResponse>> {
+        let params = params.text_document_position_params;
+        let path = match to_path(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .checked(&path)
+            .and_then(|checked| navigation::definition(&checked, &path, params.position)))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> jsonrpc::Result<Option<Vec<Location>>> {
+        let include_declaration = params.context.include_declaration;
+        let params = params.text_document_position;
+        let path = match to_path(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .checked(&path)
+            .and_then(|checked| navigation::references(&checked, &path, params.position, include_declaration)))
+    }
+
+    async fn rename(&self, params: RenameParams) -> jsonrpc::Result<Option<WorkspaceEdit>> {
+        if !navigation::is_valid_identifier(&params.new_name) {
+            return Err(jsonrpc::Error::invalid_params(format!(
+                "`{}` is not a valid identifier",
+                params.new_name
+            )));
+        }
+
+        let new_name = params.new_name;
+        let params = params.text_document_position;
+        let path = match to_path(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .checked(&path)
+            .and_then(|checked| navigation::rename(&checked, &path, params.position, &new_name)))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> jsonrpc::Result<Option<CompletionResponse>> {
+        let params = params.text_document_position;
+        let path = match to_path(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        // Completions are computed from the latest text instead of the last type
+        // check, because the user is typing. The checker of the last type check is
+        // reused, so only edited documents are checked again.
+        let workspace = self.workspace();
+        let overlay = self.overlay.snapshot();
+        let last = self.checked.lock().clone();
+        let items = match spawn_blocking(move || {
+            let checked = match last {
+                Some(v) => v,
+                None => Arc::new(check::check(&workspace, overlay.clone(), &AtomicBool::new(false))?),
+            };
+            completion::complete(&checked, &overlay, &path, params.position)
+        })
+        .await
+        {
+            Ok(v) => v,
+            Err(err) => {
+                error!("Completion failed: {:?}", err);
+                return Ok(None);
+            }
+        };
+
+        Ok(items.map(CompletionResponse::Array))
     }
 }
@@ -0,0 +1,104 @@
+//! Go to definition, find references and rename.
+
+use std::{collections::HashMap, path::Path};
+
+use stc_ts_file_analyzer::{recorder::Occurrence, Id};
+use swc_common::{FileName, Span};
+use swc_ecma_ast::Ident;
+use tower_lsp::lsp_types::{GotoDefinitionResponse, Location, Position, TextEdit, Url, WorkspaceEdit};
+
+use crate::{
+    check::Checked,
+    position::{to_byte_pos, to_lsp_range},
+};
+
+/// Imports are skipped, and the original declaration is returned instead.
+pub fn definition(checked: &Checked, path: &Path, pos: Position) -> Option<GotoDefinitionResponse> {
+    let (symbol, occurrences) = occurrences_at(checked, path, pos)?;
+
+    let locations = occurrences
+        .iter()
+        .filter(|o| o.is_decl && o.id == symbol)
+        .filter_map(|o| to_location(checked, o.span))
+        .collect::<Vec<_>>();
+
+    if locations.is_empty() {
+        return None;
+    }
+
+    Some(GotoDefinitionResponse::Array(locations))
+}
+
+pub fn references(checked: &Checked, path: &Path, pos: Position, include_declaration: bool) -> Option<Vec<Location>> {
+    let (_, occurrences) = occurrences_at(checked, path, pos)?;
+
+    Some(
+        occurrences
+            .iter()
+            .filter(|o| include_declaration || !o.is_decl)
+            .filter_map(|o| to_location(checked, o.span))
+            .collect(),
+    )
+}
+
+/// Returns [None] if the symbol at `pos` is not declared in the loaded files,
+/// like globals from the builtin libraries.
+pub fn rename(checked: &Checked, path: &Path, pos: Position, new_name: &str) -> Option<WorkspaceEdit> {
+    let (_, occurrences) = occurrences_at(checked, path, pos)?;
+    if !occurrences.iter().any(|o| o.is_decl) {
+        return None;
+    }
+
+    let mut changes = HashMap::<Url, Vec<TextEdit>>::new();
+    for o in occurrences {
+        if let Some(location) = to_location(checked, o.span) {
+            changes.entry(location.uri).or_default().push(TextEdit {
+                range: location.range,
+                new_text: new_name.to_string(),
+            });
+        }
+    }
+
+    Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    })
+}
+
+pub fn is_valid_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(c) if Ident::is_valid_start(c) => chars.all(Ident::is_valid_continue),
+        _ => false,
+    }
+}
+
+/// Returns the symbol at `pos` and all occurrences of it.
+fn occurrences_at(checked: &Checked, path: &Path, pos: Position) -> Option<(Id, Vec<Occurrence>)> {
+    let fm = checked.cm.get_source_file(&FileName::Real(path.to_path_buf()))?;
+    let pos = to_byte_pos(&fm, pos)?;
+
+    let ident = checked.recorder.ident_at(pos)?;
+
+    Some((checked.recorder.resolve(&ident.id), checked.recorder.occurrences(&ident.id)))
+}
+
+/// Uses the uri of the client for open documents.
+fn to_location(checked: &Checked, span: Span) -> Option<Location> {
+    let fm = checked.cm.lookup_byte_offset(span.lo).sf;
+    let path = match &fm.name {
+        FileName::Real(path) => path,
+        _ => return None,
+    };
+
+    let uri = match checked.overlay.get(path) {
+        Some(doc) => doc.uri,
+        None => Url::from_file_path(path).ok()?,
+    };
+
+    Some(Location {
+        uri,
+        range: to_lsp_range(&checked.cm, span),
+    })
+}
@@ -0,0 +1,146 @@
+//! In-memory contents of documents opened in the editor.
+
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use path_clean::PathClean;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::loader::{file_syntax, DefaultFileLoader, LoadFile};
+use swc_common::{FileName, SourceFile, SourceMap};
+use swc_ecma_loader::resolve::Resolve;
+use swc_ecma_parser::Syntax;
+use tower_lsp::lsp_types::Url;
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    /// The uri used by the client.
+    pub uri: Url,
+    pub version: i32,
+    pub text: Arc<String>,
+}
+
+/// Open documents, keyed by their path.
+///
+/// Cloning this is cheap and clones share the same documents.
+#[derive(Debug, Clone, Default)]
+pub struct Overlay {
+    docs: Arc<RwLock<HashMap<PathBuf, Document>>>,
+}
+
+impl Overlay {
+    pub fn insert(&self, path: PathBuf, uri: Url, version: i32, text: String) {
+        self.docs.write().insert(
+            path,
+            Document {
+                uri,
+                version,
+                text: Arc::new(text),
+            },
+        );
+    }
+
+    /// Returns a copy which is not affected by further edits.
+    pub fn snapshot(&self) -> Overlay {
+        Overlay {
+            docs: Arc::new(RwLock::new(self.docs.read().clone())),
+        }
+    }
+
+    pub fn remove(&self, path: &Path) -> Option<Document> {
+        self.docs.write().remove(path)
+    }
+
+    pub fn get(&self, path: &Path) -> Option<Document> {
+        self.docs.read().get(path).cloned()
+    }
+
+    /// Paths of all open documents, sorted.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.docs.read().keys().cloned().collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+}
+
+/// A [LoadFile] which prefers the text buffers of the editor over the file
+/// system.
+pub struct OverlayFileLoader {
+    pub overlay: Overlay,
+}
+
+impl LoadFile for OverlayFileLoader {
+    fn load_file(&self, cm: &Arc<SourceMap>, filename: &Arc<FileName>) -> Result<(Arc<SourceFile>, Syntax)> {
+        if let FileName::Real(path) = &**filename {
+            if let Some(doc) = self.overlay.get(path) {
+                return Ok((source_file(cm, filename, &doc.text), file_syntax(path)));
+            }
+        }
+
+        DefaultFileLoader.load_file(cm, filename)
+    }
+}
+
+/// Returns the file of `cm` named `filename` with `text`, adding it only if
+/// there's no such file.
+///
+/// The file may be loaded in advance to know its positions. Files with old
+/// content are ignored, as documents can be loaded again after edits.
+pub(crate) fn source_file(cm: &SourceMap, filename: &FileName, text: &str) -> Arc<SourceFile> {
+    let loaded = cm.files().iter().rev().find(|fm| fm.name == *filename && *fm.src == *text).cloned();
+
+    loaded.unwrap_or_else(|| cm.new_source_file(filename.clone(), text.to_string()))
+}
+
+/// A [Resolve] which resolves relative imports to open documents, even if they
+/// are not saved to the disk yet.
+pub struct OverlayResolver {
+    overlay: Overlay,
+    /// Reused so the cache of package.json files is shared by all imports.
+    node: NodeResolver,
+}
+
+impl OverlayResolver {
+    pub fn new(overlay: Overlay) -> Self {
+        Self {
+            overlay,
+            node: NodeResolver::new(),
+        }
+    }
+}
+
+impl Resolve for OverlayResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName> {
+        if let FileName::Real(base) = base {
+            if target.starts_with("./") || target.starts_with("../") {
+                let path = base.parent().unwrap_or_else(|| Path::new(".")).join(target).clean();
+
+                // Extensions are appended, as `./foo.bar` may import `foo.bar.ts`.
+                let candidates = ["ts", "tsx", "d.ts"]
+                    .iter()
+                    .map(|ext| append_extension(&path, ext))
+                    .chain(["index.ts", "index.tsx", "index.d.ts"].iter().map(|index| path.join(index)));
+
+                for candidate in candidates {
+                    if self.overlay.get(&candidate).is_some() {
+                        return Ok(FileName::Real(candidate));
+                    }
+                }
+            }
+        }
+
+        self.node.resolve(base, target)
+    }
+}
+
+fn append_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(ext);
+    path.into()
+}
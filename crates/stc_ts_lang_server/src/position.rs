@@ -0,0 +1,45 @@
+//! Conversion between LSP positions and [BytePos].
+//!
+//! LSP positions are 0-based and count UTF-16 code units.
+
+use swc_common::{BytePos, SourceFile, SourceMap, Span};
+use tower_lsp::lsp_types::{Position, Range};
+
+pub fn to_lsp_position(cm: &SourceMap, pos: BytePos) -> Position {
+    let loc = cm.lookup_char_pos(pos);
+
+    let character = match loc.file.get_line(loc.line - 1) {
+        Some(line) => line.chars().take(loc.col.0).map(char::len_utf16).sum(),
+        None => loc.col.0,
+    };
+
+    Position {
+        line: (loc.line - 1) as _,
+        character: character as _,
+    }
+}
+
+pub fn to_lsp_range(cm: &SourceMap, span: Span) -> Range {
+    Range {
+        start: to_lsp_position(cm, span.lo),
+        end: to_lsp_position(cm, span.hi),
+    }
+}
+
+/// Returns [None] if `pos` is out of `fm`.
+pub fn to_byte_pos(fm: &SourceFile, pos: Position) -> Option<BytePos> {
+    let line_start = *fm.lines.get(pos.line as usize)?;
+    let line = fm.get_line(pos.line as usize)?;
+
+    let mut utf16 = 0;
+    let mut offset = 0;
+    for c in line.chars() {
+        if utf16 >= pos.character as usize {
+            break;
+        }
+        utf16 += c.len_utf16();
+        offset += c.len_utf8();
+    }
+
+    Some(line_start + BytePos(offset as u32))
+}
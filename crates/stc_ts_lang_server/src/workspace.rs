@@ -0,0 +1,55 @@
+//! Configuration of the workspace opened in the editor.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use swc_ecma_ast::EsVersion;
+
+/// The configuration of a workspace, which is used by all type checks.
+#[derive(Clone)]
+pub struct Workspace {
+    pub env: Env,
+
+    /// The directory `type_roots` are relative to. Typings are not loaded if
+    /// this is [None].
+    pub dir: Option<PathBuf>,
+
+    /// `compilerOptions.typeRoots`
+    pub type_roots: Option<Vec<PathBuf>>,
+
+    /// `compilerOptions.types`
+    pub types: Option<Vec<String>>,
+}
+
+impl Workspace {
+    /// Uses the default options and `es2020.full`.
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Workspace {
+            env: Env::simple(Rule::default(), EsVersion::Es2020, ModuleConfig::EsNext, &Lib::load("es2020.full")),
+            dir,
+            type_roots: None,
+            types: None,
+        }
+    }
+}
+
+/// Loads the configuration of a workspace, like its `tsconfig.json`.
+///
+/// This is called once per workspace, and the result is reused by all type
+/// checks.
+pub trait LoadWorkspace: Send + Sync {
+    /// Loads the workspace at `root`.
+    fn load(&self, root: &Path) -> Result<Workspace>;
+}
+
+/// Uses the default options and `es2020.full` for all workspaces.
+pub struct DefaultWorkspaceLoader;
+
+impl LoadWorkspace for DefaultWorkspaceLoader {
+    fn load(&self, root: &Path) -> Result<Workspace> {
+        Ok(Workspace::new(Some(root.to_path_buf())))
+    }
+}
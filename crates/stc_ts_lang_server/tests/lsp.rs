@@ -10,7 +10,7 @@ use serde_json::{json, Value};
 use stc_ts_testing::lsp::LspClient;
 use stc_utils::AHashSet;
 use testing::run_test;
-use tower_lsp::lsp_types::{Diagnostic, PublishDiagnosticsParams};
+use tower_lsp::lsp_types::{CompletionItem, Diagnostic, NumberOrString, PublishDiagnosticsParams};
 use tracing::info;
 
 /// Builds the example lsp command, and returns to the path to it.
@@ -95,16 +95,11 @@ fn handle_configuration_request(client: &mut LspClient, result: Value) {
 #[tracing::instrument(skip_all)]
 #[allow(unused)]
 fn read_diagnostics(client: &mut LspClient) -> CollectedDiagnostics {
-    CollectedDiagnostics(Default::default())
-
-    // TODO: Implement this after implementing lsp
-
-    // let mut diagnostics = vec![];
-    // let (method, response) =
-    // client.read_notification::<PublishDiagnosticsParams>().unwrap();
-    // assert_eq!(method, "textDocument/publishDiagnostics");
-    // diagnostics.push(response.unwrap());
-    // CollectedDiagnostics(diagnostics)
+    let mut diagnostics = vec![];
+    let (method, response) = client.read_notification::<PublishDiagnosticsParams>().unwrap();
+    assert_eq!(method, "textDocument/publishDiagnostics");
+    diagnostics.push(response.unwrap());
+    CollectedDiagnostics(diagnostics)
 }
 
 fn shutdown(client: &mut LspClient) {
@@ -179,7 +174,7 @@ fn test_hover() {
                 "uri": "file:///a/file.ts",
                 "languageId": "typescript",
                 "version": 1,
-                "text": "console.log('foo');\n"
+                "text": "let foo = 1;\nfoo;\n"
               }
             }),
         );
@@ -192,8 +187,8 @@ fn test_hover() {
                     "uri": "file:///a/file.ts"
                   },
                   "position": {
-                    "line": 0,
-                    "character": 5
+                    "line": 1,
+                    "character": 1
                   }
                 }),
             )
@@ -204,7 +199,241 @@ fn test_hover() {
         assert_eq!(
             maybe_res,
             Some(json!({
-              "contents": "hover test",
+              "contents": {
+                "kind": "markdown",
+                "value": "```typescript\nnumber\n```"
+              },
+              "range": {
+                "start": { "line": 1, "character": 0 },
+                "end": { "line": 1, "character": 3 }
+              }
+            }))
+        );
+        shutdown(&mut client);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_diagnostics() {
+    run_test(false, |_cm, _handler| {
+        let mut client = init("initialize_params.json");
+        let diagnostics = did_open(
+            &mut client,
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts",
+                "languageId": "typescript",
+                "version": 1,
+                "text": "export const a: string = 1;\n"
+              }
+            }),
+        );
+
+        let diagnostics = CollectedDiagnostics(diagnostics).viewed();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some(NumberOrString::Number(2322)));
+        assert_eq!(diagnostics[0].range.start.line, 0);
+
+        client
+            .write_notification(
+                "textDocument/didChange",
+                json!({
+                  "textDocument": {
+                    "uri": "file:///a/file.ts",
+                    "version": 2
+                  },
+                  "contentChanges": [
+                    {
+                      "text": "export const a: string = '';\n"
+                    }
+                  ]
+                }),
+            )
+            .unwrap();
+
+        let diagnostics = read_diagnostics(&mut client).viewed_messages();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].version, Some(2));
+        assert_eq!(diagnostics[0].diagnostics, vec![]);
+
+        shutdown(&mut client);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Opens `a.ts` and `b.ts`, where `b.ts` imports `foo` from `a.ts`.
+fn open_two_files(client: &mut LspClient) {
+    open_files(
+        client,
+        &[
+            ("file:///a/a.ts", "export const foo = 1;\n"),
+            ("file:///a/b.ts", "import { foo } from './a';\nfoo;\n"),
+        ],
+    );
+}
+
+/// Opens `files` in order, and waits for the type check which includes the last
+/// one.
+fn open_files(client: &mut LspClient, files: &[(&str, &str)]) {
+    let (last_uri, _) = files.last().unwrap();
+
+    for (i, (uri, text)) in files.iter().enumerate() {
+        let params = json!({
+          "textDocument": {
+            "uri": uri,
+            "languageId": "typescript",
+            "version": 1,
+            "text": text
+          }
+        });
+
+        if i == 0 {
+            did_open(client, params);
+        } else {
+            client.write_notification("textDocument/didOpen", params).unwrap();
+        }
+    }
+
+    while read_diagnostics(client).0.iter().all(|p| p.uri.as_str() != *last_uri) {}
+}
+
+#[test]
+fn test_definition() {
+    run_test(false, |_cm, _handler| {
+        let mut client = init("initialize_params.json");
+        open_two_files(&mut client);
+
+        let (maybe_res, maybe_err) = client
+            .write_request::<_, _, Value>(
+                "textDocument/definition",
+                json!({
+                  "textDocument": {
+                    "uri": "file:///a/b.ts"
+                  },
+                  "position": {
+                    "line": 1,
+                    "character": 1
+                  }
+                }),
+            )
+            .unwrap();
+
+        assert!(maybe_err.is_none());
+        assert_eq!(
+            maybe_res,
+            Some(json!([{
+              "uri": "file:///a/a.ts",
+              "range": {
+                "start": { "line": 0, "character": 13 },
+                "end": { "line": 0, "character": 16 }
+              }
+            }]))
+        );
+        shutdown(&mut client);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_definition_of_renamed_import() {
+    run_test(false, |_cm, _handler| {
+        let mut client = init("initialize_params.json");
+        open_files(
+            &mut client,
+            &[
+                ("file:///a/a.spec.ts", "export const foo = 1;\n"),
+                ("file:///a/b.ts", "import { foo as bar } from './a.spec';\nbar;\n"),
+            ],
+        );
+
+        let (maybe_res, maybe_err) = client
+            .write_request::<_, _, Value>(
+                "textDocument/definition",
+                json!({
+                  "textDocument": {
+                    "uri": "file:///a/b.ts"
+                  },
+                  "position": {
+                    "line": 1,
+                    "character": 1
+                  }
+                }),
+            )
+            .unwrap();
+
+        assert!(maybe_err.is_none());
+        assert_eq!(
+            maybe_res,
+            Some(json!([{
+              "uri": "file:///a/a.spec.ts",
+              "range": {
+                "start": { "line": 0, "character": 13 },
+                "end": { "line": 0, "character": 16 }
+              }
+            }]))
+        );
+        shutdown(&mut client);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_rename() {
+    run_test(false, |_cm, _handler| {
+        let mut client = init("initialize_params.json");
+        open_two_files(&mut client);
+
+        let (maybe_res, maybe_err) = client
+            .write_request::<_, _, Value>(
+                "textDocument/rename",
+                json!({
+                  "textDocument": {
+                    "uri": "file:///a/a.ts"
+                  },
+                  "position": {
+                    "line": 0,
+                    "character": 14
+                  },
+                  "newName": "bar"
+                }),
+            )
+            .unwrap();
+
+        assert!(maybe_err.is_none());
+        assert_eq!(
+            maybe_res,
+            Some(json!({
+              "changes": {
+                "file:///a/a.ts": [{
+                  "range": {
+                    "start": { "line": 0, "character": 13 },
+                    "end": { "line": 0, "character": 16 }
+                  },
+                  "newText": "bar"
+                }],
+                "file:///a/b.ts": [{
+                  "range": {
+                    "start": { "line": 0, "character": 9 },
+                    "end": { "line": 0, "character": 12 }
+                  },
+                  "newText": "bar"
+                }, {
+                  "range": {
+                    "start": { "line": 1, "character": 0 },
+                    "end": { "line": 1, "character": 3 }
+                  },
+                  "newText": "bar"
+                }]
+              }
             }))
         );
         shutdown(&mut client);
@@ -213,3 +442,94 @@ fn test_hover() {
     })
     .unwrap();
 }
+
+#[test]
+fn test_completion() {
+    run_test(false, |_cm, _handler| {
+        let mut client = init("initialize_params.json");
+        did_open(
+            &mut client,
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts",
+                "languageId": "typescript",
+                "version": 1,
+                "text": "class C {\n    a = 1;\n    private b = '';\n    m() {}\n}\ndeclare const c: C;\nc.\n"
+              }
+            }),
+        );
+
+        let (maybe_res, maybe_err) = client
+            .write_request::<_, _, Vec<CompletionItem>>(
+                "textDocument/completion",
+                json!({
+                  "textDocument": {
+                    "uri": "file:///a/file.ts"
+                  },
+                  "position": {
+                    "line": 6,
+                    "character": 2
+                  }
+                }),
+            )
+            .unwrap();
+
+        assert!(maybe_err.is_none());
+        let items = maybe_res.unwrap();
+
+        // `b` is private.
+        let labels = items.iter().map(|item| &*item.label).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["a", "m"]);
+        assert_eq!(items[0].detail.as_deref(), Some("number"));
+        shutdown(&mut client);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_completion_of_private_members() {
+    run_test(false, |_cm, _handler| {
+        let mut client = init("initialize_params.json");
+        did_open(
+            &mut client,
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts",
+                "languageId": "typescript",
+                "version": 1,
+                "text": "class C {\n    a = 1;\n    private b = '';\n    m(c: C) {\n        c.\n    }\n}\nclass D {\n    m(c: C) {\n        c.\n    }\n}\n"
+              }
+            }),
+        );
+
+        let mut complete = |line: u32| {
+            let (maybe_res, maybe_err) = client
+                .write_request::<_, _, Vec<CompletionItem>>(
+                    "textDocument/completion",
+                    json!({
+                      "textDocument": {
+                        "uri": "file:///a/file.ts"
+                      },
+                      "position": {
+                        "line": line,
+                        "character": 10
+                      }
+                    }),
+                )
+                .unwrap();
+
+            assert!(maybe_err.is_none());
+            maybe_res.unwrap().into_iter().map(|item| item.label).collect::<Vec<_>>()
+        };
+
+        // `b` is accessible only within `C`.
+        assert_eq!(complete(4), vec!["a", "b", "m"]);
+        assert_eq!(complete(9), vec!["a", "m"]);
+        shutdown(&mut client);
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -2,28 +2,236 @@ use std::{
     fs::File,
     io::BufReader,
     path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
 };
 
 use anyhow::{bail, Context, Error};
+use dashmap::DashMap;
+use fxhash::FxBuildHasher;
+use indexmap::IndexMap;
 use path_clean::PathClean;
 use serde::Deserialize;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
+/// Extensions tried for imports without an extension.
 static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
 
-#[derive(Deserialize)]
+/// Extensions of files which can be type checked. Declaration files like
+/// `.d.mts` are included.
+static TS_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts"];
+
+/// Imports of JavaScript files are resolved to TypeScript files with the same
+/// name.
+static EXTENSION_SUBSTITUTIONS: &[(&str, &[&str])] = &[
+    ("js", &["ts", "tsx", "d.ts"]),
+    ("jsx", &["tsx", "d.ts"]),
+    ("mjs", &["mts", "d.mts"]),
+    ("cjs", &["cts", "d.cts"]),
+];
+
+/// Extensions tried after [EXTENSIONS] if JavaScript files are allowed.
+static JS_EXTENSIONS: &[&str] = &["js", "jsx"];
+
+/// Conditions of `exports` and `imports` matched by
+/// [ModuleResolution::Node10].
+static ALL_CONDITIONS: &[&str] = &["types", "import", "require", "node", "default"];
+
+static ESM_CONDITIONS: &[&str] = &["types", "import", "node", "default"];
+
+static CJS_CONDITIONS: &[&str] = &["types", "require", "node", "default"];
+
+static BUNDLER_CONDITIONS: &[&str] = &["types", "import", "default"];
+
+/// The version of TypeScript used to select `typesVersions`.
+const TS_VERSION: [u64; 3] = [4, 9, 0];
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct PackageJson {
     #[serde(default)]
     types: Option<String>,
+
+    #[serde(default)]
+    typings: Option<String>,
+
+    #[serde(default)]
+    main: Option<String>,
+
+    /// `"module"` if `.js` files of the package are ES modules.
+    #[serde(default, rename = "type")]
+    ty: Option<String>,
+
+    #[serde(default)]
+    exports: Option<Exports>,
+
+    #[serde(default)]
+    imports: Option<IndexMap<String, Exports>>,
+
+    /// Version ranges to mappings of paths.
+    #[serde(default)]
+    types_versions: Option<IndexMap<String, IndexMap<String, Vec<String>>>>,
+}
+
+/// `exports` of package.json, or a target of it.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Exports {
+    Path(String),
+    /// Subpaths if keys start with `.`, and conditions otherwise.
+    Map(IndexMap<String, Exports>),
+    /// Fallbacks.
+    Array(Vec<Exports>),
+    /// Excludes a subpath.
+    Null,
+}
+
+impl PackageJson {
+    fn load(path: &Path) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+
+        serde_json::from_reader(reader).context("failed to deserialize package.json")
+    }
+
+    /// Returns candidates for `path` redirected by `typesVersions`.
+    fn redirect(&self, path: &str) -> Option<Vec<String>> {
+        let (_, paths) = self.types_versions.as_ref()?.iter().find(|(range, _)| version_matches(range))?;
+        let (targets, star) = match_pattern(paths, path)?;
+
+        Some(
+            targets
+                .iter()
+                .map(|target| match star {
+                    Some(star) => target.replacen('*', star, 1),
+                    None => target.clone(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// `moduleResolution` of tsconfig.
+///
+/// See https://www.typescriptlang.org/tsconfig#moduleResolution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModuleResolution {
+    /// `node10`, or `node`
+    #[default]
+    Node10,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
+    /// `bundler`
+    Bundler,
+}
+
+impl FromStr for ModuleResolution {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "node" | "node10" => Ok(ModuleResolution::Node10),
+            "node16" => Ok(ModuleResolution::Node16),
+            "nodenext" => Ok(ModuleResolution::NodeNext),
+            "bundler" => Ok(ModuleResolution::Bundler),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ModuleResolution {
+    /// Returns `true` if ES modules and CommonJS modules are resolved
+    /// differently.
+    fn is_node_esm_aware(self) -> bool {
+        matches!(self, ModuleResolution::Node16 | ModuleResolution::NodeNext)
+    }
 }
 
 #[derive(Default)]
-pub struct NodeResolver;
+pub struct NodeResolver {
+    mode: ModuleResolution,
+    /// `allowJs` of tsconfig.
+    allow_js: bool,
+    /// package.json files by the directory containing them. [None] means the
+    /// directory does not have one.
+    package_jsons: DashMap<PathBuf, Option<Arc<PackageJson>>, FxBuildHasher>,
+}
 
 impl NodeResolver {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_mode(mode: ModuleResolution) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Resolves imports to JavaScript files if there's no TypeScript file
+    /// for them. Files in `node_modules` are not resolved.
+    pub fn allow_js(self, allow_js: bool) -> Self {
+        Self { allow_js, ..self }
+    }
+
+    /// Loads the package.json in `pkg_dir`. It is read only once per
+    /// directory, because it's used while resolving every import.
+    ///
+    /// A package.json which cannot be parsed is treated as an empty one.
+    fn package_json(&self, pkg_dir: &Path) -> Option<Arc<PackageJson>> {
+        if let Some(pkg) = self.package_jsons.get(pkg_dir) {
+            return pkg.clone();
+        }
+
+        let pkg_path = pkg_dir.join("package.json");
+        let pkg = if pkg_path.is_file() {
+            Some(Arc::new(PackageJson::load(&pkg_path).unwrap_or_default()))
+        } else {
+            None
+        };
+
+        self.package_jsons.insert(pkg_dir.to_path_buf(), pkg.clone());
+        pkg
+    }
+
+    fn accepts_js(&self, path: &Path) -> bool {
+        self.allow_js && !path.components().any(|c| c.as_os_str() == "node_modules")
+    }
+
+    /// Returns `true` if `file` is an ES module, which is decided by its
+    /// extension and `"type"` of the nearest package.json.
+    fn is_esm(&self, file: &Path) -> bool {
+        let name = file.to_string_lossy();
+        if name.ends_with(".mts") {
+            return true;
+        }
+        if name.ends_with(".cts") {
+            return false;
+        }
+
+        let mut dir = file.parent();
+        while let Some(pkg_dir) = dir {
+            if let Some(pkg) = self.package_json(pkg_dir) {
+                return pkg.ty.as_deref() == Some("module");
+            }
+
+            dir = pkg_dir.parent();
+        }
+
+        false
+    }
+
+    fn conditions(&self, esm: bool) -> &'static [&'static str] {
+        match self.mode {
+            ModuleResolution::Node10 => ALL_CONDITIONS,
+            ModuleResolution::Node16 | ModuleResolution::NodeNext if esm => ESM_CONDITIONS,
+            ModuleResolution::Node16 | ModuleResolution::NodeNext => CJS_CONDITIONS,
+            ModuleResolution::Bundler => BUNDLER_CONDITIONS,
+        }
     }
 
     fn wrap(&self, path: PathBuf) -> Result<FileName, Error> {
@@ -34,18 +242,41 @@ impl NodeResolver {
     /// Resolve a path as a file. If `path` refers to a file, it is returned;
     /// otherwise the `path` + each extension is tried.
     pub fn resolve_as_file(&self, path: &Path) -> Result<PathBuf, Error> {
+        if let Ok(resolved) = self.resolve_target_file(path) {
+            return Ok(resolved);
+        }
+
         // 1. If X is a file, load X as JavaScript text.
-        if path.is_file() {
+        if path.is_file() && (!is_js_file(path) || self.accepts_js(path)) {
             return Ok(path.to_path_buf());
         }
 
-        for ext in EXTENSIONS {
-            let ext_path = path.with_extension(ext);
-            if ext_path.is_file() {
-                return Ok(ext_path);
+        bail!("file not found: {}", path.display())
+    }
+
+    /// Resolve a path which has an extension, without trying other
+    /// extensions. `.js` is substituted with `.ts` and so on.
+    pub fn resolve_with_extension(&self, path: &Path) -> Result<PathBuf, Error> {
+        if is_ts_file(path) && path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+
+        let ext = path.extension().and_then(|ext| ext.to_str());
+        if let Some((_, substitutes)) = EXTENSION_SUBSTITUTIONS.iter().find(|(js, _)| Some(*js) == ext) {
+            let stem = path.with_extension("");
+
+            for ts_ext in *substitutes {
+                let ts_path = append_extension(&stem, ts_ext);
+                if ts_path.is_file() {
+                    return Ok(ts_path);
+                }
             }
         }
 
+        if is_js_file(path) && self.accepts_js(path) && path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+
         bail!("file not found: {}", path.display())
     }
 
@@ -53,9 +284,8 @@ impl NodeResolver {
     /// file if it exists, or resolving to the index.EXT file if it exists.
     pub fn resolve_as_directory(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X/package.json is a file, use it.
-        let pkg_path = path.join("package.json");
-        if pkg_path.is_file() {
-            let main = self.resolve_using_package_json(&pkg_path);
+        if let Some(pkg) = self.package_json(path) {
+            let main = self.resolve_using_package_json(path, &pkg);
             if main.is_ok() {
                 return main;
             }
@@ -65,21 +295,31 @@ impl NodeResolver {
         self.resolve_index(path)
     }
 
-    /// Resolve using the package.json "main" key.
-    fn resolve_using_package_json(&self, pkg_path: &PathBuf) -> Result<PathBuf, Error> {
-        // TODO: how to not always initialize this here?
-        let root = PathBuf::from("/");
-        let pkg_dir = pkg_path.parent().unwrap_or(&root);
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson = serde_json::from_reader(reader).context("failed to deserialize package.json")?;
+    /// Resolve using the package.json "types", "typings" or "main" key.
+    fn resolve_using_package_json(&self, pkg_dir: &Path, pkg: &PackageJson) -> Result<PathBuf, Error> {
+        let types = pkg.types.as_deref().or(pkg.typings.as_deref());
+
+        let entry = types.unwrap_or("index.d.ts");
+        if let Some(candidates) = pkg.redirect(entry.trim_start_matches("./")) {
+            for candidate in candidates {
+                let path = pkg_dir.join(candidate);
+                if let Ok(resolved) = self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path)) {
+                    return Ok(resolved);
+                }
+            }
+        }
 
-        if let Some(target) = &pkg.types {
+        if let Some(target) = types {
             let path = pkg_dir.join(target);
             return self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path));
         }
 
-        bail!("package.json does not contain a \"main\" string")
+        // Declarations next to the JavaScript file.
+        if let Some(main) = &pkg.main {
+            return self.resolve_target_file(&pkg_dir.join(main));
+        }
+
+        bail!("package.json does not contain a \"types\" string")
     }
 
     /// Resolve a directory to its index.EXT.
@@ -87,7 +327,7 @@ impl NodeResolver {
         // 1. If X/index.js is a file, load X/index.js as JavaScript text.
         // 2. If X/index.json is a file, parse X/index.json to a JavaScript object.
         // 3. If X/index.node is a file, load X/index.node as binary addon.
-        for ext in EXTENSIONS {
+        for ext in self.extensions(path) {
             let ext_path = path.join(format!("index.{}", ext));
             if ext_path.is_file() {
                 return Ok(ext_path);
@@ -97,25 +337,164 @@ impl NodeResolver {
         bail!("index not found: {}", path.display())
     }
 
-    fn try_package(&self, pkg_dir: &Path) -> Result<PathBuf, Error> {
-        self.resolve_as_file(pkg_dir).or_else(|_| self.resolve_as_directory(pkg_dir))
+    /// Resolves a file referenced by package.json, which may be a JavaScript
+    /// file with declarations next to it.
+    fn resolve_target_file(&self, path: &Path) -> Result<PathBuf, Error> {
+        if let Ok(resolved) = self.resolve_with_extension(path) {
+            return Ok(resolved);
+        }
+
+        for ext in self.extensions(path) {
+            let ext_path = append_extension(path, ext);
+            if ext_path.is_file() {
+                return Ok(ext_path);
+            }
+        }
+
+        bail!("file not found: {}", path.display())
+    }
+
+    /// Extensions to try for `path` without an extension.
+    fn extensions(&self, path: &Path) -> impl Iterator<Item = &'static str> {
+        let js: &[&str] = if self.accepts_js(path) { JS_EXTENSIONS } else { &[] };
+
+        EXTENSIONS.iter().chain(js).copied()
+    }
+
+    /// `subpath` is `.` for the package itself, and starts with `./`
+    /// otherwise.
+    fn resolve_package(&self, pkg_dir: &Path, subpath: &str, conditions: &[&str]) -> Result<PathBuf, Error> {
+        let pkg = self.package_json(pkg_dir);
+
+        if let Some(exports) = pkg.as_ref().and_then(|pkg| pkg.exports.as_ref()) {
+            // Files which are not exported are not accessible, even if they exist.
+            return self
+                .resolve_exports(pkg_dir, exports, subpath, conditions)
+                .with_context(|| format!("`{}` is not exported from `{}`", subpath, pkg_dir.join("package.json").display()));
+        }
+
+        if subpath == "." {
+            return self.resolve_as_file(pkg_dir).or_else(|_| self.resolve_as_directory(pkg_dir));
+        }
+
+        let subpath = &subpath[2..];
+        if let Some(candidates) = pkg.as_ref().and_then(|pkg| pkg.redirect(subpath)) {
+            for candidate in candidates {
+                let path = pkg_dir.join(candidate);
+                if let Ok(resolved) = self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path)) {
+                    return Ok(resolved);
+                }
+            }
+        }
+
+        let path = pkg_dir.join(subpath);
+        self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path))
+    }
+
+    fn resolve_exports(&self, pkg_dir: &Path, exports: &Exports, subpath: &str, conditions: &[&str]) -> Result<PathBuf, Error> {
+        match exports {
+            Exports::Map(map) if map.keys().any(|key| key.starts_with('.')) => {
+                let (target, star) = match_pattern(map, subpath).context("no matching subpath")?;
+
+                self.resolve_target(pkg_dir, target, star, false, conditions)
+            }
+            // Shorthand for `{ ".": exports }`
+            _ if subpath == "." => self.resolve_target(pkg_dir, exports, None, false, conditions),
+            _ => bail!("only the main entry is exported"),
+        }
+    }
+
+    /// Resolves `#specifier` using `imports` of the nearest package.json.
+    fn resolve_imports(&self, base_dir: &Path, target: &str, conditions: &[&str]) -> Result<PathBuf, Error> {
+        let mut dir = Some(base_dir);
+
+        while let Some(pkg_dir) = dir {
+            if let Some(pkg) = self.package_json(pkg_dir) {
+                let imports = pkg
+                    .imports
+                    .as_ref()
+                    .with_context(|| format!("`{}` does not have \"imports\"", pkg_dir.join("package.json").display()))?;
+                let (target, star) = match_pattern(imports, target).context("no matching import")?;
+
+                return self.resolve_target(pkg_dir, target, star, true, conditions);
+            }
+
+            dir = pkg_dir.parent();
+        }
+
+        bail!("package.json not found")
+    }
+
+    /// Resolves a target of `exports` or `imports`. Only targets of `imports`
+    /// can be other packages.
+    fn resolve_target(
+        &self,
+        pkg_dir: &Path,
+        target: &Exports,
+        star: Option<&str>,
+        allow_packages: bool,
+        conditions: &[&str],
+    ) -> Result<PathBuf, Error> {
+        match target {
+            Exports::Path(path) => {
+                let path = match star {
+                    Some(star) => path.replace('*', star),
+                    None => path.clone(),
+                };
+
+                if path.starts_with("./") {
+                    return self.resolve_target_file(&pkg_dir.join(path));
+                }
+
+                if allow_packages && !path.starts_with('/') && !path.starts_with("../") {
+                    return self.resolve_node_modules(pkg_dir, &path, conditions);
+                }
+
+                bail!("invalid target: {}", path)
+            }
+            Exports::Map(map) => {
+                for (condition, target) in map {
+                    if !conditions.contains(&&**condition) {
+                        continue;
+                    }
+
+                    if let Ok(resolved) = self.resolve_target(pkg_dir, target, star, allow_packages, conditions) {
+                        return Ok(resolved);
+                    }
+                }
+
+                bail!("no matching condition")
+            }
+            Exports::Array(targets) => {
+                for target in targets {
+                    if let Ok(resolved) = self.resolve_target(pkg_dir, target, star, allow_packages, conditions) {
+                        return Ok(resolved);
+                    }
+                }
+
+                bail!("no valid target")
+            }
+            Exports::Null => bail!("excluded"),
+        }
     }
 
     /// Resolve by walking up node_modules folders.
-    fn resolve_node_modules(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+    fn resolve_node_modules(&self, base_dir: &Path, target: &str, conditions: &[&str]) -> Result<PathBuf, Error> {
+        let (name, subpath) = split_package_specifier(target);
+
         let node_modules = base_dir.join("node_modules");
         if node_modules.is_dir() {
-            let path = node_modules.join(target);
-            let result = self.try_package(&path);
+            let path = node_modules.join(name);
+            let result = self.resolve_package(&path, &subpath, conditions);
             if result.is_ok() {
                 return result;
             }
 
             {
-                let types = node_modules.join("@types").join(target);
+                let types = node_modules.join("@types").join(types_package_name(name));
 
                 if types.is_dir() {
-                    let result = self.try_package(&types);
+                    let result = self.resolve_package(&types, &subpath, conditions);
 
                     if result.is_ok() {
                         return result;
@@ -125,7 +504,7 @@ impl NodeResolver {
         }
 
         match base_dir.parent() {
-            Some(parent) => self.resolve_node_modules(parent, target),
+            Some(parent) => self.resolve_node_modules(parent, target, conditions),
             None => bail!("not found"),
         }
     }
@@ -135,9 +514,7 @@ impl Resolve for NodeResolver {
     fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
         let base = match base {
             FileName::Real(base) => &**base,
-            _ => {
-                unreachable!("base = {:?}; target = {:?}", base, target)
-            }
+            _ => bail!("cannot resolve `{}` from `{}`", target, base),
         };
         // Absolute path
         if target.starts_with('/') {
@@ -153,8 +530,27 @@ impl Resolve for NodeResolver {
         let cwd = &Path::new(".");
         let base_dir = base.parent().unwrap_or(cwd);
 
+        let esm = self.mode.is_node_esm_aware() && self.is_esm(base);
+        let conditions = self.conditions(esm);
+
         if target.starts_with("./") || target.starts_with("../") {
             let path = base_dir.join(target);
+
+            // Relative imports of ES modules should have an extension, and
+            // directories cannot be imported.
+            if esm {
+                return self
+                    .resolve_with_extension(&path)
+                    .with_context(|| {
+                        format!(
+                            "failed to resolve `{}` as an ES module dependency from `{}`",
+                            target,
+                            base.display()
+                        )
+                    })
+                    .and_then(|p| self.wrap(p));
+            }
+
             return self
                 .resolve_as_file(&path)
                 .with_context(|| format!("failed to resolve `{}` as a file dependency from `{}`", target, base.display()))
@@ -165,8 +561,115 @@ impl Resolve for NodeResolver {
                 .and_then(|p| self.wrap(p));
         }
 
-        self.resolve_node_modules(base_dir, target)
+        if target.starts_with('#') {
+            return self
+                .resolve_imports(base_dir, target, conditions)
+                .with_context(|| format!("failed to resolve `{}` as a package import from `{}`", target, base.display()))
+                .and_then(|p| self.wrap(p));
+        }
+
+        self.resolve_node_modules(base_dir, target, conditions)
             .with_context(|| format!("failed to resolve `{}` as a node module from `{}`", target, base.display()))
             .and_then(|p| self.wrap(p))
     }
 }
+
+/// Returns `true` for `.d.ts`, `.d.mts` and `.d.cts` files.
+pub fn is_declaration_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    [".d.ts", ".d.mts", ".d.cts"].iter().any(|ext| name.ends_with(ext))
+}
+
+/// Returns `true` for `.js`, `.jsx`, `.mjs` and `.cjs` files.
+pub fn is_js_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    [".js", ".jsx", ".mjs", ".cjs"].iter().any(|ext| name.ends_with(ext))
+}
+
+fn is_ts_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    TS_EXTENSIONS.iter().any(|ext| name.ends_with(&format!(".{}", ext)))
+}
+
+/// Unlike [Path::with_extension], this does not replace the existing
+/// extension.
+fn append_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_os_string();
+    path.push(".");
+    path.push(ext);
+    path.into()
+}
+
+/// Splits `@scope/pkg/sub` into `@scope/pkg` and `./sub`.
+fn split_package_specifier(target: &str) -> (&str, String) {
+    let mut separators = target.match_indices('/').map(|(idx, _)| idx);
+    if target.starts_with('@') {
+        separators.next();
+    }
+
+    match separators.next() {
+        Some(idx) => (&target[..idx], format!(".{}", &target[idx..])),
+        None => (target, ".".into()),
+    }
+}
+
+/// `@scope/pkg` is published as `@types/scope__pkg`.
+fn types_package_name(name: &str) -> String {
+    match name.strip_prefix('@') {
+        Some(scoped) => scoped.replacen('/', "__", 1),
+        None => name.to_string(),
+    }
+}
+
+/// Finds the value for `key` in `patterns`, where keys may contain a `*`.
+///
+/// Exact matches are preferred, and the pattern with the longest prefix wins
+/// among others. Returns the string matched by `*` together.
+pub(crate) fn match_pattern<'a, 'b, T>(patterns: &'a IndexMap<String, T>, key: &'b str) -> Option<(&'a T, Option<&'b str>)> {
+    if let Some(value) = patterns.get(key) {
+        return Some((value, None));
+    }
+
+    patterns
+        .iter()
+        .filter_map(|(pattern, value)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            let star = key.strip_prefix(prefix)?.strip_suffix(suffix)?;
+
+            Some((prefix.len(), value, star))
+        })
+        .max_by_key(|(prefix_len, ..)| *prefix_len)
+        .map(|(_, value, star)| (value, Some(star)))
+}
+
+/// Returns `true` if [TS_VERSION] satisfies `range`, like `>=4.2` or `*`.
+fn version_matches(range: &str) -> bool {
+    range.split("||").any(|set| {
+        set.split_whitespace().all(|comparator| {
+            if comparator == "*" {
+                return true;
+            }
+
+            let (op, version) = [">=", "<=", ">", "<", "="]
+                .iter()
+                .find_map(|op| comparator.strip_prefix(op).map(|version| (*op, version)))
+                .unwrap_or(("=", comparator));
+
+            let mut parts = [0; 3];
+            for (part, s) in parts.iter_mut().zip(version.split('.')) {
+                match s.parse() {
+                    Ok(v) => *part = v,
+                    Err(_) => return false,
+                }
+            }
+
+            match op {
+                ">=" => TS_VERSION >= parts,
+                "<=" => TS_VERSION <= parts,
+                ">" => TS_VERSION > parts,
+                "<" => TS_VERSION < parts,
+                _ => TS_VERSION == parts,
+            }
+        })
+    })
+}
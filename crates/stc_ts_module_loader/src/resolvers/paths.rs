@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Error;
+use indexmap::IndexMap;
+use path_clean::PathClean;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::node::{match_pattern, NodeResolver};
+
+/// `baseUrl`, `paths` and `rootDirs` of tsconfig. Paths should be absolute.
+///
+/// See https://www.typescriptlang.org/docs/handbook/module-resolution.html#path-mapping
+#[derive(Debug, Default, Clone)]
+pub struct PathsConfig {
+    pub base_url: Option<PathBuf>,
+
+    /// Patterns to candidates, where both may contain a `*`.
+    pub paths: IndexMap<String, Vec<String>>,
+
+    /// The directory which candidates of [PathsConfig::paths] are relative
+    /// to, which is the directory of tsconfig declaring `paths`.
+    ///
+    /// [PathsConfig::base_url] is used instead if it's specified.
+    pub paths_base: Option<PathBuf>,
+
+    /// Directories of which contents are merged as if they are one directory.
+    pub root_dirs: Vec<PathBuf>,
+}
+
+/// Applies [PathsConfig] before resolving like [NodeResolver].
+pub struct PathsResolver {
+    inner: NodeResolver,
+    config: PathsConfig,
+}
+
+impl PathsResolver {
+    pub fn new(inner: NodeResolver, config: PathsConfig) -> Self {
+        Self { inner, config }
+    }
+
+    fn resolve_path(&self, path: &Path) -> Result<PathBuf, Error> {
+        self.inner.resolve_as_file(path).or_else(|_| self.inner.resolve_as_directory(path))
+    }
+
+    /// Candidates are tried in order, and the first one which exists wins.
+    fn resolve_using_paths(&self, target: &str) -> Option<PathBuf> {
+        let base = self.config.base_url.as_ref().or(self.config.paths_base.as_ref())?;
+        let (candidates, star) = match_pattern(&self.config.paths, target)?;
+
+        for candidate in candidates {
+            let candidate = match star {
+                Some(star) => candidate.replacen('*', star, 1),
+                None => candidate.clone(),
+            };
+
+            if let Ok(resolved) = self.resolve_path(&base.join(candidate)) {
+                return Some(resolved);
+            }
+        }
+
+        None
+    }
+
+    /// Resolves a relative import against each of `rootDirs`, starting from
+    /// the one containing the importing file.
+    fn resolve_using_root_dirs(&self, base_dir: &Path, target: &str) -> Option<PathBuf> {
+        let candidate = base_dir.join(target).clean();
+
+        let matched = self
+            .config
+            .root_dirs
+            .iter()
+            .filter(|dir| candidate.starts_with(dir))
+            .max_by_key(|dir| dir.components().count())?;
+        let suffix = candidate.strip_prefix(matched).ok()?;
+
+        let dirs = Some(matched)
+            .into_iter()
+            .chain(self.config.root_dirs.iter().filter(|dir| *dir != matched));
+        for dir in dirs {
+            if let Ok(resolved) = self.resolve_path(&dir.join(suffix)) {
+                return Some(resolved);
+            }
+        }
+
+        None
+    }
+}
+
+impl Resolve for PathsResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let base_path = match base {
+            FileName::Real(base) => &**base,
+            _ => return self.inner.resolve(base, target),
+        };
+
+        if target.starts_with("./") || target.starts_with("../") {
+            let base_dir = base_path.parent().unwrap_or_else(|| Path::new("."));
+
+            if let Some(resolved) = self.resolve_using_root_dirs(base_dir, target) {
+                return Ok(FileName::Real(resolved.clean()));
+            }
+        } else if !target.starts_with('/') {
+            // If none of the candidates exists, `baseUrl` and `node_modules`
+            // are tried like `tsc`.
+            if let Some(resolved) = self.resolve_using_paths(target) {
+                return Ok(FileName::Real(resolved.clean()));
+            }
+
+            if let Some(base_url) = &self.config.base_url {
+                if let Ok(resolved) = self.resolve_path(&base_url.join(target)) {
+                    return Ok(FileName::Real(resolved.clean()));
+                }
+            }
+        }
+
+        self.inner.resolve(base, target)
+    }
+}
@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+
+use stc_ts_module_loader::resolvers::node::{ModuleResolution, NodeResolver};
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixture").join("resolve")
+}
+
+fn resolve(target: &str) -> Option<PathBuf> {
+    resolve_from(ModuleResolution::Node10, "src/index.ts", target)
+}
+
+fn resolve_from(mode: ModuleResolution, base: &str, target: &str) -> Option<PathBuf> {
+    let base = FileName::Real(fixture_dir().join(base));
+
+    match NodeResolver::with_mode(mode).resolve(&base, target).ok()? {
+        FileName::Real(path) => Some(path),
+        _ => None,
+    }
+}
+
+fn expected(path: &str) -> Option<PathBuf> {
+    Some(fixture_dir().join(path))
+}
+
+#[test]
+fn exports_conditions() {
+    assert_eq!(resolve("exports"), expected("node_modules/exports/types/index.d.ts"));
+}
+
+#[test]
+fn exports_subpath_pattern() {
+    assert_eq!(resolve("exports/sub/a"), expected("node_modules/exports/dist/sub/a.d.ts"));
+}
+
+#[test]
+fn exports_not_exported() {
+    assert_eq!(resolve("exports/types/index"), None);
+    assert_eq!(resolve("exports/private/x"), None);
+}
+
+#[test]
+fn typings() {
+    assert_eq!(resolve("typings"), expected("node_modules/typings/lib/main.d.ts"));
+}
+
+#[test]
+fn types_versions() {
+    assert_eq!(resolve("versioned"), expected("node_modules/versioned/ts4.2/index.d.ts"));
+    assert_eq!(resolve("versioned/sub"), expected("node_modules/versioned/ts4.2/sub.d.ts"));
+}
+
+#[test]
+fn scoped_types_package() {
+    assert_eq!(resolve("@scope/pkg"), expected("node_modules/@types/scope__pkg/index.d.ts"));
+}
+
+#[test]
+fn package_imports() {
+    assert_eq!(resolve("#internal/a"), expected("src/internal/a.ts"));
+}
+
+#[test]
+fn js_extension_substitution() {
+    assert_eq!(resolve("./internal/a.js"), expected("src/internal/a.ts"));
+    assert_eq!(resolve("./internal/b.mjs"), expected("src/internal/b.mts"));
+    assert_eq!(resolve("./internal/c.cjs"), expected("src/internal/c.d.cts"));
+}
+
+#[test]
+fn node16_esm_requires_extension() {
+    let resolve = |target| resolve_from(ModuleResolution::Node16, "esm/index.ts", target);
+
+    assert_eq!(resolve("./a.js"), expected("esm/a.ts"));
+    assert_eq!(resolve("./a"), None);
+    assert_eq!(resolve("./dir"), None);
+    assert_eq!(resolve("./dir/index.js"), expected("esm/dir/index.ts"));
+}
+
+#[test]
+fn node16_cjs_allows_extensionless() {
+    assert_eq!(
+        resolve_from(ModuleResolution::Node16, "src/index.ts", "./internal/a"),
+        expected("src/internal/a.ts")
+    );
+}
+
+#[test]
+fn node16_conditions() {
+    assert_eq!(
+        resolve_from(ModuleResolution::Node16, "esm/index.ts", "dual"),
+        expected("node_modules/dual/esm/index.d.mts")
+    );
+    assert_eq!(
+        resolve_from(ModuleResolution::NodeNext, "src/index.ts", "dual"),
+        expected("node_modules/dual/cjs/index.d.cts")
+    );
+    assert_eq!(
+        resolve_from(ModuleResolution::NodeNext, "src/internal/b.mts", "dual"),
+        expected("node_modules/dual/esm/index.d.mts")
+    );
+}
+
+#[test]
+fn bundler() {
+    assert_eq!(
+        resolve_from(ModuleResolution::Bundler, "esm/index.ts", "./dir"),
+        expected("esm/dir/index.ts")
+    );
+    assert_eq!(
+        resolve_from(ModuleResolution::Bundler, "src/index.ts", "dual"),
+        expected("node_modules/dual/esm/index.d.mts")
+    );
+}
+
+#[test]
+fn allow_js() {
+    let base = FileName::Real(fixture_dir().join("src/index.ts"));
+    let resolver = NodeResolver::new().allow_js(true);
+    let resolve = |target| match resolver.resolve(&base, target).ok()? {
+        FileName::Real(path) => Some(path),
+        _ => None,
+    };
+
+    assert_eq!(resolve("./js/util"), expected("src/js/util.js"));
+    assert_eq!(resolve("./js/util.js"), expected("src/js/util.js"));
+    assert_eq!(resolve("./js"), expected("src/js/index.js"));
+    assert_eq!(resolve("./internal/a.js"), expected("src/internal/a.ts"));
+}
+
+#[test]
+fn js_not_allowed() {
+    assert_eq!(resolve("./js/util"), None);
+    assert_eq!(resolve("./js/util.js"), None);
+    assert_eq!(resolve("./js"), None);
+}
@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use stc_ts_module_loader::resolvers::{
+    node::NodeResolver,
+    paths::{PathsConfig, PathsResolver},
+};
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixture").join("paths")
+}
+
+fn resolver() -> PathsResolver {
+    let root = fixture_dir();
+
+    let mut paths = IndexMap::new();
+    paths.insert("@app/*".to_string(), vec!["missing/*".to_string(), "*".to_string()]);
+    paths.insert("@app/core/*".to_string(), vec!["core/*".to_string()]);
+    paths.insert("@lib".to_string(), vec!["../lib/index.ts".to_string()]);
+
+    PathsResolver::new(
+        NodeResolver::new(),
+        PathsConfig {
+            base_url: Some(root.join("src")),
+            paths,
+            paths_base: None,
+            root_dirs: vec![root.join("src"), root.join("generated")],
+        },
+    )
+}
+
+fn resolve(base: &str, target: &str) -> Option<PathBuf> {
+    let base = FileName::Real(fixture_dir().join(base));
+
+    match resolver().resolve(&base, target).ok()? {
+        FileName::Real(path) => Some(path),
+        _ => None,
+    }
+}
+
+fn expected(path: &str) -> Option<PathBuf> {
+    Some(fixture_dir().join(path))
+}
+
+#[test]
+fn paths_fallback() {
+    assert_eq!(resolve("src/views/page.ts", "@app/shared/b"), expected("src/shared/b.ts"));
+}
+
+#[test]
+fn paths_longest_prefix() {
+    assert_eq!(resolve("src/views/page.ts", "@app/core/a"), expected("src/core/a.ts"));
+    assert_eq!(resolve("src/views/page.ts", "@app/core"), expected("src/core/index.ts"));
+}
+
+#[test]
+fn paths_exact() {
+    assert_eq!(resolve("src/views/page.ts", "@lib"), expected("lib/index.ts"));
+}
+
+#[test]
+fn base_url() {
+    assert_eq!(resolve("src/views/page.ts", "shared/b"), expected("src/shared/b.ts"));
+    assert_eq!(resolve("src/views/page.ts", "missing"), None);
+}
+
+#[test]
+fn root_dirs() {
+    assert_eq!(
+        resolve("src/views/page.ts", "./template"),
+        expected("generated/views/template.d.ts")
+    );
+    assert_eq!(resolve("src/views/page.ts", "../core/a"), expected("src/core/a.ts"));
+}
@@ -162,6 +162,7 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
                     rule.always_strict = strict;
                     rule.strict_null_checks = strict;
                     rule.strict_function_types = strict;
+                    rule.use_unknown_in_catch_variables = strict;
                 } else if s.starts_with("noLib:") {
                     let v = s["noLib:".len()..].trim().parse().unwrap();
                     if v {
@@ -173,6 +174,27 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
                 } else if s.starts_with("noImplicitReturns:") {
                     let v = s["noImplicitReturns:".len()..].trim().parse().unwrap();
                     rule.no_implicit_returns = v;
+                } else if s.starts_with("noFallthroughCasesInSwitch:") {
+                    let v = s["noFallthroughCasesInSwitch:".len()..].trim().parse().unwrap();
+                    rule.no_fallthrough_cases_in_switch = v;
+                } else if s.starts_with("noUnusedLocals:") {
+                    let v = s["noUnusedLocals:".len()..].trim().parse().unwrap();
+                    rule.no_unused_locals = v;
+                } else if s.starts_with("noUnusedParameters:") {
+                    let v = s["noUnusedParameters:".len()..].trim().parse().unwrap();
+                    rule.no_unused_parameters = v;
+                } else if s.starts_with("exactOptionalPropertyTypes:") {
+                    let v = s["exactOptionalPropertyTypes:".len()..].trim().parse().unwrap();
+                    rule.exact_optional_property_types = v;
+                } else if s.starts_with("noUncheckedIndexedAccess:") {
+                    let v = s["noUncheckedIndexedAccess:".len()..].trim().parse().unwrap();
+                    rule.no_unchecked_indexed_access = v;
+                } else if s.starts_with("noPropertyAccessFromIndexSignature:") {
+                    let v = s["noPropertyAccessFromIndexSignature:".len()..].trim().parse().unwrap();
+                    rule.no_property_access_from_index_signature = v;
+                } else if s.starts_with("useUnknownInCatchVariables:") {
+                    let v = s["useUnknownInCatchVariables:".len()..].trim().parse().unwrap();
+                    rule.use_unknown_in_catch_variables = v;
                 } else if s.starts_with("declaration") {
                 } else if s.starts_with("stripInternal:") {
                     // TODO(kdy1): Handle
@@ -218,6 +240,9 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
                     // Ignored as we don't generate them.
                 } else if s.to_lowercase().starts_with("usedefineforclassfields") {
                     rule.use_define_property_for_class_fields = true;
+                } else if s.starts_with("experimentalDecorators:") {
+                    let v = s["experimentalDecorators:".len()..].trim().parse().unwrap();
+                    rule.experimental_decorators = v;
                 } else if s.to_lowercase().starts_with("jsx") {
                     rule.jsx = s["jsx:".len()..].trim().to_lowercase().parse().unwrap();
                 } else if s.to_lowercase().starts_with("noemit") || s.to_lowercase().starts_with("preserveconstenums") {
@@ -229,6 +254,7 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
                     rule.always_strict = strict;
                     rule.strict_null_checks = strict;
                     rule.strict_function_types = strict;
+                    rule.use_unknown_in_catch_variables = strict;
                 } else if s.to_ascii_lowercase().starts_with("filename") {
                 } else if s.to_ascii_lowercase().starts_with("allowjs") || s.to_ascii_lowercase().starts_with("checkjs") {
                     panic!("allowJs and checkJs are not supported yet. See https://github.com/dudykr/stc/issues/702")
@@ -0,0 +1,197 @@
+//! Comment directives which control reporting of errors.
+//!
+//! - `// @ts-ignore` and `// @ts-expect-error` suppress errors on the next
+//!   line.
+//! - `// @ts-nocheck` and `// @ts-check` at the top of a file disable or enable
+//!   checking of the file.
+
+use fxhash::FxHashMap;
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_utils::StcComments;
+use swc_common::{
+    comments::{Comment, CommentKind},
+    BytePos, SourceMap, Span, Spanned,
+};
+use swc_ecma_ast::Module;
+
+/// Directives of a file.
+#[derive(Debug, Default)]
+pub(crate) struct FileDirectives {
+    /// `Some(true)` for `@ts-check` and `Some(false)` for `@ts-nocheck`. The
+    /// last one wins if both exist.
+    pub check: Option<bool>,
+
+    /// Keyed by the 0-based line where the comment ends.
+    suppressions: FxHashMap<usize, Suppression>,
+}
+
+#[derive(Debug)]
+struct Suppression {
+    span: Span,
+    expect_error: bool,
+    used: bool,
+}
+
+impl FileDirectives {
+    /// Returns [None] if `module` does not have any directive.
+    ///
+    /// All comments in the file of `module` are used, even if they are not
+    /// attached to a node.
+    pub fn collect(cm: &SourceMap, comments: &StcComments, module: &Module) -> Option<Self> {
+        let fm = cm.lookup_byte_offset(module.span.lo).sf;
+
+        let mut found = vec![];
+        for map in [&comments.leading, &comments.trailing] {
+            for entry in map.iter() {
+                if fm.start_pos <= *entry.key() && *entry.key() <= fm.end_pos {
+                    found.extend(entry.value().iter().cloned());
+                }
+            }
+        }
+
+        if found.is_empty() {
+            return None;
+        }
+        // A comment may be stored as both leading and trailing.
+        found.sort_by_key(|c| c.span.lo);
+        found.dedup_by_key(|c| c.span.lo);
+
+        // Pragmas are only allowed before the first statement.
+        let pragma_end = module.body.first().map(|item| item.span().lo).unwrap_or(module.span.hi);
+
+        let mut directives = FileDirectives::default();
+
+        for c in found {
+            if c.kind == CommentKind::Line && c.span.hi <= pragma_end {
+                match pragma(&c.text) {
+                    Some("ts-check") => directives.check = Some(true),
+                    Some("ts-nocheck") => directives.check = Some(false),
+                    _ => {}
+                }
+            }
+
+            if let Some(expect_error) = suppression(&c) {
+                let line = cm.lookup_char_pos(c.span.hi).line - 1;
+
+                // Like tsc, the directive of a block comment starts at its last line.
+                let span = match (c.kind, c.text.rfind('\n')) {
+                    (CommentKind::Block, Some(idx)) => c.span.with_lo(c.span.lo + BytePos(2 + idx as u32 + 1)),
+                    _ => c.span,
+                };
+
+                directives.suppressions.insert(
+                    line,
+                    Suppression {
+                        span,
+                        expect_error,
+                        used: false,
+                    },
+                );
+            }
+        }
+
+        if directives.check.is_none() && directives.suppressions.is_empty() {
+            return None;
+        }
+
+        Some(directives)
+    }
+}
+
+/// Returns the name of a pragma like `ts-nocheck`.
+fn pragma(text: &str) -> Option<&str> {
+    let text = text.strip_prefix('/').unwrap_or(text).trim_start();
+    let text = text.strip_prefix('@')?;
+
+    text.split_whitespace().next()
+}
+
+/// Returns `Some(true)` for `@ts-expect-error` and `Some(false)` for
+/// `@ts-ignore`.
+fn suppression(c: &Comment) -> Option<bool> {
+    let text = match c.kind {
+        CommentKind::Line => c.text.strip_prefix('/').unwrap_or(&c.text).trim_start(),
+        // Only the last line of a block comment is used.
+        CommentKind::Block => c
+            .text
+            .lines()
+            .last()
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| c == '/' || c == '*')
+            .trim_start(),
+    };
+
+    if text.starts_with("@ts-expect-error") {
+        Some(true)
+    } else if text.starts_with("@ts-ignore") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Removes errors suppressed by `directives` and reports unused
+/// `@ts-expect-error` directives.
+///
+/// `directives` is keyed by the start position of files.
+pub(crate) fn apply(cm: &SourceMap, mut directives: FxHashMap<BytePos, FileDirectives>, errors: Vec<Error>) -> Vec<Error> {
+    if directives.is_empty() {
+        return errors;
+    }
+
+    let mut errors = ErrorKind::flatten(errors);
+
+    errors.retain(|err| {
+        let span = err.span();
+        if span.is_dummy() {
+            return true;
+        }
+
+        let loc = cm.lookup_char_pos(span.lo);
+        let file = match directives.get_mut(&loc.file.start_pos) {
+            Some(v) => v,
+            None => return true,
+        };
+
+        if file.check == Some(false) {
+            return false;
+        }
+
+        // Like tsc, blank lines and line comments between a directive and the error
+        // are allowed.
+        let mut line = loc.line - 1;
+        while line > 0 {
+            line -= 1;
+
+            if let Some(s) = file.suppressions.get_mut(&line) {
+                s.used = true;
+                return false;
+            }
+
+            let text = loc.file.get_line(line).unwrap_or_default();
+            let text = text.trim();
+            if !text.is_empty() && !text.starts_with("//") {
+                break;
+            }
+        }
+
+        true
+    });
+
+    let mut unused = directives
+        .into_values()
+        .filter(|file| file.check != Some(false))
+        .flat_map(|file| file.suppressions.into_values())
+        .filter(|s| s.expect_error && !s.used)
+        .map(|s| s.span)
+        .collect::<Vec<_>>();
+    unused.sort_by_key(|span| span.lo);
+
+    errors.extend(
+        unused
+            .into_iter()
+            .map(|span| ErrorKind::UnusedTsExpectErrorDirective { span }.into()),
+    );
+
+    errors
+}
@@ -1,30 +1,40 @@
 //! Full type checker with dependency support.
 #![feature(box_syntax)]
 
-use std::{mem::take, sync::Arc, time::Instant};
+use std::{mem::take, path::Path, sync::Arc, time::Instant};
 
 use dashmap::{DashMap, DashSet, SharedValue};
 use fxhash::{FxBuildHasher, FxHashMap};
-use loader::LoadModule;
+use loader::{LoadModule, ModuleRecord};
 use once_cell::sync::OnceCell;
 use parking_lot::{Mutex, RwLock};
 use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
+use stc_ts_builtin_types::Lib;
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
 use stc_ts_env::Env;
-use stc_ts_errors::{debug::debugger::Debugger, Error};
-use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
+use stc_ts_errors::{debug::debugger::Debugger, Error, ErrorKind};
+use stc_ts_file_analyzer::{
+    analyzer::Analyzer, env::EnvFactory, loader::Load, recorder::Recorder, validator::ValidateWith, ModuleTypeData, VResult,
+};
+use stc_ts_module_loader::resolvers::node::is_js_file;
 use stc_ts_storage::{ErrorStore, File, Group, Single};
 use stc_ts_types::{ModuleId, Type};
+use stc_ts_utils::StcComments;
 use stc_utils::{cache::Freeze, early_error};
 use swc_atoms::JsWord;
-use swc_common::{errors::Handler, FileName, SourceMap, Spanned, DUMMY_SP};
+use swc_common::{errors::Handler, BytePos, FileName, SourceMap, Spanned, DUMMY_SP};
 use swc_ecma_ast::Module;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
+use crate::directives::FileDirectives;
+
+mod directives;
 pub mod loader;
 mod typings;
 
+pub use typings::type_roots;
+
 /// Onc instance per swc::Compiler
 pub struct Checker<L>
 where
@@ -35,7 +45,9 @@ where
     /// Cache
     module_types: RwLock<FxHashMap<ModuleId, Arc<OnceCell<Type>>>>,
 
-    declared_modules: RwLock<Vec<(ModuleId, Type)>>,
+    /// Ambient modules and module augmentations, with the files declaring
+    /// them.
+    declared_modules: RwLock<Vec<(ModuleId, Arc<FileName>, Type)>>,
 
     /// Information required to generate `.d.ts` files.
     dts_modules: Arc<DashMap<ModuleId, RModule, FxBuildHasher>>,
@@ -47,9 +59,17 @@ where
 
     errors: Mutex<Vec<Error>>,
 
-    env: Env,
+    /// Comment directives like `@ts-ignore`, keyed by the start position of
+    /// files.
+    directives: Mutex<FxHashMap<BytePos, FileDirectives>>,
+
+    /// Replaced when files reference libraries using `/// <reference
+    /// lib="..." />`, which should happen before analysis starts.
+    env: RwLock<Env>,
 
     debugger: Option<Debugger>,
+
+    recorder: Option<Recorder>,
 }
 
 impl<L> Checker<L>
@@ -58,14 +78,16 @@ where
 {
     pub fn new(cm: Arc<SourceMap>, handler: Arc<Handler>, env: Env, debugger: Option<Debugger>, module_loader: L) -> Self {
         Checker {
-            env,
+            env: RwLock::new(env),
             cm,
             handler,
             module_types: Default::default(),
             dts_modules: Default::default(),
             started: Default::default(),
             errors: Default::default(),
+            directives: Default::default(),
             debugger,
+            recorder: None,
             declared_modules: Default::default(),
             module_loader,
         }
@@ -87,19 +109,58 @@ where
         self.dts_modules.remove(&id).map(|v| v.1.into_orig())
     }
 
+    /// Records types and identifiers to `recorder` while checking. This is
+    /// disabled by default because it slows down type checking.
+    pub fn set_recorder(&mut self, recorder: Option<Recorder>) {
+        self.recorder = recorder;
+    }
+
     pub fn module_loader(&self) -> &L {
         &self.module_loader
     }
 
+    /// Reloads `filename` and drops the analysis results of it and modules
+    /// depending on it, so they are analyzed again by the next
+    /// [Checker::check].
+    ///
+    /// Returns the invalidated files.
+    pub fn invalidate(&self, filename: &Arc<FileName>) -> anyhow::Result<Vec<Arc<FileName>>> {
+        let invalidated = self.module_loader.invalidate(filename)?;
+
+        let mut module_types = self.module_types.write();
+        for (id, _) in &invalidated {
+            module_types.remove(id);
+            self.dts_modules.remove(id);
+            self.started.remove(id);
+        }
+        self.declared_modules
+            .write()
+            .retain(|(_, declaring, _)| invalidated.iter().all(|(_, filename)| filename != declaring));
+
+        Ok(invalidated.into_iter().map(|(_, filename)| filename).collect())
+    }
+
     /// After calling this method, you can get errors using `.take_errors()`
+    ///
+    /// If `entry` cannot be loaded, the error is emitted to the handler and
+    /// [ModuleId::builtin] is returned.
     pub fn check(&self, entry: Arc<FileName>) -> ModuleId {
         let start = Instant::now();
 
-        let modules = self.module_loader.load_module(&entry, true).expect("failed to load entry");
+        let modules = match self.module_loader.load_module(&entry, true) {
+            Ok(v) => v,
+            Err(err) => {
+                self.handler.err(&format!("failed to load `{}`: {:?}", entry, err));
+                return ModuleId::builtin();
+            }
+        };
 
         let end = Instant::now();
         log::debug!("Loading of `{}` and dependencies took {:?}", entry, end - start);
 
+        // Dependencies of `entry` may reference libraries.
+        self.load_lib_references();
+
         let start = Instant::now();
 
         self.analyze_module(None, entry.clone());
@@ -110,16 +171,89 @@ where
         modules.entry.id
     }
 
+    /// Errors suppressed by comment directives are removed. Syntax errors
+    /// cannot be suppressed.
     pub fn take_errors(&mut self) -> Vec<Error> {
-        take(self.errors.get_mut())
+        let errors = take(self.errors.get_mut());
+        let directives = take(self.directives.get_mut());
+
+        let mut all = self.module_loader.take_syntax_errors();
+        all.extend(directives::apply(&self.cm, directives, errors));
+        all
+    }
+
+    /// Loads `roots` and files they depend on, and adds libraries they
+    /// reference to the builtin types.
+    ///
+    /// Call this with all root files before [Checker::check], so every module
+    /// is analyzed with libraries referenced by any file.
+    pub fn add_roots(&self, roots: &[Arc<FileName>]) {
+        self.module_loader.add_roots(roots);
+        self.load_lib_references();
+    }
+
+    /// Adds libraries referenced by `/// <reference lib="..." />` to the
+    /// builtin types. Modules which are already analyzed are not affected.
+    fn load_lib_references(&self) {
+        let libs = self
+            .module_loader
+            .lib_references()
+            .iter()
+            .flat_map(|name| {
+                let libs = Lib::load(name);
+                if libs.is_empty() {
+                    warn!("Unknown library `{}` is referenced", name);
+                }
+                libs
+            })
+            .collect::<Vec<_>>();
+
+        let mut env = self.env.write();
+        if let Some(new) = Env::with_libs(&env, &libs) {
+            if !self.module_types.read().is_empty() {
+                warn!(
+                    "Libraries {:?} are referenced after analysis started, and modules already analyzed do not use them",
+                    libs
+                );
+            }
+            info!("Added referenced libraries: {:?}", libs);
+
+            *env = new;
+        }
+    }
+
+    fn collect_directives(&self, comments: &StcComments, filename: &FileName, module: &Module) {
+        let mut directives = FileDirectives::collect(&self.cm, comments, module);
+
+        // Errors of JavaScript files are reported only if `checkJs` is enabled or the
+        // file has `// @ts-check`.
+        let is_js = match filename {
+            FileName::Real(path) => is_js_file(path),
+            FileName::Custom(name) => is_js_file(Path::new(name)),
+            _ => false,
+        };
+        if is_js && !self.env.read().rule().check_js {
+            directives.get_or_insert_with(Default::default).check.get_or_insert(false);
+        }
+
+        if let Some(directives) = directives {
+            let start_pos = self.cm.lookup_byte_offset(module.span.lo).sf.start_pos;
+
+            self.directives.lock().insert(start_pos, directives);
+        }
     }
 
     /// Analyzes one module.
     fn analyze_module(&self, starter: Option<Arc<FileName>>, path: Arc<FileName>) -> Type {
-        let modules_in_group = self
-            .module_loader
-            .load_module(&path, false)
-            .expect("failed to load module? (cycle)");
+        let modules_in_group = match self.module_loader.load_module(&path, false) {
+            Ok(v) => v,
+            Err(err) => {
+                // Modules are loaded before analysis, so this happens only if a file is removed
+                // while checking.
+                error!("Failed to load `{}`: {:?}", path, err);
+                return empty_module_type(&path);
+            }
+        };
 
         let id = modules_in_group.entry.id;
         {
@@ -172,18 +306,21 @@ where
                 let mut mutations;
                 {
                     let mut a = Analyzer::root(
-                        self.env.clone(),
+                        self.env.read().clone(),
                         self.cm.clone(),
                         modules_in_group.comments.clone(),
                         box &mut storage,
                         self,
                         self.debugger.clone(),
                     );
+                    a.set_recorder(self.recorder.clone());
                     let _ = modules.validate_with(&mut a);
                     mutations = a.mutations.unwrap();
                 }
 
                 for (record, mut dts_module) in modules_in_group.modules.iter().zip(modules) {
+                    self.collect_directives(&modules_in_group.comments, &record.filename, &record.ast);
+
                     let type_data = storage.info.entry(record.id).or_default();
 
                     {
@@ -261,16 +398,71 @@ where
         }
     }
 
-    fn analyze_non_circular_module(&self, module_id: ModuleId, path: Arc<FileName>) -> Type {
-        let start = Instant::now();
+    /// Merges ambient module declarations and augmentations of a module into
+    /// its type. Files declaring them are analyzed first unless they are
+    /// being analyzed.
+    fn merge_declared_modules(&self, record: &ModuleRecord, ty: Type) -> Type {
+        for file in self.module_loader.declaring_files(&record.filename) {
+            let is_started = match self.module_loader.load_module(&file, false) {
+                Ok(records) => self.started.contains(&records.entry.id),
+                Err(..) => continue,
+            };
+
+            if !is_started {
+                self.analyze_module(None, file);
+            }
+        }
 
-        let is_dts = match &*path {
-            FileName::Real(path) => path.to_string_lossy().ends_with(".d.ts"),
-            _ => false,
+        let declared_modules = self.declared_modules.read();
+        let mut declared = declared_modules.iter().filter(|(id, ..)| *id == record.id).peekable();
+        if declared.peek().is_none() {
+            return ty;
+        }
+
+        let mut module = match ty.normalize() {
+            Type::Module(module) => module.clone(),
+            _ => return ty,
         };
+        for (_, _, ty) in declared {
+            if let Type::Module(declared) = ty.normalize() {
+                for (name, ty) in &declared.exports.vars {
+                    module.exports.vars.entry(name.clone()).or_insert_with(|| ty.clone());
+                }
+                for (name, types) in &declared.exports.types {
+                    module.exports.types.entry(name.clone()).or_default().extend(types.iter().cloned());
+                }
+            }
+        }
+
+        Type::Module(module).freezed()
+    }
+
+    /// Callers should check if the module can be loaded using
+    /// [Load::module_id].
+    fn load_dep(&self, base: &Arc<FileName>, dep: &str) -> VResult<loader::Records> {
+        self.module_loader.load_dep(base, dep).map_err(|err| {
+            error!("Failed to load `{}` from `{}`: {:?}", dep, base, err);
+
+            ErrorKind::ModuleNotFound {
+                span: DUMMY_SP,
+                name: dep.into(),
+            }
+            .into()
+        })
+    }
+
+    fn analyze_non_circular_module(&self, module_id: ModuleId, path: Arc<FileName>) -> Type {
+        let start = Instant::now();
 
         let mut node_id_gen = NodeIdGenerator::default();
-        let records = self.module_loader.load_module(&path, false).expect("failed to load module?");
+        let records = match self.module_loader.load_module(&path, false) {
+            Ok(v) => v,
+            Err(err) => {
+                // See `analyze_module`.
+                error!("Failed to load `{}`: {:?}", path, err);
+                return empty_module_type(&path);
+            }
+        };
         assert_eq!(
             records.modules.len(),
             1,
@@ -279,6 +471,12 @@ where
 
         let record = records.modules.into_iter().next().unwrap();
 
+        // The loader may serve declarations for a file which is not named `.d.ts`,
+        // e.g. for the outputs of referenced projects.
+        let is_dts = record.is_dts;
+
+        self.collect_directives(&records.comments, &record.filename, &record.ast);
+
         let mut module = RModule::from_orig(&mut node_id_gen, record.ast.clone());
 
         let mut storage = Single {
@@ -293,13 +491,14 @@ where
         {
             let start = Instant::now();
             let mut a = Analyzer::root(
-                self.env.clone(),
+                self.env.read().clone(),
                 self.cm.clone(),
                 records.comments,
                 box &mut storage,
                 self,
                 self.debugger.clone(),
             );
+            a.set_recorder(self.recorder.clone());
 
             module.visit_with(&mut a);
 
@@ -366,32 +565,55 @@ where
     }
 
     fn load_circular_dep(&self, base: &Arc<FileName>, dep: &str, _partial: &ModuleTypeData) -> VResult<Type> {
-        let records = self.module_loader.load_dep(base, dep).unwrap();
+        let records = self.load_dep(base, dep)?;
 
         let data = self.analyze_module(Some(base.clone()), records.entry.filename.clone());
 
-        Ok(data)
+        Ok(self.merge_declared_modules(&records.entry, data))
     }
 
     fn load_non_circular_dep(&self, base: &Arc<FileName>, dep: &str) -> VResult<Type> {
-        let records = self.module_loader.load_dep(base, dep).unwrap();
+        let records = self.load_dep(base, dep)?;
 
         let data = self.analyze_module(Some(base.clone()), records.entry.filename.clone());
 
-        Ok(data)
+        Ok(self.merge_declared_modules(&records.entry, data))
     }
 
-    fn declare_module(&self, name: &JsWord, module: Type) {
+    fn declare_module(&self, base: &Arc<FileName>, name: &JsWord, module: Type) {
         module.assert_clone_cheap();
 
-        let module_id = self
+        // Resolved like imports, so augmentations like `declare module "./foo"` refer
+        // to the augmented file.
+        let records = match self
             .module_loader
-            .load_module(&Arc::new(FileName::Custom(name.to_string())), false)
-            .unwrap()
-            .entry
-            .id;
+            .load_dep(base, name)
+            .or_else(|_| self.module_loader.load_module(&Arc::new(FileName::Custom(name.to_string())), false))
+        {
+            Ok(records) => records,
+            Err(err) => {
+                error!("Failed to load module `{}` declared by `{}`: {:?}", name, base, err);
+                return;
+            }
+        };
 
         info!("Declaring module with type `{}`", name);
-        self.declared_modules.write().push((module_id, module));
+        self.declared_modules.write().push((records.entry.id, base.clone(), module));
     }
 }
+
+/// Used for modules which cannot be loaded.
+fn empty_module_type(path: &FileName) -> Type {
+    Type::Module(stc_ts_types::Module {
+        span: DUMMY_SP,
+        name: RTsModuleName::Str(RStr {
+            span: DUMMY_SP,
+            value: path.to_string().into(),
+            raw: None,
+        }),
+        exports: Default::default(),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+    .freezed()
+}
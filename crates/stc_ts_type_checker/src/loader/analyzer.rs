@@ -1,11 +1,11 @@
-use stc_ts_utils::imports::find_imports_in_comments;
+use stc_ts_utils::imports::{find_imports_in_comments, ImportRef};
 use swc_atoms::JsWord;
 use swc_common::{comments::Comments, Span, Spanned};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
-/// Returns `(declared modules, references ,dependencies)`
-pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> (Vec<JsWord>, Vec<JsWord>, Vec<JsWord>)
+/// Returns `(declared modules, references, referenced libs, dependencies)`
+pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> (Vec<JsWord>, Vec<JsWord>, Vec<JsWord>, Vec<JsWord>)
 where
     C: Comments,
 {
@@ -13,12 +13,13 @@ where
         comments,
         declared_modules: Default::default(),
         references: Default::default(),
+        libs: Default::default(),
         deps: Default::default(),
     };
 
     m.visit_with(&mut v);
 
-    (v.declared_modules, v.references, v.deps)
+    (v.declared_modules, v.references, v.libs, v.deps)
 }
 
 struct DepFinder<C>
@@ -28,6 +29,7 @@ where
     comments: C,
     declared_modules: Vec<JsWord>,
     references: Vec<JsWord>,
+    libs: Vec<JsWord>,
     deps: Vec<JsWord>,
 }
 
@@ -36,9 +38,12 @@ where
     C: Comments,
 {
     fn check_comments(&mut self, span: Span) {
-        let deps = find_imports_in_comments(&self.comments, span);
-
-        self.references.extend(deps.into_iter().map(|i| i.to_path()));
+        for i in find_imports_in_comments(&self.comments, span) {
+            match i {
+                ImportRef::Lib(lib) => self.libs.push(lib),
+                _ => self.references.extend(i.to_path()),
+            }
+        }
     }
 }
 
@@ -0,0 +1,601 @@
+//! Converts JSDoc comments of JavaScript files into TypeScript syntax, so the
+//! analyzer handles them like type annotations.
+//!
+//! - `@type`, `@param`, `@returns` and `@template` are added to the declaration
+//!   the comment is attached to.
+//! - `@typedef` (with `@property`) and `@import` become type aliases and
+//!   type-only imports, wherever the comment is.
+//!
+//! Type expressions are parsed by the TypeScript parser, and spans of the
+//! types point to the comments.
+
+use stc_ts_utils::StcComments;
+use swc_common::{
+    comments::{Comment, CommentKind},
+    BytePos, SourceFile, Span, Spanned,
+};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
+use swc_ecma_visit::{VisitMut, VisitMutWith};
+
+/// Desugars JSDoc of `module`, which is parsed from `fm`.
+pub(super) fn desugar(comments: &StcComments, fm: &SourceFile, module: &mut Module) {
+    module.visit_mut_with(&mut Desugarer { comments });
+
+    // Type aliases of modules are exported, like `tsc`.
+    let is_module = module.body.iter().any(|item| matches!(item, ModuleItem::ModuleDecl(..)));
+
+    let mut found = vec![];
+    for map in [&comments.leading, &comments.trailing] {
+        for entry in map.iter() {
+            if (fm.start_pos..=fm.end_pos).contains(entry.key()) {
+                found.extend(entry.value().iter().filter(|c| is_jsdoc(c)).cloned());
+            }
+        }
+    }
+    found.sort_by_key(|c| c.span.lo);
+    found.dedup_by_key(|c| c.span.lo);
+
+    let mut items = vec![];
+    for c in &found {
+        let tags = parse_tags(c);
+
+        for tag in &tags {
+            match &*tag.name {
+                "typedef" => {
+                    if let Some(alias) = typedef(tag, &tags) {
+                        items.push(if is_module {
+                            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                                span: tag.span,
+                                decl: Decl::TsTypeAlias(alias),
+                            }))
+                        } else {
+                            ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(alias)))
+                        });
+                    }
+                }
+                "import" => {
+                    // `@import` starts at `@`.
+                    let src = format!("import{}", tag.rest);
+                    for item in parse_items(&src, tag.span.lo + BytePos(1)).into_iter().flatten() {
+                        if let ModuleItem::ModuleDecl(ModuleDecl::Import(mut import)) = item {
+                            import.type_only = true;
+                            items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !items.is_empty() {
+        items.append(&mut module.body);
+        module.body = items;
+    }
+}
+
+struct Desugarer<'a> {
+    comments: &'a StcComments,
+}
+
+impl Desugarer<'_> {
+    /// Tags of the last JSDoc comment attached to `pos`.
+    fn tags_at(&self, pos: BytePos) -> Vec<Tag> {
+        self.comments
+            .leading
+            .get(&pos)
+            .and_then(|comments| comments.iter().rev().find(|c| is_jsdoc(c)).map(parse_tags))
+            .unwrap_or_default()
+    }
+
+    fn annotate_var(&self, tags: &[Tag], var: &mut VarDecl) {
+        if tags.is_empty() {
+            return;
+        }
+
+        if let Some(decl) = var.decls.first_mut() {
+            if let Some(ty) = tags.iter().find(|tag| tag.name == "type").and_then(|tag| tag.parse_type()) {
+                if let Pat::Ident(i) = &mut decl.name {
+                    i.type_ann.get_or_insert_with(|| type_ann(ty));
+                }
+                return;
+            }
+
+            if let Some(init) = &mut decl.init {
+                annotate_fn_expr(tags, init);
+            }
+        }
+    }
+}
+
+impl VisitMut for Desugarer<'_> {
+    fn visit_mut_class_method(&mut self, n: &mut ClassMethod) {
+        let tags = self.tags_at(n.span.lo);
+        annotate_function(&tags, &mut n.function);
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_class_prop(&mut self, n: &mut ClassProp) {
+        let tags = self.tags_at(n.span.lo);
+        if n.type_ann.is_none() {
+            n.type_ann = tags
+                .iter()
+                .find(|tag| tag.name == "type")
+                .and_then(|tag| tag.parse_type())
+                .map(type_ann);
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_constructor(&mut self, n: &mut Constructor) {
+        let tags = self.tags_at(n.span.lo);
+        for param in &mut n.params {
+            if let ParamOrTsParamProp::Param(param) = param {
+                annotate_param(&tags, &mut param.pat);
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    /// Comments of exported declarations are attached to `export`.
+    fn visit_mut_export_decl(&mut self, n: &mut ExportDecl) {
+        let tags = self.tags_at(n.span.lo);
+        match &mut n.decl {
+            Decl::Fn(f) => annotate_function(&tags, &mut f.function),
+            Decl::Var(var) => self.annotate_var(&tags, var),
+            _ => {}
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_export_default_decl(&mut self, n: &mut ExportDefaultDecl) {
+        if let DefaultDecl::Fn(f) = &mut n.decl {
+            let tags = self.tags_at(n.span.lo);
+            annotate_function(&tags, &mut f.function);
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_fn_decl(&mut self, n: &mut FnDecl) {
+        let tags = self.tags_at(n.function.span.lo);
+        annotate_function(&tags, &mut n.function);
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_var_decl(&mut self, n: &mut VarDecl) {
+        let tags = self.tags_at(n.span.lo);
+        self.annotate_var(&tags, n);
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+/// Annotates a function expression initializing a variable.
+fn annotate_fn_expr(tags: &[Tag], e: &mut Expr) {
+    match e {
+        Expr::Paren(e) => annotate_fn_expr(tags, &mut e.expr),
+        Expr::Fn(f) => annotate_function(tags, &mut f.function),
+        Expr::Arrow(f) => {
+            for param in &mut f.params {
+                annotate_param(tags, param);
+            }
+            if f.return_type.is_none() {
+                f.return_type = return_type(tags);
+            }
+            if f.type_params.is_none() {
+                f.type_params = type_params(tags);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn annotate_function(tags: &[Tag], f: &mut Function) {
+    if tags.is_empty() {
+        return;
+    }
+
+    for param in &mut f.params {
+        annotate_param(tags, &mut param.pat);
+    }
+    if f.return_type.is_none() {
+        f.return_type = return_type(tags);
+    }
+    if f.type_params.is_none() {
+        f.type_params = type_params(tags);
+    }
+}
+
+/// Applies `@param {T} name`. `T=` and `[name]` mark optional parameters, and
+/// `...T` marks rest parameters.
+fn annotate_param(tags: &[Tag], pat: &mut Pat) {
+    let name = match pat {
+        Pat::Ident(i) => &i.id.sym,
+        Pat::Assign(AssignPat { left, .. }) => match &**left {
+            Pat::Ident(i) => &i.id.sym,
+            _ => return,
+        },
+        Pat::Rest(RestPat { arg, .. }) => match &**arg {
+            Pat::Ident(i) => &i.id.sym,
+            _ => return,
+        },
+        _ => return,
+    };
+
+    let tag = tags
+        .iter()
+        .filter(|tag| matches!(&*tag.name, "param" | "arg" | "argument"))
+        .find(|tag| param_name(&tag.rest).map(|(param, _)| param) == Some(&**name));
+    let tag = match tag {
+        Some(tag) => tag,
+        None => return,
+    };
+    let (text, pos) = match &tag.ty {
+        Some(ty) => ty,
+        None => return,
+    };
+
+    let optional = text.trim_end().ends_with('=') || param_name(&tag.rest).map(|(_, optional)| optional) == Some(true);
+    let text = text.trim_end().trim_end_matches('=');
+    let (text, pos) = match text.trim_start().strip_prefix("...") {
+        Some(elem) => (elem, *pos + BytePos((text.len() - elem.len()) as u32)),
+        None => (text, *pos),
+    };
+    let ty = match parse_type(text, pos) {
+        Some(ty) => ty,
+        None => return,
+    };
+
+    match pat {
+        Pat::Ident(i) => {
+            i.id.optional |= optional;
+            i.type_ann.get_or_insert_with(|| type_ann(ty));
+        }
+        Pat::Assign(AssignPat { left, .. }) => {
+            if let Pat::Ident(i) = &mut **left {
+                i.type_ann.get_or_insert_with(|| type_ann(ty));
+            }
+        }
+        Pat::Rest(rest) => {
+            rest.type_ann.get_or_insert_with(|| {
+                type_ann(box TsType::TsArrayType(TsArrayType {
+                    span: ty.span(),
+                    elem_type: ty,
+                }))
+            });
+        }
+        _ => {}
+    }
+}
+
+fn return_type(tags: &[Tag]) -> Option<Box<TsTypeAnn>> {
+    tags.iter()
+        .find(|tag| matches!(&*tag.name, "returns" | "return"))
+        .and_then(|tag| tag.parse_type())
+        .map(type_ann)
+}
+
+/// Merges `@template T, U` and `@template {C} T`. Like `tsc`, the constraint
+/// applies to the first name.
+fn type_params(tags: &[Tag]) -> Option<Box<TsTypeParamDecl>> {
+    let mut params = vec![];
+    let mut span = None;
+
+    for tag in tags.iter().filter(|tag| tag.name == "template") {
+        let names = template_names(&tag.rest);
+        if names.trim().is_empty() {
+            continue;
+        }
+
+        let src = match &tag.ty {
+            Some((constraint, _)) => {
+                let (first, others) = names
+                    .split_once(',')
+                    .map(|(first, others)| (first, Some(others)))
+                    .unwrap_or((names, None));
+                match others {
+                    Some(others) => format!("type _<{} extends {},{}>=0", first, constraint, others),
+                    None => format!("type _<{} extends {}>=0", first, constraint),
+                }
+            }
+            None => format!("type _<{}>=0", names),
+        };
+
+        // `type _<` is 7 bytes long, so names have correct spans.
+        if let Some(alias) = parse_alias(&src, tag.rest_pos - BytePos(7)) {
+            params.extend(alias.type_params.into_iter().flat_map(|decl| decl.params));
+            span.get_or_insert(tag.span);
+        }
+    }
+
+    Some(box TsTypeParamDecl { span: span?, params })
+}
+
+/// `@typedef {T} Name`, or `@typedef {Object} Name` followed by `@property`
+/// tags.
+fn typedef(tag: &Tag, tags: &[Tag]) -> Option<Box<TsTypeAliasDecl>> {
+    let (name, _) = param_name(&tag.rest)?;
+    let name_pos = tag.rest_pos + BytePos((tag.rest.len() - tag.rest.trim_start().len()) as u32);
+
+    let properties = tags
+        .iter()
+        .filter(|tag| matches!(&*tag.name, "property" | "prop"))
+        .filter_map(|tag| {
+            let (name, optional) = param_name(&tag.rest)?;
+            let (ty, _) = tag.ty.as_ref()?;
+
+            Some(format!("{}{}:{};", name, if optional { "?" } else { "" }, ty))
+        })
+        .collect::<Vec<_>>();
+
+    let is_object = match &tag.ty {
+        Some((ty, _)) => matches!(ty.trim(), "Object" | "object"),
+        None => true,
+    };
+
+    let mut alias = if is_object && !properties.is_empty() {
+        // Spans of properties cannot be preserved.
+        let mut alias = parse_alias(&format!("type _={{{}}}", properties.concat()), BytePos(1))?;
+        alias.visit_mut_with(&mut SpanReplacer(tag.span));
+        alias
+    } else {
+        let (text, pos) = tag.ty.as_ref()?;
+        let ty = parse_type(text, *pos)?;
+
+        box TsTypeAliasDecl {
+            span: tag.span,
+            declare: false,
+            id: Ident::new("_".into(), tag.span),
+            type_params: None,
+            type_ann: ty,
+        }
+    };
+
+    alias.span = tag.span;
+    alias.id = Ident::new(
+        name.into(),
+        Span::new(name_pos, name_pos + BytePos(name.len() as u32), Default::default()),
+    );
+    alias.type_params = type_params(tags);
+
+    Some(alias)
+}
+
+fn type_ann(ty: Box<TsType>) -> Box<TsTypeAnn> {
+    box TsTypeAnn {
+        span: ty.span(),
+        type_ann: ty,
+    }
+}
+
+/// A block tag like `@param {string} name description`.
+struct Tag {
+    name: String,
+    /// Text between braces and its position.
+    ty: Option<(String, BytePos)>,
+    /// Text after the type, until the next tag.
+    rest: String,
+    rest_pos: BytePos,
+    span: Span,
+}
+
+impl Tag {
+    fn parse_type(&self) -> Option<Box<TsType>> {
+        let (text, pos) = self.ty.as_ref()?;
+
+        parse_type(text, *pos)
+    }
+}
+
+fn is_jsdoc(c: &Comment) -> bool {
+    c.kind == CommentKind::Block && c.text.starts_with('*')
+}
+
+fn parse_tags(c: &Comment) -> Vec<Tag> {
+    // The text starts after `/*`.
+    let base = c.span.lo + BytePos(2);
+    let text = strip_margins(&c.text);
+
+    let starts = text
+        .match_indices('@')
+        .map(|(idx, _)| idx)
+        .filter(|&idx| {
+            let after = text[idx + 1..].starts_with(|c: char| c.is_ascii_alphabetic());
+            let before = text[..idx].ends_with(char::is_whitespace) || idx == 0;
+
+            after && before
+        })
+        .collect::<Vec<_>>();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(text.len());
+            let body = &text[start + 1..end];
+
+            let name = &body[..body.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(body.len())];
+            let mut rest_start = start + 1 + name.len();
+
+            // `@import { X } from 'y'` does not have a type.
+            let mut ty = None;
+            if name != "import" {
+                if let Some(s) = text[rest_start..end].trim_start().strip_prefix('{') {
+                    if let Some(len) = matching_brace(s) {
+                        let ty_start = end - s.len();
+                        ty = Some((s[..len].to_string(), base + BytePos(ty_start as u32)));
+                        rest_start = ty_start + len + 1;
+                    }
+                }
+            }
+
+            Tag {
+                name: name.to_string(),
+                ty,
+                rest: text[rest_start..end].to_string(),
+                rest_pos: base + BytePos(rest_start as u32),
+                span: Span::new(
+                    base + BytePos(start as u32),
+                    base + BytePos(text[..end].trim_end().len() as u32),
+                    Default::default(),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Replaces `*` at the start of lines with a space, so that offsets are
+/// preserved.
+fn strip_margins(text: &str) -> String {
+    let mut buf = String::with_capacity(text.len());
+    let mut at_line_start = true;
+
+    for c in text.chars() {
+        if at_line_start && c == '*' {
+            buf.push(' ');
+            continue;
+        }
+
+        at_line_start = (at_line_start && c.is_whitespace()) || c == '\n';
+        buf.push(c);
+    }
+
+    buf
+}
+
+/// Returns the length of `s` before the brace closing an already opened
+/// brace.
+fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (idx, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(idx),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the name of `@param`, and whether it's in brackets, which means
+/// it's optional. Properties of parameters like `opts.x` are ignored.
+fn param_name(rest: &str) -> Option<(&str, bool)> {
+    let rest = rest.trim_start();
+
+    let (rest, optional) = match rest.strip_prefix('[') {
+        Some(rest) => (rest.trim_start(), true),
+        None => (rest, false),
+    };
+
+    let len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+    if len == 0 || rest[len..].starts_with('.') {
+        return None;
+    }
+
+    Some((&rest[..len], optional))
+}
+
+/// `T, U description` => `T, U`
+fn template_names(rest: &str) -> &str {
+    let line = rest.lines().next().unwrap_or_default();
+    let mut end = 0;
+    let mut pos = 0;
+
+    loop {
+        pos += line[pos..].len() - line[pos..].trim_start().len();
+        let len = line[pos..].find(|c: char| !is_ident_char(c)).unwrap_or(line.len() - pos);
+        if len == 0 {
+            break;
+        }
+        pos += len;
+        end = pos;
+
+        let spaces = line[pos..].len() - line[pos..].trim_start().len();
+        if !line[pos + spaces..].starts_with(',') {
+            break;
+        }
+        pos += spaces + 1;
+    }
+
+    &line[..end]
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Parses a JSDoc type expression located at `pos`.
+fn parse_type(text: &str, pos: BytePos) -> Option<Box<TsType>> {
+    let pos = pos + BytePos((text.len() - text.trim_start().len()) as u32);
+    let text = text.trim();
+    let span = Span::new(pos, pos + BytePos(text.len() as u32), Default::default());
+
+    if matches!(text, "*" | "?") {
+        return Some(keyword(span, TsKeywordTypeKind::TsAnyKeyword));
+    }
+
+    // `?T` is nullable and `!T` is non-nullable.
+    if let Some(inner) = text.strip_prefix('?') {
+        let ty = parse_type(inner, pos + BytePos(1))?;
+
+        return Some(box TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(
+            TsUnionType {
+                span,
+                types: vec![ty, keyword(span, TsKeywordTypeKind::TsNullKeyword)],
+            },
+        )));
+    }
+    if let Some(inner) = text.strip_prefix('!') {
+        return parse_type(inner, pos + BytePos(1));
+    }
+
+    // `Array.<T>` is same as `Array<T>`. `type _=` is 7 bytes long, so types have
+    // correct spans.
+    let src = format!("type _={}", text.replace(".<", " <"));
+
+    parse_alias(&src, pos - BytePos(7)).map(|alias| alias.type_ann)
+}
+
+fn keyword(span: Span, kind: TsKeywordTypeKind) -> Box<TsType> {
+    box TsType::TsKeywordType(TsKeywordType { span, kind })
+}
+
+fn parse_alias(src: &str, lo: BytePos) -> Option<Box<TsTypeAliasDecl>> {
+    match parse_items(src, lo)?.pop()? {
+        ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(alias))) => Some(alias),
+        _ => None,
+    }
+}
+
+/// Parses `src` as if it's located at `lo`. Returns [None] on any error.
+fn parse_items(src: &str, lo: BytePos) -> Option<Vec<ModuleItem>> {
+    let input = StringInput::new(src, lo, lo + BytePos(src.len() as u32));
+    let lexer = Lexer::new(Syntax::Typescript(Default::default()), EsVersion::latest(), input, None);
+
+    let mut parser = Parser::new_from(lexer);
+    let module = parser.parse_module().ok()?;
+    if !parser.take_errors().is_empty() {
+        return None;
+    }
+
+    Some(module.body)
+}
+
+/// Sets spans of synthesized nodes to the span of the tag.
+struct SpanReplacer(Span);
+
+impl VisitMut for SpanReplacer {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = self.0;
+    }
+}
@@ -0,0 +1,162 @@
+//! Files served from memory, for embedding the type checker without writing
+//! files to the disk.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Result};
+use fxhash::FxHashMap;
+use parking_lot::RwLock;
+use path_clean::PathClean;
+use swc_common::{FileName, SourceFile, SourceMap};
+use swc_ecma_loader::resolve::Resolve;
+use swc_ecma_parser::Syntax;
+
+use super::{file_syntax, LoadFile};
+
+/// Extensions tried for imports without an extension.
+static EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx"];
+
+/// Imports of JavaScript files are resolved to TypeScript files with the same
+/// name.
+static EXTENSION_SUBSTITUTIONS: &[(&str, &[&str])] = &[
+    ("js", &["ts", "tsx", "d.ts"]),
+    ("jsx", &["tsx", "d.ts"]),
+    ("mjs", &["mts", "d.mts"]),
+    ("cjs", &["cts", "d.cts"]),
+];
+
+/// An in-memory file system, which is both a [LoadFile] and a [Resolve].
+///
+/// Files are keyed by [FileName::Real] or [FileName::Custom], and imports of a
+/// file resolve to files of the same kind. Cloning is cheap and clones share
+/// the files, so files can be updated between checks. Call
+/// `Checker::invalidate` for files which are already checked.
+///
+/// Packages are resolved to `node_modules/{name}` or
+/// `node_modules/@types/{name}`, but `package.json` is not used.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFs {
+    files: Arc<RwLock<FxHashMap<FileName, Arc<String>>>>,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces a file, and returns the previous content.
+    pub fn insert(&self, filename: FileName, content: impl Into<String>) -> Option<Arc<String>> {
+        self.files.write().insert(filename, Arc::new(content.into()))
+    }
+
+    pub fn remove(&self, filename: &FileName) -> Option<Arc<String>> {
+        self.files.write().remove(filename)
+    }
+
+    pub fn get(&self, filename: &FileName) -> Option<Arc<String>> {
+        self.files.read().get(filename).cloned()
+    }
+
+    pub fn contains(&self, filename: &FileName) -> bool {
+        self.files.read().contains_key(filename)
+    }
+
+    /// Names of all files, sorted.
+    pub fn files(&self) -> Vec<FileName> {
+        let mut files = self.files.read().keys().cloned().collect::<Vec<_>>();
+        files.sort_by_cached_key(|filename| filename.to_string());
+        files
+    }
+
+    /// Returns the first existing file among `path` with extensions and
+    /// `path/index` with extensions.
+    fn probe(&self, path: &Path, custom: bool) -> Option<FileName> {
+        let path = path.to_path_buf().clean();
+        let mut candidates = vec![path.clone()];
+
+        let ext = path.extension().and_then(|ext| ext.to_str());
+        if let Some((_, substitutes)) = EXTENSION_SUBSTITUTIONS.iter().find(|(js, _)| Some(*js) == ext) {
+            let stem = path.with_extension("");
+            candidates.extend(substitutes.iter().map(|ext| append_extension(&stem, ext)));
+        }
+
+        candidates.extend(EXTENSIONS.iter().map(|ext| append_extension(&path, ext)));
+        candidates.extend(EXTENSIONS.iter().map(|ext| path.join(format!("index.{}", ext))));
+
+        candidates
+            .into_iter()
+            .map(|path| {
+                if custom {
+                    FileName::Custom(path.to_string_lossy().into_owned())
+                } else {
+                    FileName::Real(path)
+                }
+            })
+            .find(|filename| self.contains(filename))
+    }
+}
+
+impl LoadFile for MemoryFs {
+    fn load_file(&self, cm: &Arc<SourceMap>, filename: &Arc<FileName>) -> Result<(Arc<SourceFile>, Syntax)> {
+        let content = match self.get(filename) {
+            Some(v) => v,
+            None => bail!("`{}` does not exist in memory", filename),
+        };
+
+        let syntax = match &**filename {
+            FileName::Real(path) => file_syntax(path),
+            FileName::Custom(name) => file_syntax(Path::new(name)),
+            _ => bail!("MemoryFs only supports real and custom files"),
+        };
+        let fm = cm.new_source_file((**filename).clone(), (*content).clone());
+
+        Ok((fm, syntax))
+    }
+}
+
+impl Resolve for MemoryFs {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName> {
+        let (base, custom) = match base {
+            FileName::Real(path) => (path.clone(), false),
+            FileName::Custom(name) => (PathBuf::from(name), true),
+            _ => bail!("cannot resolve `{}` from `{}`", target, base),
+        };
+        let base_dir = base.parent().unwrap_or_else(|| Path::new(""));
+
+        let resolved = if target.starts_with("./") || target.starts_with("../") {
+            self.probe(&base_dir.join(target), custom)
+        } else if target.starts_with('/') {
+            self.probe(Path::new(target), custom)
+        } else {
+            // A custom file may be named like a package.
+            Some(FileName::Custom(target.to_string()))
+                .filter(|filename| self.contains(filename))
+                .or_else(|| {
+                    base_dir.ancestors().find_map(|dir| {
+                        let node_modules = dir.join("node_modules");
+
+                        self.probe(&node_modules.join(target), custom)
+                            .or_else(|| self.probe(&node_modules.join("@types").join(target), custom))
+                    })
+                })
+        };
+
+        match resolved {
+            Some(v) => Ok(v),
+            None => bail!("failed to resolve `{}` from `{}`", target, base.display()),
+        }
+    }
+}
+
+/// Unlike [Path::with_extension], this does not replace the existing
+/// extension.
+fn append_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(ext);
+    path.into()
+}
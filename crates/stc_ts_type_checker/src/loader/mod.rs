@@ -1,24 +1,37 @@
-use std::sync::{Arc, Mutex, RwLock};
+use std::{
+    borrow::Cow,
+    mem::take,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
 
 use anyhow::{bail, Context, Result};
 use auto_impl::auto_impl;
 use dashmap::{DashMap, DashSet};
 use fxhash::FxBuildHasher;
-use petgraph::algo::kosaraju_scc;
+use petgraph::{
+    algo::kosaraju_scc,
+    EdgeDirection::{Incoming, Outgoing},
+};
 use rayon::prelude::*;
 use stc_ts_env::Env;
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_module_loader::resolvers::node::{is_declaration_file, is_js_file};
 use stc_ts_types::{module_id::ModuleIdGenerator, ModuleId};
-use stc_ts_utils::StcComments;
+use stc_ts_utils::{imports::package_of_types, StcComments};
+use swc_atoms::JsWord;
 use swc_common::{FileName, SourceFile, SourceMap, Span, SyntaxContext, GLOBALS};
-use swc_ecma_ast::{EsVersion, Module};
+use swc_ecma_ast::{EsVersion, Module, ModuleItem};
 use swc_ecma_loader::resolve::Resolve;
-use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_parser::{error::SyntaxError, lexer::Lexer, EsConfig, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_visit::VisitMutWith;
 use swc_fast_graph::digraph::FastDiGraphMap;
 
 use self::analyzer::find_modules_and_deps;
 
 mod analyzer;
+mod jsdoc;
+pub mod memory;
 pub mod store;
 
 pub struct ModuleRecord {
@@ -53,9 +66,54 @@ pub trait LoadModule: 'static + Send + Sync {
 
     /// Same constraints for [`LoadModule::load_module`] applies.
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records>;
+
+    /// Reloads `filename` using the file loader, and returns it and all modules
+    /// depending on it, directly or indirectly.
+    ///
+    /// Returns an empty vector if `filename` is not loaded. The default
+    /// implementation does not track loaded files, so it always does.
+    fn invalidate(&self, _filename: &Arc<FileName>) -> Result<Vec<(ModuleId, Arc<FileName>)>> {
+        Ok(vec![])
+    }
+
+    /// Returns all files in the module graph.
+    fn files(&self) -> Vec<Arc<FileName>> {
+        vec![]
+    }
+
+    /// Registers ambient modules declared by `roots` and files they reference
+    /// or import, before any of them is loaded.
+    ///
+    /// Callers checking multiple root files should call this first, so that
+    /// `declare module "foo"` in one root is visible to imports of the others.
+    fn add_roots(&self, _roots: &[Arc<FileName>]) {}
+
+    /// Returns syntax errors found since the last call.
+    fn take_syntax_errors(&self) -> Vec<Error> {
+        vec![]
+    }
+
+    /// Returns files declaring `filename`, using ambient module declarations
+    /// like `declare module "foo"` or module augmentations like `declare
+    /// module "./foo"`.
+    ///
+    /// Ambient modules are loaded as [FileName::Custom] named after the
+    /// declaration, including wildcard declarations like `declare module
+    /// "*.svg"`.
+    fn declaring_files(&self, _filename: &Arc<FileName>) -> Vec<Arc<FileName>> {
+        vec![]
+    }
+
+    /// Returns names of builtin libraries referenced by loaded files, using
+    /// `/// <reference lib="dom" />`.
+    fn lib_references(&self) -> Vec<JsWord> {
+        vec![]
+    }
 }
 
-/// **NOTE**: [FileName::Custom] is not passed to this type.
+/// [FileName::Custom] is also passed to this type. Ambient modules like
+/// `declare module "foo"` are loaded as custom files named after them, and
+/// they are treated as empty modules if this fails.
 pub trait LoadFile: 'static + Send + Sync {
     fn load_file(&self, cm: &Arc<SourceMap>, filename: &Arc<FileName>) -> Result<(Arc<SourceFile>, Syntax)>;
 }
@@ -74,12 +132,26 @@ where
     /// TODO(kdu1): Split the
     comments: StcComments,
     loading_started: DashSet<Arc<FileName>, FxBuildHasher>,
+    /// Files whose ambient modules are registered.
+    discovered: DashSet<Arc<FileName>, FxBuildHasher>,
     dep_graph: RwLock<FastDiGraphMap<ModuleId, ()>>,
     cycles: RwLock<Vec<Vec<ModuleId>>>,
 
     ids: ModuleIdGenerator,
     parse_cache: DashMap<Arc<FileName>, (Arc<ModuleRecord>, StcComments), FxBuildHasher>,
+    /// A file with syntax errors is loaded with the AST recovered by the
+    /// parser, or as an empty module if the parser cannot recover.
     parsing_errors: Mutex<Vec<swc_ecma_parser::error::Error>>,
+
+    /// Names of ambient modules to the files declaring them.
+    ambient_modules: DashMap<JsWord, Vec<Arc<FileName>>, FxBuildHasher>,
+    /// Modules to the files augmenting them.
+    augmentations: DashMap<Arc<FileName>, Vec<Arc<FileName>>, FxBuildHasher>,
+    /// Names of `/// <reference lib="..." />`.
+    lib_references: DashSet<JsWord, FxBuildHasher>,
+    /// Directories to look up `/// <reference types="..." />` in, before
+    /// `node_modules` of the referencing file.
+    type_roots: Vec<PathBuf>,
 }
 
 impl<L, R> ModuleLoader<L, R>
@@ -96,33 +168,186 @@ where
 
             comments: Default::default(),
             loading_started: Default::default(),
+            discovered: Default::default(),
             dep_graph: Default::default(),
             cycles: Default::default(),
             parse_cache: Default::default(),
             ids: Default::default(),
             parsing_errors: Default::default(),
+            ambient_modules: Default::default(),
+            augmentations: Default::default(),
+            lib_references: Default::default(),
+            type_roots: Default::default(),
         }
     }
 
-    fn load_recursively(&self, filename: &Arc<FileName>, calc_cycles: bool) -> Result<ModuleId> {
-        let (id, _) = self.ids.generate(filename);
+    /// Sets type roots for `/// <reference types="..." />`, which should be
+    /// computed by [crate::type_roots] like typings of the project.
+    pub fn with_type_roots(mut self, type_roots: Vec<PathBuf>) -> Self {
+        self.type_roots = type_roots;
+        self
+    }
 
-        // This function works only once per file.
-        if !self.loading_started.insert(filename.clone()) {
-            return Ok(id);
+    /// Like `tsc`, ambient modules take precedence over files, but wildcard
+    /// declarations are used only if `module_specifier` cannot be resolved.
+    ///
+    /// `@types/foo` of `/// <reference types="foo" />` falls back to `foo`, for
+    /// packages shipping their own types.
+    fn resolve(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<FileName> {
+        if !is_relative(module_specifier) && self.is_ambient_module(module_specifier) {
+            return Ok(FileName::Custom(module_specifier.to_string()));
         }
 
-        let (entry, comments) = self.parse(filename)?;
+        self.resolver
+            .resolve(base, module_specifier)
+            .or_else(|err| match package_of_types(module_specifier) {
+                Some(package) => self.resolver.resolve(base, &package).map_err(|_| err),
+                None => Err(err),
+            })
+            .or_else(|err| {
+                // The pattern with the longest prefix wins.
+                let pattern = self
+                    .ambient_modules
+                    .iter()
+                    .filter(|entry| !entry.value().is_empty())
+                    .filter_map(|entry| {
+                        let (prefix, suffix) = entry.key().split_once('*')?;
+                        let matches = module_specifier.len() >= prefix.len() + suffix.len()
+                            && module_specifier.starts_with(prefix)
+                            && module_specifier.ends_with(suffix);
+
+                        matches.then(|| (prefix.len(), entry.key().to_string()))
+                    })
+                    .max_by_key(|(prefix_len, _)| *prefix_len);
+
+                match pattern {
+                    Some((_, pattern)) => Ok(FileName::Custom(pattern)),
+                    None => Err(err),
+                }
+            })
+    }
+
+    /// Resolves a reference like `/// <reference path="..." />`.
+    ///
+    /// Like `tsc`, `/// <reference types="foo" />` is looked up in type roots
+    /// first, and the first type root containing the package wins.
+    fn resolve_reference(&self, base: &Arc<FileName>, specifier: &str) -> Result<FileName> {
+        if package_of_types(specifier).is_some() {
+            let name = specifier.trim_start_matches("@types/");
+
+            for type_root in &self.type_roots {
+                let dir = type_root.join(name);
+                if let Ok(resolved) = self.resolver.resolve(base, &dir.to_string_lossy()) {
+                    return Ok(resolved);
+                }
+            }
+        }
 
-        let (_declared_modules, references, deps) = find_modules_and_deps(&comments, &entry.ast);
+        self.resolve(base, specifier)
+    }
+
+    /// Returns `true` if a loaded file declares `name` like `declare module
+    /// "foo"`. Wildcard declarations are named after the pattern.
+    fn is_ambient_module(&self, name: &str) -> bool {
+        self.ambient_modules
+            .get(&JsWord::from(name))
+            .map_or(false, |files| !files.is_empty())
+    }
+
+    /// Records `declare module "foo"` of `filename`. Declarations in modules
+    /// augment the resolved modules.
+    fn register_declared_modules(&self, filename: &Arc<FileName>, ast: &Module, names: Vec<JsWord>) {
+        let is_module = ast.body.iter().any(|item| matches!(item, ModuleItem::ModuleDecl(..)));
+
+        for name in names {
+            if is_module || is_relative(&name) {
+                match self.resolve(filename, &name) {
+                    Ok(target) => {
+                        self.augmentations.entry(Arc::new(target)).or_default().push(filename.clone());
+                        continue;
+                    }
+                    Err(err) if is_relative(&name) => {
+                        log::debug!("Failed to resolve the module augmented by `{}`: {:?}", filename, err);
+                        continue;
+                    }
+                    // Declares a new module.
+                    Err(..) => {}
+                }
+            }
+
+            let mut files = self.ambient_modules.entry(name).or_default();
+            if !files.contains(filename) {
+                files.push(filename.clone());
+            }
+        }
+    }
+
+    /// Parses `filename` and files it references or imports, and registers
+    /// ambient modules declared by them.
+    ///
+    /// This runs before imports are resolved for the dependency graph, because
+    /// dependencies are loaded in parallel and `declare module "foo"` should
+    /// not depend on the order they are loaded in.
+    fn discover(&self, filename: &Arc<FileName>) {
+        if !self.discovered.insert(filename.clone()) {
+            return;
+        }
+
+        // Errors are reported by `load_recursively`.
+        let (entry, comments) = match self.parse(filename) {
+            Ok(v) => v,
+            Err(..) => return,
+        };
+
+        let (declared_modules, references, libs, imports) = find_modules_and_deps(&comments, &entry.ast);
+
+        for lib in libs {
+            self.lib_references.insert(lib);
+        }
+
+        self.register_declared_modules(filename, &entry.ast, declared_modules);
+
+        let deps = references
+            .into_iter()
+            .map(|dep| (dep, false))
+            .chain(imports.into_iter().map(|dep| (dep, true)))
+            .collect::<Vec<_>>();
+        let discover_dep = |(dep, is_normal_dep): &(JsWord, bool)| {
+            let resolved = if *is_normal_dep {
+                self.resolve(filename, dep)
+            } else {
+                self.resolve_reference(filename, dep)
+            };
+
+            // Unresolved imports may refer to ambient modules which are not registered
+            // yet.
+            if let Ok(dep_path) = resolved {
+                self.discover(&Arc::new(dep_path));
+            }
+        };
+
+        if cfg!(feature = "no-threading") {
+            deps.iter().for_each(discover_dep);
+        } else {
+            GLOBALS.with(|globals| deps.par_iter().for_each(|dep| GLOBALS.set(globals, || discover_dep(dep))));
+        }
+    }
 
-        let deps = if cfg!(feature = "no-threading") {
+    fn load_deps(&self, filename: &Arc<FileName>, deps: &[JsWord], is_normal_dep: bool) -> Vec<Result<(ModuleId, bool)>> {
+        let resolve = |dep: &JsWord| {
+            if is_normal_dep {
+                self.resolve(filename, dep)
+            } else {
+                self.resolve_reference(filename, dep)
+            }
+        };
+
+        if cfg!(feature = "no-threading") {
             GLOBALS.with(|globals| {
-                (references.iter().map(|v| (v, false)))
-                    .chain(deps.iter().map(|v| (v, true)))
-                    .map(|(dep, is_normal_dep)| {
+                deps.iter()
+                    .map(|dep| {
                         GLOBALS.set(globals, || {
-                            let dep_path = Arc::new(self.resolver.resolve(filename, dep)?);
+                            let dep_path = Arc::new(resolve(dep)?);
 
                             self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
                         })
@@ -131,18 +356,38 @@ where
             })
         } else {
             GLOBALS.with(|globals| {
-                (references.par_iter().map(|v| (v, false)))
-                    .chain(deps.par_iter().map(|v| (v, true)))
-                    .map(|(dep, is_normal_dep)| {
+                deps.par_iter()
+                    .map(|dep| {
                         GLOBALS.set(globals, || {
-                            let dep_path = Arc::new(self.resolver.resolve(filename, dep)?);
+                            let dep_path = Arc::new(resolve(dep)?);
 
                             self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
                         })
                     })
                     .collect::<Vec<_>>()
             })
-        };
+        }
+    }
+
+    fn load_recursively(&self, filename: &Arc<FileName>, calc_cycles: bool) -> Result<ModuleId> {
+        let (id, _) = self.ids.generate(filename);
+
+        // This function works only once per file.
+        if !self.loading_started.insert(filename.clone()) {
+            return Ok(id);
+        }
+
+        self.discover(filename);
+
+        let (entry, comments) = self.parse(filename)?;
+
+        let (declared_modules, references, _, mut imports) = find_modules_and_deps(&comments, &entry.ast);
+
+        // Augmented modules are loaded like imports.
+        imports.extend(declared_modules.into_iter().filter(|name| is_relative(name)));
+
+        let mut deps = self.load_deps(filename, &references, false);
+        deps.extend(self.load_deps(filename, &imports, true));
 
         {
             // Add to the dependency graph
@@ -193,8 +438,10 @@ where
     fn parse_inner(&self, filename: &Arc<FileName>) -> Result<(Arc<ModuleRecord>, StcComments)> {
         let comments = self.comments.clone();
 
-        let (fm, syntax) = match &**filename {
-            FileName::Custom(..) => {
+        let (fm, syntax) = match (&**filename, self.loader.load_file(&self.cm, filename)) {
+            (_, Ok(v)) => v,
+
+            (FileName::Custom(name), Err(..)) if self.is_ambient_module(name) => {
                 let fm = self.cm.new_source_file((**filename).clone(), String::new());
 
                 let (id, top_level_mark) = self.ids.generate(filename);
@@ -215,10 +462,7 @@ where
                 ));
             }
 
-            _ => self
-                .loader
-                .load_file(&self.cm, filename)
-                .with_context(|| format!("failed to load module `{}`", filename))?,
+            (_, Err(err)) => return Err(err.context(format!("failed to load module `{}`", filename))),
         };
 
         let lexer = Lexer::new(syntax, EsVersion::latest(), StringInput::from(&*fm), Some(&comments));
@@ -232,7 +476,11 @@ where
                 let mut errors = self.parsing_errors.lock().unwrap();
                 errors.push(err);
 
-                bail!("Failed to parse {}", filename)
+                Module {
+                    span: Span::new(fm.start_pos, fm.end_pos, Default::default()),
+                    body: Default::default(),
+                    shebang: Default::default(),
+                }
             }
         };
         let extra_errors = parser.take_errors();
@@ -241,6 +489,10 @@ where
             errors.extend(extra_errors);
         }
 
+        if !syntax.typescript() {
+            jsdoc::desugar(&comments, &fm, &mut ast);
+        }
+
         let (id, top_level_mark) = self.ids.generate(filename);
         let top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
 
@@ -306,12 +558,279 @@ where
 
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records> {
         let filename = self
-            .resolver
             .resolve(base, module_specifier)
             .with_context(|| format!("failed to resolve `{}` from `{}`", module_specifier, base))?;
 
         self.load_module(&Arc::new(filename), false)
     }
+
+    fn invalidate(&self, filename: &Arc<FileName>) -> Result<Vec<(ModuleId, Arc<FileName>)>> {
+        if !self.loading_started.contains(filename) {
+            return Ok(vec![]);
+        }
+
+        let (id, _) = self.ids.generate(filename);
+
+        let mut invalidated = vec![id];
+        {
+            let mut g = self.dep_graph.write().unwrap();
+
+            let mut i = 0;
+            while i < invalidated.len() {
+                let cur = invalidated[i];
+                for dependent in g.neighbors_directed(cur, Incoming) {
+                    if !invalidated.contains(&dependent) {
+                        invalidated.push(dependent);
+                    }
+                }
+                i += 1;
+            }
+
+            // Imports of the file may be changed.
+            let deps = g.neighbors_directed(id, Outgoing).collect::<Vec<_>>();
+            for dep in deps {
+                g.remove_edge(id, dep);
+            }
+        }
+
+        self.parse_cache.remove(filename);
+        self.loading_started.remove(filename);
+        self.discovered.remove(filename);
+        for mut files in self.ambient_modules.iter_mut() {
+            files.retain(|file| file != filename);
+        }
+        for mut files in self.augmentations.iter_mut() {
+            files.retain(|file| file != filename);
+        }
+
+        self.load_recursively(filename, false)
+            .with_context(|| format!("failed to reload `{}`", filename))?;
+
+        {
+            let g = self.dep_graph.read().unwrap();
+            *self.cycles.write().unwrap() = kosaraju_scc(&*g).into_iter().filter(|cycle| cycle.len() > 1).collect();
+        }
+
+        Ok(invalidated.into_iter().map(|id| (id, self.ids.path(id))).collect())
+    }
+
+    fn files(&self) -> Vec<Arc<FileName>> {
+        self.loading_started.iter().map(|filename| filename.key().clone()).collect()
+    }
+
+    fn add_roots(&self, roots: &[Arc<FileName>]) {
+        if cfg!(feature = "no-threading") {
+            roots.iter().for_each(|root| self.discover(root));
+        } else {
+            GLOBALS.with(|globals| roots.par_iter().for_each(|root| GLOBALS.set(globals, || self.discover(root))));
+        }
+    }
+
+    fn declaring_files(&self, filename: &Arc<FileName>) -> Vec<Arc<FileName>> {
+        let mut files = self
+            .augmentations
+            .get(filename)
+            .map(|files| files.value().clone())
+            .unwrap_or_default();
+
+        if let FileName::Custom(name) = &**filename {
+            if let Some(declaring) = self.ambient_modules.get(&JsWord::from(&**name)) {
+                files.extend(declaring.iter().cloned());
+            }
+        }
+
+        files
+    }
+
+    fn lib_references(&self) -> Vec<JsWord> {
+        self.lib_references.iter().map(|lib| lib.key().clone()).collect()
+    }
+
+    fn take_syntax_errors(&self) -> Vec<Error> {
+        let errors = take(&mut *self.parsing_errors.lock().unwrap());
+
+        errors
+            .into_iter()
+            .map(|err| {
+                let (code, msg) = tsc_syntax_error(err.kind());
+
+                ErrorKind::SyntaxError {
+                    span: err.span(),
+                    code,
+                    msg: msg.into_owned(),
+                }
+                .into()
+            })
+            .collect()
+    }
+}
+
+fn is_relative(module_specifier: &str) -> bool {
+    module_specifier.starts_with("./") || module_specifier.starts_with("../")
+}
+
+/// Maps a syntax error of swc to the code and the message used by `tsc`.
+///
+/// If the message of `tsc` has placeholders, the message of swc is used.
+/// Errors which `tsc` does not report while parsing are reported as `TS1005`.
+fn tsc_syntax_error(kind: &SyntaxError) -> (usize, Cow<'static, str>) {
+    let (code, msg) = match kind {
+        SyntaxError::TS1003 { .. } | SyntaxError::ExpectedIdent { .. } => (1003, Some("Identifier expected.")),
+        SyntaxError::ExpctedSemi { .. } => (1005, Some("';' expected.")),
+        SyntaxError::TS1005 { .. } | SyntaxError::Expected { .. } | SyntaxError::Unexpected { .. } | SyntaxError::Eof { .. } => {
+            (1005, None)
+        }
+        SyntaxError::UnterminatedStrLit { .. } => (1002, Some("Unterminated string literal.")),
+        SyntaxError::TS1009 { .. } => (1009, Some("Trailing comma not allowed.")),
+        SyntaxError::UnterminatedBlockComment { .. } => (1010, Some("'*/' expected.")),
+        SyntaxError::CommaAfterRestElement { .. } => (1013, Some("A rest parameter or binding pattern may not have a trailing comma.")),
+        SyntaxError::TS1014 { .. } | SyntaxError::NonLastRestParam { .. } => {
+            (1014, Some("A rest parameter must be last in a parameter list."))
+        }
+        SyntaxError::TS1015 { .. } => (1015, Some("Parameter cannot have question mark and initializer.")),
+        SyntaxError::TsRequiredAfterOptional { .. } => (1016, Some("A required parameter cannot follow an optional parameter.")),
+        SyntaxError::TS1029 { .. } => (1029, None),
+        SyntaxError::TS1030 { .. } => (1030, None),
+        SyntaxError::TS1031 { .. } => (1031, None),
+        SyntaxError::TS1038 { .. } => (1038, Some("A 'declare' modifier cannot be used in an already ambient context.")),
+        SyntaxError::TS1042 { .. } => (1042, None),
+        SyntaxError::TS1047 { .. } => (1047, Some("A rest parameter cannot be optional.")),
+        SyntaxError::TS1048 { .. } => (1048, Some("A rest parameter cannot have an initializer.")),
+        SyntaxError::SetterParam { .. } | SyntaxError::SetterParamRequired { .. } => {
+            (1049, Some("A 'set' accessor must have exactly one parameter."))
+        }
+        SyntaxError::RestPatInSetter { .. } => (1053, Some("A 'set' accessor cannot have rest parameter.")),
+        SyntaxError::GetterParam { .. } => (1054, Some("A 'get' accessor cannot have parameters.")),
+        SyntaxError::TS1056 { .. } => (1056, Some("Accessors are only available when targeting ECMAScript 5 and higher.")),
+        SyntaxError::TS1085 { .. } => (1085, None),
+        SyntaxError::TS1089 { .. } => (1089, None),
+        SyntaxError::TS1092 { .. } => (1092, Some("Type parameters cannot appear on a constructor declaration.")),
+        SyntaxError::TS1093 { .. } => (1093, Some("Type annotation cannot appear on a constructor declaration.")),
+        SyntaxError::TS1096 { .. } => (1096, Some("An index signature must have exactly one parameter.")),
+        SyntaxError::TS1098 { .. } => (1098, Some("Type parameter list cannot be empty.")),
+        SyntaxError::TS1100 { .. } => (1100, None),
+        SyntaxError::WithInStrict { .. } => (1101, Some("'with' statements are not allowed in strict mode.")),
+        SyntaxError::TS1102 { .. } => (1102, Some("'delete' cannot be called on an identifier in strict mode.")),
+        SyntaxError::TS1105 { .. } => (
+            1105,
+            Some("A 'break' statement can only be used within an enclosing iteration or switch statement."),
+        ),
+        SyntaxError::TS1106 { .. } => (1106, None),
+        SyntaxError::TS1107 { .. } => (1107, Some("Jump target cannot cross function boundary.")),
+        SyntaxError::ReturnNotAllowed { .. } => (1108, Some("A 'return' statement can only be used within a function body.")),
+        SyntaxError::TS1109 { .. } | SyntaxError::InvalidExpr { .. } | SyntaxError::EmptyParenExpr { .. } => {
+            (1109, Some("Expression expected."))
+        }
+        SyntaxError::TS1110 { .. } => (1110, Some("Type expected.")),
+        SyntaxError::MultipleDefault { .. } => (
+            1113,
+            Some("A 'default' clause cannot appear more than once in a 'switch' statement."),
+        ),
+        SyntaxError::TS1114 { .. } | SyntaxError::DuplicateLabel { .. } => (1114, None),
+        SyntaxError::TS1115 { .. } => (
+            1115,
+            Some("A 'continue' statement can only jump to a label of an enclosing iteration statement."),
+        ),
+        SyntaxError::TS1116 { .. } => (
+            1116,
+            Some("A 'break' statement can only jump to a label of an enclosing statement."),
+        ),
+        SyntaxError::LegacyOctal { .. } => (1121, None),
+        SyntaxError::TS1123 { .. } => (1123, Some("Variable declaration list cannot be empty.")),
+        SyntaxError::ExpectedDigit { .. } => (1124, Some("Digit expected.")),
+        SyntaxError::UnexpectedChar { .. } | SyntaxError::InvalidIdentChar { .. } => (1127, Some("Invalid character.")),
+        SyntaxError::TS1141 { .. } => (1141, Some("String literal expected.")),
+        SyntaxError::LineBreakInThrow { .. } => (1142, Some("Line break not permitted here.")),
+        SyntaxError::ConstDeclarationsRequireInitialization { .. } => (1155, Some("'const' declarations must be initialized.")),
+        SyntaxError::UnterminatedTpl { .. } => (1160, Some("Unterminated template literal.")),
+        SyntaxError::UnterminatedRegExpLit { .. } => (1161, Some("Unterminated regular expression literal.")),
+        SyntaxError::TS1162 { .. } => (1162, Some("An object member cannot be declared optional.")),
+        SyntaxError::TS1164 { .. } => (1164, Some("Computed property names are not allowed in enums.")),
+        SyntaxError::TS1171 { .. } => (1171, Some("A comma expression is not allowed in a computed property name.")),
+        SyntaxError::TS1172 { .. } => (1172, Some("'extends' clause already seen.")),
+        SyntaxError::TS1173 { .. } => (1173, Some("'extends' clause must precede 'implements' clause.")),
+        SyntaxError::TS1174 { .. } => (1174, Some("Classes can only extend a single class.")),
+        SyntaxError::TS1175 { .. } => (1175, Some("'implements' clause already seen.")),
+        SyntaxError::TS1183 { .. } => (1183, Some("An implementation cannot be declared in ambient contexts.")),
+        SyntaxError::TS1184 { .. } => (1184, Some("Modifiers cannot appear here.")),
+        SyntaxError::TS1196 { .. } => (
+            1196,
+            Some("Catch clause variable type annotation must be 'any' or 'unknown' if specified."),
+        ),
+        SyntaxError::TS1242 { .. } => (
+            1242,
+            Some("'abstract' modifier can only appear on a class, method, or property declaration."),
+        ),
+        SyntaxError::TS1243 { .. } => (1243, None),
+        SyntaxError::TS1244 { .. } => (1244, Some("Abstract methods can only appear within an abstract class.")),
+        SyntaxError::TS1245 { .. } => (1245, None),
+        SyntaxError::TS1267 { .. } => (1267, None),
+        SyntaxError::TS1273 { .. } => (1273, None),
+        SyntaxError::TS1274 { .. } => (1274, None),
+        SyntaxError::IdentAfterNum { .. } => (1351, Some("An identifier or keyword cannot immediately follow a numeric literal.")),
+        SyntaxError::TS2206 { .. } => (2206, None),
+        SyntaxError::TS2207 { .. } => (2207, None),
+        SyntaxError::TS2369 { .. } => (2369, Some("A parameter property is only allowed in a constructor implementation.")),
+        SyntaxError::TS2371 { .. } => (
+            2371,
+            Some("A parameter initializer is only allowed in a function or constructor implementation."),
+        ),
+        SyntaxError::DuplicateConstructor { .. } => (2392, Some("Multiple constructor implementations are not allowed.")),
+        SyntaxError::TS2406 { .. } => (
+            2406,
+            Some("The left-hand side of a 'for...in' statement must be a variable or a property access."),
+        ),
+        SyntaxError::TS2410 { .. } => (
+            2410,
+            Some("The 'with' statement is not supported. All symbols in a 'with' block will have type 'any'."),
+        ),
+        SyntaxError::TS2414 { .. } => (2414, None),
+        SyntaxError::TS2427 { .. } => (2427, None),
+        SyntaxError::TS2452 { .. } => (2452, Some("An enum member cannot have a numeric name.")),
+        SyntaxError::TS2483 { .. } => (
+            2483,
+            Some("The left-hand side of a 'for...of' statement cannot use a type annotation."),
+        ),
+        SyntaxError::TS2491 { .. } => (
+            2491,
+            Some("The left-hand side of a 'for...in' statement cannot be a destructuring pattern."),
+        ),
+        SyntaxError::TS2499 { .. } => (
+            2499,
+            Some("An interface can only extend an identifier/qualified-name with optional type arguments."),
+        ),
+        SyntaxError::TS2703 { .. } => (2703, Some("The operand of a 'delete' operator must be a property reference.")),
+        SyntaxError::TS4112 { .. } => (4112, None),
+        SyntaxError::NumericSeparatorIsAllowedOnlyBetweenTwoDigits { .. } => (6188, Some("Numeric separators are not allowed here.")),
+        SyntaxError::TS8038 { .. } => (8038, None),
+        SyntaxError::JSXExpectedClosingTag { .. } => (17002, None),
+        _ => (1005, None),
+    };
+
+    match msg {
+        Some(msg) => (code, msg.into()),
+        None => (code, kind.msg()),
+    }
+}
+
+/// Decides the syntax of a file by its extension. JavaScript files are parsed
+/// as ECMAScript, and JSDoc of them is used as types.
+pub fn file_syntax(path: &Path) -> Syntax {
+    let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+
+    if is_js_file(path) {
+        // Like `tsc`, `.js` files may contain JSX.
+        return Syntax::Es(EsConfig {
+            jsx: ext == "js" || ext == "jsx",
+            ..Default::default()
+        });
+    }
+
+    Syntax::Typescript(TsConfig {
+        dts: is_declaration_file(path),
+        tsx: ext == "tsx",
+        ..Default::default()
+    })
 }
 
 pub struct DefaultFileLoader;
@@ -324,13 +843,7 @@ impl LoadFile for DefaultFileLoader {
                     .load_file(path)
                     .with_context(|| format!("failed to load module `{}`", path.display()))?;
 
-                let syntax = TsConfig {
-                    dts: path.as_os_str().to_string_lossy().ends_with(".d.ts"),
-                    tsx: path.extension().map(|v| v == "tsx").unwrap_or(false),
-                    ..Default::default()
-                };
-
-                Ok((fm, Syntax::Typescript(syntax)))
+                Ok((fm, file_syntax(path)))
             }
             _ => {
                 bail!("DefaultFileLoader only supports real files")
@@ -1,13 +1,17 @@
 use std::{
-    fs::read_dir,
+    fs::{read_dir, read_to_string},
     path::{Path, PathBuf},
     sync::Arc,
     time::Instant,
 };
 
+use fxhash::FxHashSet;
 use rayon::prelude::*;
 use stc_ts_module_loader::resolvers::node::NodeResolver;
-use swc_common::FileName;
+use stc_ts_utils::imports::types_package;
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast::{Decl, EsVersion, ModuleItem, Stmt};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 
 use crate::{loader::LoadModule, Checker};
 
@@ -15,63 +19,133 @@ impl<L> Checker<L>
 where
     L: LoadModule,
 {
-    fn try_loading_typing_of_one_package(&self, dir: &Path) {
-        if !dir.is_dir() {
-            return;
-        }
-
-        let result = NodeResolver
-            .resolve_as_file(dir)
-            .or_else(|_| NodeResolver.resolve_as_directory(dir));
-
-        if let Ok(entry) = result {
-            let entry = Arc::new(FileName::Real(entry));
+    /// Load typings from node_modules.
+    ///
+    /// Type roots are computed by [type_roots]. If `types` is [None], all
+    /// packages in type roots which declare globals are loaded. Otherwise,
+    /// only packages listed in `types` are loaded, so `types: []` disables
+    /// loading packages.
+    ///
+    /// - https://www.typescriptlang.org/tsconfig#typeRoots
+    /// - https://www.typescriptlang.org/tsconfig#types
+    pub fn load_typings(&self, base: &Path, type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
+        let type_roots = self::type_roots(base, type_roots)
+            .into_iter()
+            .filter(|dir| dir.is_dir())
+            .collect::<Vec<_>>();
+
+        let entries = match types {
+            Some(types) => types
+                .par_iter()
+                .filter_map(|name| {
+                    let package = typings_dir_name(name);
+
+                    // Like `tsc`, the first type root containing the package wins.
+                    match type_roots.iter().map(|dir| dir.join(&package)).find(|dir| dir.is_dir()) {
+                        Some(dir) => typings_entry(&dir, false),
+                        None => {
+                            log::warn!("Cannot find typings for `{}`", name);
+                            None
+                        }
+                    }
+                })
+                .collect::<Vec<_>>(),
+            None => {
+                let mut names = FxHashSet::default();
+                let dirs = type_roots
+                    .iter()
+                    .filter_map(|dir| read_dir(dir).ok())
+                    .flat_map(|entries| entries.filter_map(Result::ok).map(|e| e.path()))
+                    .filter(|dir| names.insert(dir.file_name().map(|name| name.to_os_string())))
+                    .collect::<Vec<_>>();
+
+                dirs.into_par_iter().filter_map(|dir| typings_entry(&dir, true)).collect()
+            }
+        };
+
+        // Typings may reference libraries, which should be added before analysis.
+        self.add_roots(&entries);
+
+        entries.into_par_iter().for_each(|entry| {
             let start = Instant::now();
 
-            self.analyze_module(None, entry);
+            self.analyze_module(None, entry.clone());
 
             let end = Instant::now();
-            log::debug!("Loading typings at `{}` took {:?}", dir.display(), end - start);
-        }
+            log::debug!("Loading typings at `{}` took {:?}", entry, end - start);
+        });
     }
+}
 
-    fn load_typings_from_dir(&self, dir: &Path, types: Option<&[String]>) {
-        let types_dir = dir.join("node_modules").join("@types");
-
-        if !types_dir.is_dir() {
-            return Default::default();
-        }
+/// Returns the type roots to look up typings in, which default to
+/// `node_modules/@types` of `base` and its ancestors. Configured type roots are
+/// relative to `base`.
+///
+/// This is used for both [Checker::load_typings] and `/// <reference
+/// types="..." />`.
+pub fn type_roots(base: &Path, type_roots: Option<&[PathBuf]>) -> Vec<PathBuf> {
+    match type_roots {
+        Some(type_roots) => type_roots.iter().map(|dir| base.join(dir)).collect(),
+        None => base.ancestors().map(|dir| dir.join("node_modules").join("@types")).collect(),
+    }
+}
 
-        let dirs = types.map(|s| s.iter().map(|s| PathBuf::from(s.clone())).collect()).or_else(|| {
-            let pkgs = read_dir(&types_dir).ok()?;
+/// Returns the name of the directory in type roots for the package `name`,
+/// like `babel__core` for `@babel/core`.
+pub(crate) fn typings_dir_name(name: &str) -> String {
+    types_package(name).trim_start_matches("@types/").to_string()
+}
 
-            let f = pkgs.into_iter().filter_map(Result::ok).map(|e| e.path()).collect::<Vec<_>>();
+/// Returns the entry of typings at `dir`.
+///
+/// If `only_global` is true, typings which do not declare globals are skipped.
+/// Such typings are analyzed when they are imported.
+fn typings_entry(dir: &Path, only_global: bool) -> Option<Arc<FileName>> {
+    if !dir.is_dir() {
+        return None;
+    }
 
-            Some(f)
-        });
+    let resolver = NodeResolver::new();
+    let entry = resolver.resolve_as_file(dir).or_else(|_| resolver.resolve_as_directory(dir)).ok()?;
 
-        if let Some(dirs) = dirs {
-            dirs.into_par_iter().for_each(|dir| {
-                self.try_loading_typing_of_one_package(&types_dir.join(dir));
-            });
-        }
+    if only_global && !declares_globals(&entry) {
+        log::debug!("Skipping typings at `{}` as they do not declare globals", dir.display());
+        return None;
     }
 
-    /// Load typings from node_modules.
-    ///
-    /// - https://www.typescriptlang.org/tsconfig#typeRoots
-    /// - https://www.typescriptlang.org/tsconfig#types
-    pub fn load_typings(&self, base: &Path, _type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
-        let mut dirs = vec![];
-
-        let mut cur = Some(base);
-        while let Some(c) = cur {
-            dirs.push(c.to_path_buf());
-            cur = c.parent();
-        }
+    Some(Arc::new(FileName::Real(entry)))
+}
 
-        dirs.into_par_iter().for_each(|dir| {
-            self.load_typings_from_dir(&dir, types);
-        });
-    }
+/// Returns `true` if `entry` is a script or has `declare global`. Typings
+/// which cannot be parsed are assumed to declare globals.
+fn declares_globals(entry: &Path) -> bool {
+    let content = match read_to_string(entry) {
+        Ok(v) => v,
+        Err(..) => return true,
+    };
+
+    let cm = SourceMap::default();
+    let fm = cm.new_source_file(FileName::Real(entry.to_path_buf()), content);
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsConfig {
+            dts: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        StringInput::from(&*fm),
+        None,
+    );
+
+    let module = match Parser::new_from(lexer).parse_module() {
+        Ok(v) => v,
+        Err(..) => return true,
+    };
+
+    let is_module = module.body.iter().any(|item| matches!(item, ModuleItem::ModuleDecl(..)));
+
+    !is_module
+        || module
+            .body
+            .iter()
+            .any(|item| matches!(item, ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(m))) if m.global))
 }
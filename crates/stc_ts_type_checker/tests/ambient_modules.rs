@@ -0,0 +1,150 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_env::Rule;
+use stc_ts_type_checker::loader::{memory::MemoryFs, LoadModule};
+use swc_common::FileName;
+
+use self::harness::{check_files, codes, new_checker, take_errors};
+
+fn check(files: &[(&str, &str)], entry: &str) -> Vec<(usize, String)> {
+    check_files(Rule::default(), files, entry)
+}
+
+#[test]
+fn ambient_module() {
+    let errors = check(
+        &[
+            ("/p/types.d.ts", "declare module 'foo' {\n    export const x: number;\n}"),
+            (
+                "/p/index.ts",
+                "/// <reference path=\"./types.d.ts\" />
+import { x } from 'foo';
+const a: number = x;
+const b: string = x;",
+            ),
+        ],
+        "/p/index.ts",
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
+
+#[test]
+fn ambient_module_takes_precedence() {
+    let errors = check(
+        &[
+            ("/p/node_modules/foo/index.d.ts", "export const x: string;"),
+            ("/p/types.d.ts", "declare module 'foo' {\n    export const x: number;\n}"),
+            (
+                "/p/index.ts",
+                "/// <reference path=\"./types.d.ts\" />
+import { x } from 'foo';
+const a: number = x;",
+            ),
+        ],
+        "/p/index.ts",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn wildcard_module() {
+    let errors = check(
+        &[
+            (
+                "/p/types.d.ts",
+                "declare module '*.svg' {\n    const url: string;\n    export default url;\n}",
+            ),
+            (
+                "/p/index.ts",
+                "/// <reference path=\"./types.d.ts\" />
+import logo from './logo.svg';
+import icon from './icons/icon.svg';
+const a: string = logo;
+const b: number = icon;
+import missing from './missing.png';",
+            ),
+        ],
+        "/p/index.ts",
+    );
+
+    assert_eq!(codes(&errors), vec![2307, 2322], "{:?}", errors);
+}
+
+#[test]
+fn module_augmentation() {
+    let errors = check(
+        &[
+            ("/p/existing.ts", "export const a = 1;"),
+            (
+                "/p/augment.ts",
+                "import './existing';
+declare module './existing' {
+    export const added: string;
+}",
+            ),
+            (
+                "/p/index.ts",
+                "/// <reference path=\"./augment.ts\" />
+import { a, added } from './existing';
+const x: number = a;
+const y: number = added;",
+            ),
+        ],
+        "/p/index.ts",
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
+
+/// `./consumer` imports `foo` before `./types` declaring it is loaded.
+#[test]
+fn ambient_module_declared_by_later_import() {
+    let errors = check(
+        &[
+            ("/p/consumer.ts", "import { x } from 'foo';\nexport const a: string = x;"),
+            ("/p/types.d.ts", "declare module 'foo' {\n    export const x: number;\n}"),
+            ("/p/index.ts", "import './consumer';\nimport './types';"),
+        ],
+        "/p/index.ts",
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
+
+/// Roots are checked one by one, but ambient modules of all roots are known
+/// before the first one is loaded.
+#[test]
+fn ambient_module_declared_by_other_root() {
+    let errors = testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(
+            FileName::Real(PathBuf::from("/p/index.ts")),
+            "import { x } from 'foo';\nconst a: string = x;",
+        );
+        fs.insert(
+            FileName::Real(PathBuf::from("/p/types.d.ts")),
+            "declare module 'foo' {\n    export const x: number;\n}",
+        );
+
+        let mut checker = new_checker(cm, Rule::default(), &fs);
+        let roots = ["/p/index.ts", "/p/types.d.ts"]
+            .iter()
+            .map(|path| Arc::new(FileName::Real(PathBuf::from(path))))
+            .collect::<Vec<_>>();
+
+        checker.module_loader().add_roots(&roots);
+        for root in roots {
+            checker.check(root);
+        }
+
+        Ok(take_errors(&mut checker))
+    })
+    .unwrap();
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
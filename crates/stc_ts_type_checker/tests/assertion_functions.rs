@@ -0,0 +1,164 @@
+use stc_ts_env::Rule;
+
+use self::harness::check;
+
+#[path = "common/harness.rs"]
+mod harness;
+
+const ASSERTIONS: &str = "declare function assertIsString(value: unknown): asserts value is string;
+declare function assert(value: unknown): asserts value;
+";
+
+fn codes(src: &str) -> Vec<usize> {
+    let rule = Rule {
+        strict_null_checks: true,
+        ..Default::default()
+    };
+
+    harness::codes(&check(rule, &format!("{}{}", ASSERTIONS, src)))
+}
+
+#[test]
+fn narrows_rest_of_flow() {
+    let errors = codes(
+        "export function f(x: unknown, y: string | undefined) {
+    assertIsString(x);
+    const s: string = x;
+    assert(y);
+    const t: string = y;
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn does_not_narrow_before_call() {
+    let errors = codes(
+        "export function f(x: unknown) {
+    const s: string = x;
+    assertIsString(x);
+}",
+    );
+
+    assert_eq!(errors, vec![2322]);
+}
+
+#[test]
+fn narrows_properties() {
+    let errors = codes(
+        "interface Box {
+    value: unknown;
+}
+export function f(b: Box) {
+    assertIsString(b.value);
+    const s: string = b.value;
+}
+export class C {
+    value: unknown;
+
+    m() {
+        assertIsString(this.value);
+        const s: string = this.value;
+    }
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn asserts_truthy_property() {
+    let errors = codes(
+        "interface Box {
+    value: string | undefined;
+}
+export function f(b: Box) {
+    assert(b.value);
+    const s: string = b.value;
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn asserts_this() {
+    let errors = codes(
+        "class Animal {
+    assertIsDog(): asserts this is Dog {
+        if (!(this instanceof Dog)) {
+            throw new Error();
+        }
+    }
+}
+class Dog extends Animal {
+    bark(): void {}
+}
+export function f(a: Animal) {
+    a.assertIsDog();
+    a.bark();
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn call_target_without_type_ann() {
+    let errors = codes(
+        "const inferred = (value: unknown): asserts value => {};
+const annotated: (value: unknown) => asserts value = inferred;
+export function f(x: unknown) {
+    inferred(x);
+    annotated(x);
+}",
+    );
+
+    assert_eq!(errors, vec![2775]);
+}
+
+#[test]
+fn call_target_is_param_without_type_ann() {
+    let errors = codes(
+        "export function f(x: unknown, inferred = (value: unknown): asserts value => {}, annotated: (value: unknown) => asserts value) {
+    inferred(x);
+    annotated(x);
+}",
+    );
+
+    assert_eq!(errors, vec![2775]);
+}
+
+#[test]
+fn call_target_not_dotted_name() {
+    let errors = codes(
+        "declare const asserts: { [key: string]: (value: unknown) => asserts value };
+export function f(x: unknown) {
+    asserts['key'](x);
+}",
+    );
+
+    assert_eq!(errors, vec![2776]);
+}
+
+#[test]
+fn call_target_is_class_property_without_type_ann() {
+    let errors = codes(
+        "export class C {
+    inferred = (value: unknown): asserts value => {};
+    annotated: (value: unknown) => asserts value = (value: unknown): asserts value => {};
+
+    m(x: unknown) {
+        this.inferred(x);
+        this.annotated(x);
+    }
+}
+export function f(c: C, x: unknown) {
+    c.inferred(x);
+    c.annotated(x);
+}",
+    );
+
+    assert_eq!(errors, vec![2775, 2775]);
+}
@@ -0,0 +1,202 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::check;
+
+fn codes(rule: Rule, src: &str) -> Vec<usize> {
+    harness::codes(&check(rule, src))
+}
+
+fn implicit_returns() -> Rule {
+    Rule {
+        no_implicit_returns: true,
+        ..Default::default()
+    }
+}
+
+fn fallthrough() -> Rule {
+    Rule {
+        no_fallthrough_cases_in_switch: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn missing_return() {
+    let src = "export function f(x: boolean) {
+    if (x) {
+        return 1;
+    }
+}";
+
+    assert_eq!(codes(Rule::default(), src), vec![]);
+    assert_eq!(codes(implicit_returns(), src), vec![7030]);
+}
+
+#[test]
+fn all_paths_return() {
+    let errors = codes(
+        implicit_returns(),
+        "export function f(x: boolean) {
+    if (x) {
+        return 1;
+    } else {
+        return 2;
+    }
+}
+export function g(x: boolean) {
+    try {
+        return 1;
+    } finally {
+        x = false;
+    }
+}
+export function h() {
+    while (true) {
+        return 1;
+    }
+}
+export function thrown(x: boolean) {
+    if (x) {
+        return 1;
+    }
+    throw new Error();
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn void_and_any() {
+    let errors = codes(
+        implicit_returns(),
+        "export function v(x: boolean): void {
+    if (x) {
+        return;
+    }
+}
+export function a(x: boolean): any {
+    if (x) {
+        return 1;
+    }
+}
+export function noValue(x: boolean) {
+    if (x) {
+        return;
+    }
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn bare_return() {
+    let errors = codes(
+        implicit_returns(),
+        "export function f(x: boolean): number | undefined {
+    if (x) {
+        return;
+    }
+    return 1;
+}",
+    );
+
+    assert_eq!(errors, vec![7030]);
+}
+
+#[test]
+fn fallthrough_cases() {
+    let src = "export function f(x: number) {
+    let y = 0;
+    switch (x) {
+        case 0:
+        case 1:
+            y = 1;
+        case 2:
+            y = 2;
+            break;
+        case 3:
+            return y;
+        default:
+            y = 3;
+    }
+    return y;
+}";
+
+    assert_eq!(codes(Rule::default(), src), vec![]);
+    assert_eq!(codes(fallthrough(), src), vec![7029]);
+}
+
+#[test]
+fn never_returning_call() {
+    let errors = codes(
+        implicit_returns(),
+        "declare function fail(message: string): never;
+
+export function f(x: boolean) {
+    if (x) {
+        return 1;
+    }
+    fail('x');
+}
+export function g(x: boolean) {
+    if (x) {
+        return 1;
+    } else {
+        fail('x');
+    }
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn labeled_break() {
+    let src = "export function f(x: boolean) {
+    outer: {
+        if (x) {
+            break outer;
+        }
+        return 1;
+    }
+}";
+
+    assert_eq!(codes(implicit_returns(), src), vec![7030]);
+
+    // `break inner` does not exit the loop.
+    let src = "export function g(x: boolean) {
+    while (true) {
+        inner: {
+            if (x) {
+                break inner;
+            }
+            return 1;
+        }
+    }
+}";
+
+    assert_eq!(codes(implicit_returns(), src), vec![]);
+}
+
+#[test]
+fn fallthrough_after_never_returning_call() {
+    let src = "declare function fail(message: string): never;
+
+export function f(x: number) {
+    switch (x) {
+        case 0:
+            fail('x');
+        case 1:
+            return 1;
+        default:
+            return 2;
+    }
+}";
+
+    assert_eq!(codes(fallthrough(), src), vec![]);
+}
@@ -0,0 +1,69 @@
+//! Checks files served by [MemoryFs], for tests which do not need fixtures on
+//! the disk.
+#![allow(dead_code)]
+
+use std::{io::sink, path::PathBuf, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_type_checker::{
+    loader::{memory::MemoryFs, ModuleLoader},
+    Checker,
+};
+use swc_common::{errors::Handler, FileName, SourceMap};
+use swc_ecma_ast::EsVersion;
+
+pub type MemoryChecker = Checker<ModuleLoader<MemoryFs, MemoryFs>>;
+
+/// The entry of [check].
+pub const ENTRY: &str = "/p/index.ts";
+
+pub fn new_checker(cm: Arc<SourceMap>, rule: Rule, fs: &MemoryFs) -> MemoryChecker {
+    let handler = Arc::new(Handler::with_emitter_writer(Box::new(sink()), None));
+    let env = Env::simple(rule, EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+
+    Checker::new(
+        cm.clone(),
+        handler,
+        env.clone(),
+        None,
+        ModuleLoader::new(cm, env, fs.clone(), fs.clone()),
+    )
+}
+
+/// Takes errors of `checker` as codes and messages, sorted.
+pub fn take_errors(checker: &mut MemoryChecker) -> Vec<(usize, String)> {
+    let mut errors = ErrorKind::flatten(checker.take_errors())
+        .into_iter()
+        .map(|err| (err.code(), err.message()))
+        .collect::<Vec<_>>();
+    errors.sort();
+    errors
+}
+
+pub fn codes(errors: &[(usize, String)]) -> Vec<usize> {
+    errors.iter().map(|(code, _)| *code).collect()
+}
+
+/// Checks `entry` among `files`, which are pairs of paths and contents.
+pub fn check_files(rule: Rule, files: &[(&str, &str)], entry: &str) -> Vec<(usize, String)> {
+    testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        for (path, content) in files {
+            fs.insert(FileName::Real(PathBuf::from(path)), *content);
+        }
+
+        let mut checker = new_checker(cm, rule, &fs);
+        checker.check(Arc::new(FileName::Real(PathBuf::from(entry))));
+
+        Ok(take_errors(&mut checker))
+    })
+    .unwrap()
+}
+
+/// Checks `src` as [ENTRY].
+pub fn check(rule: Rule, src: &str) -> Vec<(usize, String)> {
+    check_files(rule, &[(ENTRY, src)], ENTRY)
+}
@@ -0,0 +1,105 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::{check, codes};
+
+fn legacy() -> Rule {
+    Rule {
+        experimental_decorators: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn legacy_decorators() {
+    let errors = check(
+        legacy(),
+        "declare function sealed(target: Function): void;
+declare function log(target: Object, key: string, descriptor: TypedPropertyDescriptor<(x: number) => string>): void;
+declare function prop(target: Object, key: string): void;
+declare function inject(target: Object, key: string | undefined, index: number): void;
+
+@sealed
+export class A {
+    @prop
+    name: string = '';
+
+    constructor(@inject value: number) {}
+
+    @log
+    method(@inject x: number): string {
+        return '';
+    }
+}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn legacy_method_decorator_mismatch() {
+    let errors = check(
+        legacy(),
+        "declare function log(target: Object, key: string, descriptor: TypedPropertyDescriptor<(x: string) => string>): void;
+
+export class A {
+    @log
+    method(x: number): string {
+        return '';
+    }
+}",
+    );
+
+    assert_eq!(codes(&errors), vec![1241], "{:?}", errors);
+}
+
+#[test]
+fn legacy_param_decorator_mismatch() {
+    let errors = check(
+        legacy(),
+        "declare function inject(target: Object, key: string, index: string): void;
+
+export class A {
+    method(@inject x: number): void {}
+}",
+    );
+
+    assert_eq!(codes(&errors), vec![1239], "{:?}", errors);
+}
+
+#[test]
+fn standard_method_decorator() {
+    let src = "declare function bound(value: (x: number) => string, context: ClassMethodDecoratorContext): void;
+
+export class A {
+    @bound
+    method(x: number): string {
+        return '';
+    }
+
+    @bound
+    other(x: boolean): string {
+        return '';
+    }
+}";
+
+    let errors = check(Rule::default(), src);
+
+    assert_eq!(codes(&errors), vec![1241], "{:?}", errors);
+}
+
+#[test]
+fn standard_param_decorator() {
+    let errors = check(
+        Rule::default(),
+        "declare function inject(value: undefined, context: unknown): void;
+
+export class A {
+    method(@inject x: number): void {}
+}",
+    );
+
+    assert_eq!(codes(&errors), vec![1206], "{:?}", errors);
+}
@@ -0,0 +1,120 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::{check, codes};
+
+#[test]
+fn ts_ignore() {
+    let errors = check(
+        Rule::default(),
+        "// @ts-ignore
+const a: number = 'a';
+const b: number = 'b';",
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
+
+#[test]
+fn ts_ignore_skips_blank_lines_and_comments() {
+    let errors = check(
+        Rule::default(),
+        "// @ts-ignore
+
+// The next line is wrong.
+const a: number = 'a';",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn ts_ignore_in_block_comment() {
+    let errors = check(
+        Rule::default(),
+        "/*
+ * @ts-ignore
+ */
+const a: number = 'a';",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn used_ts_expect_error() {
+    let errors = check(
+        Rule::default(),
+        "declare function f(a: number, b: number): void;
+f(
+    1,
+    // @ts-expect-error
+    'b',
+);",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn unused_ts_expect_error() {
+    let errors = check(
+        Rule::default(),
+        "// @ts-expect-error
+const a: number = 1;",
+    );
+
+    assert_eq!(errors, vec![(2578, "Unused '@ts-expect-error' directive.".into())]);
+}
+
+/// The comment is not at the start or end of any node.
+#[test]
+fn unattached_ts_expect_error() {
+    let errors = check(
+        Rule::default(),
+        "function f() {
+    // @ts-expect-error
+}",
+    );
+
+    assert_eq!(codes(&errors), vec![2578], "{:?}", errors);
+}
+
+#[test]
+fn ts_nocheck() {
+    let errors = check(
+        Rule::default(),
+        "// @ts-nocheck
+const a: number = 'a';
+// @ts-expect-error
+const b: number = 1;",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn ts_nocheck_after_statement_is_ignored() {
+    let errors = check(
+        Rule::default(),
+        "const a: number = 'a';
+// @ts-nocheck
+const b: number = 'b';",
+    );
+
+    assert_eq!(codes(&errors), vec![2322, 2322], "{:?}", errors);
+}
+
+#[test]
+fn ts_check_overrides_ts_nocheck() {
+    let errors = check(
+        Rule::default(),
+        "// @ts-nocheck
+// @ts-check
+const a: number = 'a';",
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
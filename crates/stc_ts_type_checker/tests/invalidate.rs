@@ -0,0 +1,46 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use std::sync::Arc;
+
+use stc_ts_env::Rule;
+use stc_ts_type_checker::loader::memory::MemoryFs;
+use swc_common::FileName;
+
+use self::harness::{new_checker, take_errors};
+
+fn real(path: &str) -> FileName {
+    FileName::Real(path.into())
+}
+
+#[test]
+fn invalidate_dependents() {
+    testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(real("a.ts"), "export const a: number = 1;");
+        fs.insert(real("b.ts"), "import { a } from './a';\nexport const b: string = a;");
+        fs.insert(real("c.ts"), "export const c: string = 1;");
+
+        let mut checker = new_checker(cm, Rule::default(), &fs);
+
+        let b = Arc::new(real("b.ts"));
+        let c = Arc::new(real("c.ts"));
+
+        checker.check(b.clone());
+        checker.check(c.clone());
+        assert_eq!(take_errors(&mut checker).len(), 2);
+
+        fs.insert(real("a.ts"), "export const a: string = '';");
+
+        let mut invalidated = checker.invalidate(&Arc::new(real("a.ts"))).unwrap();
+        invalidated.sort();
+        assert_eq!(invalidated, vec![Arc::new(real("a.ts")), Arc::new(real("b.ts"))]);
+
+        checker.check(b);
+        checker.check(c);
+        assert_eq!(take_errors(&mut checker).len(), 0);
+
+        Ok(())
+    })
+    .unwrap();
+}
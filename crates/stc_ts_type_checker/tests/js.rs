@@ -0,0 +1,123 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use std::path::Path;
+
+use stc_ts_env::Rule;
+use stc_ts_type_checker::loader::file_syntax;
+use swc_ecma_parser::{EsConfig, Syntax};
+
+use self::harness::{check_files, codes};
+
+fn check(files: &[(&str, &str)], entry: &str, check_js: bool) -> Vec<(usize, String)> {
+    let rule = Rule {
+        check_js,
+        ..Default::default()
+    };
+
+    check_files(rule, files, entry)
+}
+
+#[test]
+fn js_syntax() {
+    assert!(matches!(file_syntax(Path::new("a.js")), Syntax::Es(EsConfig { jsx: true, .. })));
+    assert!(!file_syntax(Path::new("a.mjs")).typescript());
+    assert!(file_syntax(Path::new("a.d.ts")).dts());
+}
+
+#[test]
+fn jsdoc_types() {
+    let errors = check(
+        &[(
+            "a.js",
+            "/** @type {number} */
+const n = 'a';
+
+/**
+ * @param {string} s
+ * @returns {number}
+ */
+function len(s) {
+    return s.length;
+}
+len(1);
+
+/**
+ * @template T
+ * @param {T} x
+ * @returns {T}
+ */
+function id(x) {
+    return x;
+}
+/** @type {string} */
+const s = id(1);",
+        )],
+        "a.js",
+        true,
+    );
+
+    assert_eq!(codes(&errors), vec![2322, 2322, 2345], "{:?}", errors);
+}
+
+#[test]
+fn jsdoc_typedef_and_import() {
+    let errors = check(
+        &[
+            (
+                "types.js",
+                "/**
+ * @typedef {Object} Point
+ * @property {number} x
+ * @property {number} [y]
+ */
+
+/** @typedef {string | number} Id */
+
+export {};",
+            ),
+            (
+                "main.js",
+                "/** @import { Point, Id } from './types' */
+
+/** @type {Point} */
+export const p = { x: 1 };
+
+/** @type {Id} */
+export const id = true;",
+            ),
+        ],
+        "main.js",
+        true,
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
+
+#[test]
+fn exports_of_js_are_inferred() {
+    let errors = check(
+        &[
+            ("util.js", "export const name = 'stc';"),
+            ("main.ts", "import { name } from './util';\nconst n: number = name;"),
+        ],
+        "main.ts",
+        false,
+    );
+
+    assert_eq!(codes(&errors), vec![2322], "{:?}", errors);
+}
+
+#[test]
+fn errors_of_js_require_check_js() {
+    let code = "/** @type {number} */\nconst n = 'a';";
+
+    assert_eq!(check(&[("a.js", code)], "a.js", false), vec![]);
+    assert_eq!(codes(&check(&[("a.js", code)], "a.js", true)), vec![2322]);
+
+    let checked = format!("// @ts-check\n{}", code);
+    assert_eq!(codes(&check(&[("a.js", &checked)], "a.js", false)), vec![2322]);
+
+    let unchecked = format!("// @ts-nocheck\n{}", code);
+    assert_eq!(check(&[("a.js", &unchecked)], "a.js", true), vec![]);
+}
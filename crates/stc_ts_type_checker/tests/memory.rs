@@ -0,0 +1,113 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_env::Rule;
+use stc_ts_type_checker::loader::memory::MemoryFs;
+use stc_ts_types::ModuleId;
+use swc_common::{FileName, SourceMap};
+use swc_ecma_loader::resolve::Resolve;
+
+use self::harness::{take_errors, MemoryChecker};
+
+fn new_checker(cm: Arc<SourceMap>, fs: &MemoryFs) -> MemoryChecker {
+    harness::new_checker(cm, Rule::default(), fs)
+}
+
+fn codes(checker: &mut MemoryChecker) -> Vec<usize> {
+    harness::codes(&take_errors(checker))
+}
+
+fn custom(name: &str) -> FileName {
+    FileName::Custom(name.into())
+}
+
+fn real(path: &str) -> FileName {
+    FileName::Real(PathBuf::from(path))
+}
+
+#[test]
+fn custom_files() {
+    testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(custom("lib.ts"), "export const name = 'stc';");
+        fs.insert(custom("snippet.ts"), "import { name } from './lib';\nconst n: number = name;");
+
+        let mut checker = new_checker(cm, &fs);
+        checker.check(Arc::new(custom("snippet.ts")));
+
+        assert_eq!(codes(&mut checker), vec![2322]);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn missing_custom_file() {
+    testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(custom("snippet.ts"), "import { name } from 'typo';");
+
+        let mut checker = new_checker(cm, &fs);
+        assert_eq!(checker.check(Arc::new(custom("typo.ts"))), ModuleId::builtin());
+
+        checker.check(Arc::new(custom("snippet.ts")));
+        assert_eq!(codes(&mut checker), vec![2307]);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn update_between_checks() {
+    testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        let entry = Arc::new(real("/project/index.ts"));
+        fs.insert(real("/project/lib.ts"), "export const value = 'a';");
+        fs.insert((*entry).clone(), "import { value } from './lib';\nconst n: number = value;");
+
+        let mut checker = new_checker(cm, &fs);
+        checker.check(entry.clone());
+        assert_eq!(codes(&mut checker), vec![2322]);
+
+        fs.insert(real("/project/lib.ts"), "export const value = 1;");
+        let invalidated = checker.invalidate(&Arc::new(real("/project/lib.ts"))).unwrap();
+        assert!(invalidated.contains(&entry));
+
+        checker.check(entry);
+        assert_eq!(codes(&mut checker), vec![]);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn resolve() {
+    let fs = MemoryFs::new();
+    for path in [
+        "/p/src/a.ts",
+        "/p/src/dir/index.tsx",
+        "/p/src/b.d.ts",
+        "/p/node_modules/pkg/index.d.ts",
+        "/p/node_modules/@types/node/index.d.ts",
+    ] {
+        fs.insert(real(path), "");
+    }
+    fs.insert(custom("virtual:config"), "");
+
+    let base = real("/p/src/main.ts");
+    let resolve = |target| fs.resolve(&base, target).ok();
+
+    assert_eq!(resolve("./a"), Some(real("/p/src/a.ts")));
+    assert_eq!(resolve("./a.js"), Some(real("/p/src/a.ts")));
+    assert_eq!(resolve("./dir"), Some(real("/p/src/dir/index.tsx")));
+    assert_eq!(resolve("../src/b"), Some(real("/p/src/b.d.ts")));
+    assert_eq!(resolve("pkg"), Some(real("/p/node_modules/pkg/index.d.ts")));
+    assert_eq!(resolve("node"), Some(real("/p/node_modules/@types/node/index.d.ts")));
+    assert_eq!(resolve("virtual:config"), Some(custom("virtual:config")));
+    assert_eq!(resolve("./missing"), None);
+}
@@ -0,0 +1,58 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::check;
+
+/// Pairs of sources and the diagnostic reported by `tsc`.
+const CASES: &[(&str, usize, &str)] = &[
+    ("let a = b;", 2304, "Cannot find name 'b'."),
+    ("let a = a;", 2448, "Block-scoped variable 'a' used before its declaration."),
+    (
+        "declare const s: string;
+const n: number = s;",
+        2322,
+        "Type 'string' is not assignable to type 'number'.",
+    ),
+    (
+        "function f(a: number) {}
+f();",
+        2554,
+        "Expected 1 arguments, but got 0.",
+    ),
+    (
+        "declare const t: [number];
+const [a, b] = t;",
+        2493,
+        "Tuple type '[number]' of length '1' has no element at index '1'.",
+    ),
+    (
+        "declare const t: [number] | [string];
+const [a, b] = t;",
+        2339,
+        "Property '1' does not exist on type '[number] | [string]'.",
+    ),
+    (
+        "interface A {
+    a: number;
+    b: string;
+}
+interface B {
+    a: number;
+}
+declare const b: B;
+const a: A = b;",
+        2741,
+        "Property 'b' is missing in type 'B' but required in type 'A'.",
+    ),
+];
+
+#[test]
+fn messages_match_tsc() {
+    for (src, code, msg) in CASES {
+        let errors = check(Rule::default(), src);
+
+        assert_eq!(errors, vec![(*code, msg.to_string())], "{}", src);
+    }
+}
@@ -0,0 +1,43 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::{check_files, codes};
+
+fn check(files: &[(&str, &str)], entry: &str) -> Vec<(usize, String)> {
+    check_files(Rule::default(), files, entry)
+}
+
+#[test]
+fn unresolved_imports() {
+    let errors = check(
+        &[(
+            "a.ts",
+            "import { x } from './missing';
+import * as ns from 'pkg';
+export { y } from './gone';
+export * from './gone';
+const n: number = x + ns.foo;
+const s: string = 1;",
+        )],
+        "a.ts",
+    );
+
+    assert_eq!(codes(&errors), vec![2307, 2307, 2307, 2307, 2322], "{:?}", errors);
+    assert!(errors.iter().any(|(_, msg)| msg.contains("'./missing'")), "{:?}", errors);
+}
+
+#[test]
+fn unresolved_dynamic_import() {
+    let errors = check(&[("a.ts", "import('./missing');")], "a.ts");
+
+    assert_eq!(codes(&errors), vec![2307], "{:?}", errors);
+}
+
+#[test]
+fn missing_entry() {
+    let errors = check(&[], "a.ts");
+
+    assert_eq!(errors, vec![]);
+}
@@ -0,0 +1,149 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_type_checker::{
+    loader::{memory::MemoryFs, ModuleLoader},
+    type_roots, Checker,
+};
+use swc_common::{errors::Handler, FileName};
+use swc_ecma_ast::EsVersion;
+
+use self::harness::{check_files, codes, new_checker, take_errors, ENTRY};
+
+#[path = "common/harness.rs"]
+mod harness;
+
+fn check(files: &[(&str, &str)]) -> Vec<(usize, String)> {
+    check_files(Rule::default(), files, ENTRY)
+}
+
+#[test]
+fn lib_reference() {
+    let code = "const body: HTMLElement = document.body;";
+
+    assert_ne!(check(&[(ENTRY, code)]), vec![]);
+
+    let errors = check(&[
+        ("/p/globals.d.ts", "/// <reference lib=\"dom\" />\ndeclare const version: string;"),
+        (ENTRY, &format!("/// <reference path=\"./globals.d.ts\" />\n{}", code)),
+    ]);
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn lib_reference_of_later_root() {
+    let errors = testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(FileName::Real("/p/a.ts".into()), "const body: HTMLElement = document.body;");
+        fs.insert(FileName::Real("/p/b.ts".into()), "/// <reference lib=\"dom\" />\nexport {};");
+
+        let mut checker = new_checker(cm, Rule::default(), &fs);
+        let roots = vec![
+            Arc::new(FileName::Real("/p/a.ts".into())),
+            Arc::new(FileName::Real("/p/b.ts".into())),
+        ];
+
+        checker.add_roots(&roots);
+        for root in roots {
+            checker.check(root);
+        }
+
+        Ok(take_errors(&mut checker))
+    })
+    .unwrap();
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn lib_reference_does_not_affect_clones_of_env() {
+    let errors = testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(
+            FileName::Real(PathBuf::from(ENTRY)),
+            "/// <reference lib=\"dom\" />\nconst body: HTMLElement = document.body;",
+        );
+        fs.insert(FileName::Real("/p/other.ts".into()), "const body: HTMLElement = document.body;");
+
+        let env = Env::simple(Rule::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+        let checker_with_env = || {
+            let handler = Arc::new(Handler::with_emitter_writer(Box::new(std::io::sink()), None));
+            Checker::new(
+                cm.clone(),
+                handler,
+                env.clone(),
+                None,
+                ModuleLoader::new(cm.clone(), env.clone(), fs.clone(), fs.clone()),
+            )
+        };
+
+        let mut checker = checker_with_env();
+        checker.check(Arc::new(FileName::Real(PathBuf::from(ENTRY))));
+        assert_eq!(take_errors(&mut checker), vec![]);
+
+        let mut checker = checker_with_env();
+        checker.check(Arc::new(FileName::Real("/p/other.ts".into())));
+
+        Ok(take_errors(&mut checker))
+    })
+    .unwrap();
+
+    assert_ne!(errors, vec![]);
+}
+
+#[test]
+fn types_reference() {
+    let errors = check(&[
+        ("/p/node_modules/foo/index.d.ts", "declare const foo: number;"),
+        ("/p/node_modules/@types/foo/index.d.ts", "declare const foo: string;"),
+        ("/p/node_modules/bar/index.d.ts", "declare const bar: string;"),
+        (
+            ENTRY,
+            "/// <reference types=\"foo\" />
+/// <reference types=\"bar\" />
+const a: number = foo;
+const b: number = bar;",
+        ),
+    ]);
+
+    assert_eq!(codes(&errors), vec![2322, 2322], "{:?}", errors);
+}
+
+#[test]
+fn types_reference_in_type_root() {
+    let errors = testing::run_test(false, |cm, _| {
+        let fs = MemoryFs::new();
+        fs.insert(FileName::Real("/p/types/foo/index.d.ts".into()), "declare const foo: number;");
+        fs.insert(
+            FileName::Real("/p/node_modules/@types/foo/index.d.ts".into()),
+            "declare const foo: string;",
+        );
+        fs.insert(
+            FileName::Real(PathBuf::from(ENTRY)),
+            "/// <reference types=\"foo\" />\nconst a: number = foo;",
+        );
+
+        let handler = Arc::new(Handler::with_emitter_writer(Box::new(std::io::sink()), None));
+        let env = Env::simple(Rule::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+        let type_roots = type_roots(Path::new("/p"), Some(&[PathBuf::from("types")]));
+        let mut checker = Checker::new(
+            cm.clone(),
+            handler,
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, fs.clone(), fs).with_type_roots(type_roots),
+        );
+
+        checker.check(Arc::new(FileName::Real(PathBuf::from(ENTRY))));
+
+        Ok(take_errors(&mut checker))
+    })
+    .unwrap();
+
+    assert_eq!(errors, vec![]);
+}
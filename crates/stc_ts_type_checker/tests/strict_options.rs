@@ -0,0 +1,128 @@
+use stc_ts_env::Rule;
+
+use self::harness::check;
+
+#[path = "common/harness.rs"]
+mod harness;
+
+fn codes(rule: Rule, src: &str) -> Vec<usize> {
+    harness::codes(&check(rule, src))
+}
+
+fn strict() -> Rule {
+    Rule {
+        strict_null_checks: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn unchecked_indexed_access() {
+    let src = "declare const arr: number[];
+declare const map: { [key: string]: number };
+export const a: number = arr[0];
+export const b: number = map['key'];
+export const c: number | undefined = arr[0];";
+
+    assert_eq!(codes(strict(), src), vec![]);
+    assert_eq!(
+        codes(
+            Rule {
+                no_unchecked_indexed_access: true,
+                ..strict()
+            },
+            src
+        ),
+        vec![2322, 2322]
+    );
+}
+
+#[test]
+fn unchecked_indexed_access_of_tuple_rest() {
+    let src = "declare const t: [string, ...number[]];
+export const a: string = t[0];
+export const b: number = t[1];
+export const c: number = t[5];
+export const d: number | undefined = t[1];";
+
+    assert_eq!(codes(strict(), src), vec![]);
+    assert_eq!(
+        codes(
+            Rule {
+                no_unchecked_indexed_access: true,
+                ..strict()
+            },
+            src
+        ),
+        vec![2322, 2322]
+    );
+}
+
+#[test]
+fn property_access_from_index_signature() {
+    let src = "declare const map: { [key: string]: number; known: number };
+export const a = map.unknown;
+export const b = map['unknown'];
+export const c = map.known;";
+
+    assert_eq!(codes(Rule::default(), src), vec![]);
+    assert_eq!(
+        codes(
+            Rule {
+                no_property_access_from_index_signature: true,
+                ..Default::default()
+            },
+            src
+        ),
+        vec![4111]
+    );
+}
+
+#[test]
+fn unknown_in_catch_variables() {
+    let src = "try {
+} catch (e) {
+    const s: string = e;
+}
+try {
+} catch (e: any) {
+    const s: string = e;
+}
+export {};";
+
+    assert_eq!(codes(strict(), src), vec![]);
+    assert_eq!(
+        codes(
+            Rule {
+                use_unknown_in_catch_variables: true,
+                ..strict()
+            },
+            src
+        ),
+        vec![2322]
+    );
+}
+
+#[test]
+fn exact_optional_property_types() {
+    let src = "interface A {
+    x?: number;
+}
+export const a: A = { x: undefined };
+export const b: A = {};
+export function f(v: A) {
+    v.x = undefined;
+}";
+
+    assert_eq!(codes(strict(), src), vec![]);
+    assert_eq!(
+        codes(
+            Rule {
+                exact_optional_property_types: true,
+                ..strict()
+            },
+            src
+        ),
+        vec![2375, 2412]
+    );
+}
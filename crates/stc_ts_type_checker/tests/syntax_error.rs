@@ -0,0 +1,35 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::{check_files, codes};
+
+fn check(files: &[(&str, &str)], entry: &str) -> Vec<(usize, String)> {
+    check_files(Rule::default(), files, entry)
+}
+
+#[test]
+fn syntax_error_in_dependency() {
+    let errors = check(
+        &[("a.ts", "import './b';\nconst s: string = 1;"), ("b.ts", "export const b = ;")],
+        "a.ts",
+    );
+
+    assert_eq!(errors[0], (1109, "Expression expected.".into()), "{:?}", errors);
+    assert_eq!(codes(&errors), vec![1109, 2322]);
+}
+
+#[test]
+fn syntax_error_in_entry() {
+    let errors = check(&[("a.ts", "const a = ;")], "a.ts");
+
+    assert_eq!(errors, vec![(1109, "Expression expected.".into())]);
+}
+
+#[test]
+fn unterminated_string_literal() {
+    let errors = check(&[("a.ts", "const a = 'abc;\n")], "a.ts");
+
+    assert_eq!(errors, vec![(1002, "Unterminated string literal.".into())]);
+}
@@ -525,7 +525,7 @@ impl Resolve for TestFileSystem {
         println!("resolve: {:?} {:?}", base, module_specifier);
 
         if !module_specifier.starts_with('.') {
-            return NodeResolver.resolve(base, module_specifier);
+            return NodeResolver::new().resolve(base, module_specifier);
         }
 
         if let Some(name) = module_specifier.strip_prefix("./") {
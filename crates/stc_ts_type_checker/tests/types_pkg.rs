@@ -44,7 +44,7 @@ fn run_tests_for_types_pkg(module_specifier: &str) {
             handler.clone(),
             env.clone(),
             None,
-            ModuleLoader::new(cm, env, NodeResolver, DefaultFileLoader),
+            ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader),
         );
 
         checker.check(Arc::new(path));
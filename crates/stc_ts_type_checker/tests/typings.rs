@@ -0,0 +1,63 @@
+use std::{
+    io::sink,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    loader::{DefaultFileLoader, LoadModule, ModuleLoader},
+    Checker,
+};
+use swc_common::{errors::Handler, FileName};
+use swc_ecma_ast::EsVersion;
+
+/// Returns names of packages loaded by [Checker::load_typings].
+fn load_typings(types: Option<&[String]>) -> Vec<String> {
+    testing::run_test(false, |cm, _| {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("typings");
+
+        let handler = Arc::new(Handler::with_emitter_writer(Box::new(sink()), None));
+        let env = Env::simple(Rule::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+        let checker = Checker::new(
+            cm.clone(),
+            handler,
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader),
+        );
+
+        checker.load_typings(&base, Some(&[PathBuf::from("types")]), types);
+
+        let mut packages = checker
+            .module_loader()
+            .files()
+            .iter()
+            .filter_map(|filename| match &**filename {
+                FileName::Real(path) => Some(path.parent()?.file_name()?.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        packages.sort();
+        Ok(packages)
+    })
+    .unwrap()
+}
+
+#[test]
+fn only_global_typings_are_loaded() {
+    assert_eq!(load_typings(None), vec!["augment", "global"]);
+}
+
+#[test]
+fn listed_types_are_loaded() {
+    assert_eq!(load_typings(Some(&["module".to_string()])), vec!["module"]);
+}
+
+#[test]
+fn empty_types() {
+    assert_eq!(load_typings(Some(&[])), Vec::<String>::new());
+}
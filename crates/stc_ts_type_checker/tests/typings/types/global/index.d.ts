@@ -0,0 +1 @@
+declare const version: string;
@@ -0,0 +1 @@
+export declare function helper(): void;
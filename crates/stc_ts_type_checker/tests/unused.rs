@@ -0,0 +1,274 @@
+#[path = "common/harness.rs"]
+mod harness;
+
+use stc_ts_env::Rule;
+
+use self::harness::{check_files, ENTRY};
+
+fn check(rule: Rule, src: &str) -> Vec<(usize, String)> {
+    check_files(
+        rule,
+        &[("/p/lib.ts", "export const a = 1;\nexport const b = 2;"), (ENTRY, src)],
+        ENTRY,
+    )
+}
+
+fn locals() -> Rule {
+    Rule {
+        no_unused_locals: true,
+        ..Default::default()
+    }
+}
+
+fn parameters() -> Rule {
+    Rule {
+        no_unused_parameters: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn disabled_by_default() {
+    let errors = check(
+        Rule::default(),
+        "import { a } from './lib';\nconst x = 1;\nexport function f(y: number) {}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn unused_locals() {
+    let errors = check(
+        locals(),
+        "import { a, b } from './lib';
+const used = a;
+let written = 1;
+written = 2;
+type Alias = string;
+interface Iface {}
+function recursive(): void {
+    recursive();
+}
+export const value = used;
+export function f(y: number) {}",
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            (6133, "'b' is declared but its value is never read.".into()),
+            (6133, "'recursive' is declared but its value is never read.".into()),
+            (6133, "'written' is declared but its value is never read.".into()),
+            (6196, "'Alias' is declared but never used.".into()),
+            (6196, "'Iface' is declared but never used.".into()),
+        ]
+    );
+}
+
+#[test]
+fn grouped_declarations() {
+    let errors = check(
+        locals(),
+        "import { a, b } from './lib';
+const x = 1, y = 2;
+const { p, q } = { p: 1, q: 2 };
+const [_first, second] = [1, 2];
+const { r: _renamed, s: _s } = { r: 1, s: 2 };
+export {};",
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            (6133, "'second' is declared but its value is never read.".into()),
+            (6192, "All imports in import declaration are unused.".into()),
+            (6198, "All destructured elements are unused.".into()),
+            (6199, "All variables are unused.".into()),
+        ]
+    );
+}
+
+#[test]
+fn exported_and_ambient_declarations() {
+    let errors = check(
+        locals(),
+        "declare const ambient: number;
+declare namespace NS {
+    const inner: number;
+}
+declare global {
+    interface Window {}
+}
+const exported = 1;
+export { exported };
+export interface Exported {}",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn script_globals() {
+    let errors = check(locals(), "const global = 1;\nfunction g() {\n    const local = 1;\n}");
+
+    assert_eq!(errors, vec![(6133, "'local' is declared but its value is never read.".into())]);
+}
+
+#[test]
+fn private_members() {
+    let errors = check(
+        locals(),
+        "export let values: unknown;
+export class A {
+    private used = 1;
+    private unused = 2;
+    #usedName = 3;
+    #unusedName = 4;
+    constructor(private p: number, private q: number) {
+        values = [this.used, this.#usedName, this.p];
+    }
+}",
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            (6133, "'#unusedName' is declared but its value is never read.".into()),
+            (6133, "'unused' is declared but its value is never read.".into()),
+            (6138, "Property 'q' is declared but its value is never read.".into()),
+        ]
+    );
+}
+
+#[test]
+fn unused_parameters() {
+    let errors = check(
+        parameters(),
+        "export function f(used: number, unused: number, _ignored: number) {
+    return used;
+}
+export function g<T, U>(x: T) {
+    return x;
+}
+export function h<T, U>() {}
+export declare function ambient(x: number): void;
+export const arrow = (x: number, { a }: { a: number }) => 1;",
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            (6133, "'U' is declared but its value is never read.".into()),
+            (6133, "'a' is declared but its value is never read.".into()),
+            (6133, "'unused' is declared but its value is never read.".into()),
+            (6133, "'x' is declared but its value is never read.".into()),
+            (6205, "All type parameters are unused.".into()),
+        ]
+    );
+}
+
+#[test]
+fn namespaces() {
+    let errors = check(
+        locals(),
+        "namespace Unused {
+    const hidden = 1;
+    export const shown = 2;
+}
+namespace Used {
+    export const value = 1;
+}
+namespace Used {
+    export const other = Used.value;
+}
+export const x = Used.other;",
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            (6133, "'Unused' is declared but its value is never read.".into()),
+            (6133, "'hidden' is declared but its value is never read.".into()),
+        ]
+    );
+}
+
+#[test]
+fn enums() {
+    let errors = check(
+        locals(),
+        "enum Unused {
+    A,
+}
+enum Used {
+    A,
+    B = A,
+}
+const enum ConstUsed {
+    C,
+}
+export const e = [Used.B, ConstUsed.C];",
+    );
+
+    assert_eq!(errors, vec![(6196, "'Unused' is declared but never used.".into())]);
+}
+
+#[test]
+fn overloads() {
+    let src = "function unused(x: number): number;
+function unused(x: string): string;
+function unused(x: any) {
+    return x;
+}
+function used(x: number): number;
+function used(x: any, y?: any) {
+    return x;
+}
+export const o = used(1);
+export class C {
+    private m(x: number): void;
+    private m(x: any) {}
+    private n(): void;
+    private n() {}
+    run() {
+        this.n();
+    }
+}";
+
+    // Like `tsc`, each declaration is reported.
+    assert_eq!(
+        check(locals(), src),
+        vec![
+            (6133, "'m' is declared but its value is never read.".into()),
+            (6133, "'m' is declared but its value is never read.".into()),
+            (6133, "'unused' is declared but its value is never read.".into()),
+            (6133, "'unused' is declared but its value is never read.".into()),
+            (6133, "'unused' is declared but its value is never read.".into()),
+        ]
+    );
+
+    // Parameters of overload signatures are not reported.
+    assert_eq!(
+        check(parameters(), src),
+        vec![
+            (6133, "'x' is declared but its value is never read.".into()),
+            (6133, "'y' is declared but its value is never read.".into()),
+        ]
+    );
+}
+
+#[test]
+fn private_accessors() {
+    let errors = check(
+        locals(),
+        "export class A {
+    private get unused() {
+        return 1;
+    }
+    private set unused(v: number) {}
+}",
+    );
+
+    assert_eq!(errors, vec![(6133, "'unused' is declared but its value is never read.".into())]);
+}
@@ -8,23 +8,51 @@ use swc_common::{
 pub enum ImportRef {
     /// path="foo"
     Path(JsWord),
-    /// type="foo"
+    /// types="foo"
     Types(JsWord),
+    /// lib="foo"
+    Lib(JsWord),
 
     /// ES6 import.
     Normal(JsWord),
 }
 
 impl ImportRef {
-    pub fn to_path(self) -> JsWord {
+    /// Returns the module specifier to load, or [None] for `lib` references
+    /// which are builtin libraries.
+    ///
+    /// `types` references are loaded from the `@types` package, like `tsc`.
+    /// Module loaders should fall back to the package itself using
+    /// [package_of_types].
+    pub fn to_path(self) -> Option<JsWord> {
         match self {
-            ImportRef::Path(s) => format!("./{}", s).into(),
-            ImportRef::Types(s) => s,
-            ImportRef::Normal(s) => s,
+            ImportRef::Path(s) => Some(format!("./{}", s).into()),
+            ImportRef::Types(s) => Some(types_package(&s).into()),
+            ImportRef::Lib(..) => None,
+            ImportRef::Normal(s) => Some(s),
         }
     }
 }
 
+/// Returns the name of the `@types` package for `package`, like `@types/node`
+/// for `node` and `@types/babel__core` for `@babel/core`.
+pub fn types_package(package: &str) -> String {
+    match package.strip_prefix('@') {
+        Some(scoped) => format!("@types/{}", scoped.replacen('/', "__", 1)),
+        None => format!("@types/{}", package),
+    }
+}
+
+/// Reverse of [types_package].
+pub fn package_of_types(module_specifier: &str) -> Option<String> {
+    let name = module_specifier.strip_prefix("@types/")?;
+
+    Some(match name.split_once("__") {
+        Some((scope, name)) => format!("@{}/{}", scope, name),
+        None => name.to_string(),
+    })
+}
+
 pub fn find_imports_in_comments<C>(comments: C, span: Span) -> Vec<ImportRef>
 where
     C: Comments,
@@ -56,8 +84,8 @@ where
                     deps.push(ImportRef::Path(path.into()));
                 } else if let Some(path) = cmt_text.strip_prefix("types=\"") {
                     deps.push(ImportRef::Types(path.into()));
-                } else {
-                    // TODO: Handle lib, types
+                } else if let Some(lib) = cmt_text.strip_prefix("lib=\"") {
+                    deps.push(ImportRef::Lib(lib.to_ascii_lowercase().into()));
                 }
             }
         }
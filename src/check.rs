@@ -1,4 +1,34 @@
+use std::{
+    collections::HashSet,
+    env::current_dir,
+    fs::metadata,
+    io::stdout,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Error};
 use clap::Args;
+use stc_ts_env::Env;
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::{node::is_declaration_file, paths::PathsResolver};
+use stc_ts_type_checker::{
+    loader::{DefaultFileLoader, LoadModule, ModuleLoader},
+    type_roots, Checker,
+};
+use swc_common::{errors::Handler, FileName, SourceMap, Spanned};
+
+use crate::{
+    config::ProjectConfig,
+    format::{self, display_path, Diagnostic, Format},
+    project::{ProjectFileLoader, ProjectGraph, ReferencedDts},
+};
+
+/// Interval of checking modification times of files in watch mode.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
 
 /// Perform type checking, but this command is not public api and is only used
 /// for testing.
@@ -16,3 +46,320 @@ pub struct TestCommand {
     #[clap(long)]
     pub types: Option<Vec<String>>,
 }
+
+/// Type check a project, using the `tsconfig.json`.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct CheckCommand {
+    /// Path to the `tsconfig.json` file, or to a directory containing it.
+    ///
+    /// Defaults to the nearest `tsconfig.json` of the current directory.
+    #[clap(short = 'p', long)]
+    pub project: Option<PathBuf>,
+
+    /// The format of diagnostics. Formats other than `human` are written to
+    /// stdout.
+    #[clap(long, value_enum, default_value_t = Format::Human)]
+    pub format: Format,
+
+    /// Type check again when files in the module graph are modified.
+    #[clap(short, long)]
+    pub watch: bool,
+}
+
+impl CheckCommand {
+    /// Returns the number of errors found.
+    pub fn run(self, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<usize, Error> {
+        let project = match &self.project {
+            Some(path) => path.clone(),
+            None => {
+                let cwd = current_dir().context("failed to get current directory")?;
+                ProjectConfig::find(&cwd).with_context(|| format!("failed to find tsconfig.json in `{}`", cwd.display()))?
+            }
+        };
+
+        if !self.watch {
+            let graph = ProjectGraph::load(ProjectConfig::load(&project)?)?;
+            let mut errors = vec![];
+            for checker in &mut new_checkers(&cm, &handler, &graph) {
+                errors.extend(checker.check(&cm)?);
+            }
+
+            return self.report(&cm, &handler, &errors);
+        }
+
+        // Projects are loaded again if a `tsconfig.json` is modified.
+        loop {
+            match ProjectConfig::load(&project).and_then(ProjectGraph::load) {
+                Ok(graph) => self.watch(&cm, &handler, &graph)?,
+                Err(err) => {
+                    handler.err(&format!("{:?}", err));
+                    eprintln!("Watching for changes of the project config.");
+
+                    let path = if project.is_dir() {
+                        project.join("tsconfig.json")
+                    } else {
+                        project.clone()
+                    };
+                    wait_for_changes(&[Arc::new(FileName::Real(path))], &[]);
+                }
+            }
+        }
+    }
+
+    /// Type checks `graph` whenever files are modified, until a `tsconfig.json`
+    /// of it is modified.
+    fn watch(&self, cm: &Arc<SourceMap>, handler: &Arc<Handler>, graph: &ProjectGraph) -> Result<(), Error> {
+        let mut checkers = new_checkers(cm, handler, graph);
+        let configs = graph.projects.iter().collect::<Vec<_>>();
+
+        let mut changed = vec![];
+        loop {
+            let result = checkers.iter_mut().try_fold(vec![], |mut errors, checker| -> Result<_, Error> {
+                checker.invalidate(cm, &changed)?;
+                errors.extend(checker.check(cm)?);
+                Ok(errors)
+            });
+
+            match result {
+                Ok(errors) => {
+                    let error_count = self.report(cm, handler, &errors)?;
+                    eprintln!("Found {} errors. Watching for file changes.", error_count);
+                }
+                Err(err) => {
+                    handler.err(&format!("{:?}", err));
+                }
+            }
+
+            let mut files = checkers
+                .iter()
+                .flat_map(|checker| checker.checker.module_loader().files())
+                .collect::<Vec<_>>();
+            files.sort();
+            files.dedup();
+
+            match wait_for_changes(&files, &configs) {
+                Change::Files(files) => changed = files,
+                Change::Config => return Ok(()),
+            }
+        }
+    }
+
+    /// Returns the number of errors.
+    fn report(&self, cm: &SourceMap, handler: &Handler, errors: &[stc_ts_errors::Error]) -> Result<usize, Error> {
+        match self.format {
+            Format::Human => {
+                for err in errors {
+                    err.emit(handler);
+                }
+            }
+            _ => {
+                let diagnostics = errors.iter().map(|err| Diagnostic::new(cm, err)).collect::<Vec<_>>();
+                format::write(self.format, &diagnostics, &mut stdout().lock()).context("failed to write diagnostics")?;
+            }
+        }
+
+        log::info!("Found {} errors", errors.len());
+
+        Ok(errors.len())
+    }
+}
+
+/// Creates a checker for each project of `graph`.
+fn new_checkers<'a>(cm: &Arc<SourceMap>, handler: &Arc<Handler>, graph: &'a ProjectGraph) -> Vec<ProjectChecker<'a>> {
+    let dts = ReferencedDts::default();
+
+    // Referenced projects come first, so their declarations are available to
+    // projects depending on them.
+    let project_count = graph.projects.len();
+    graph
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(idx, config)| ProjectChecker::new(cm, handler, config, dts.clone(), idx + 1 != project_count))
+        .collect()
+}
+
+/// Type checker of a project.
+///
+/// In watch mode, this is reused so only changed modules and modules depending
+/// on them are analyzed again.
+struct ProjectChecker<'a> {
+    config: &'a ProjectConfig,
+    checker: Checker<ModuleLoader<ProjectFileLoader, PathsResolver>>,
+    /// If true, declarations of the project are stored to `dts`.
+    is_referenced: bool,
+    dts: ReferencedDts,
+    /// Errors of modules which are analyzed.
+    errors: Vec<stc_ts_errors::Error>,
+}
+
+impl<'a> ProjectChecker<'a> {
+    fn new(cm: &Arc<SourceMap>, handler: &Arc<Handler>, config: &'a ProjectConfig, dts: ReferencedDts, is_referenced: bool) -> Self {
+        let env = {
+            let start = Instant::now();
+
+            let env = Env::simple(config.rule(), config.target(), config.module(), &config.libs());
+
+            let end = Instant::now();
+
+            log::info!("Loading builtin libraries took {:?}", end - start);
+
+            env
+        };
+
+        {
+            let start = Instant::now();
+
+            let checker = Checker::new(
+                cm.clone(),
+                handler.clone(),
+                env.clone(),
+                None,
+                ModuleLoader::new(cm.clone(), env.clone(), config.resolver(), DefaultFileLoader),
+            );
+
+            checker.load_typings(&config.dir, config.type_roots().as_deref(), config.types());
+
+            let end = Instant::now();
+
+            log::info!("Loading typing libraries took {:?}", end - start);
+        }
+
+        let checker = Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env, config.resolver(), ProjectFileLoader { dts: dts.clone() })
+                .with_type_roots(type_roots(&config.dir, config.type_roots().as_deref())),
+        );
+
+        ProjectChecker {
+            config,
+            checker,
+            is_referenced,
+            dts,
+            errors: vec![],
+        }
+    }
+
+    /// Type checks root files of the project, and returns all errors of the
+    /// project.
+    ///
+    /// Modules analyzed by previous calls are not analyzed again unless they
+    /// are invalidated.
+    fn check(&mut self, cm: &Arc<SourceMap>) -> Result<Vec<stc_ts_errors::Error>, Error> {
+        let files = self.config.root_files()?;
+        log::info!("Found {} root files in `{}`", files.len(), self.config.path.display());
+
+        let start = Instant::now();
+
+        let roots = files.iter().map(|file| Arc::new(FileName::Real(file.clone()))).collect::<Vec<_>>();
+        self.checker.add_roots(&roots);
+
+        let mut module_ids = vec![];
+        for (file, root) in files.into_iter().zip(roots) {
+            let id = self.checker.check(root);
+            module_ids.push((file, id));
+        }
+
+        if self.is_referenced {
+            for (file, id) in module_ids {
+                if is_declaration_file(&file) {
+                    continue;
+                }
+
+                if let Some(module) = self.checker.take_dts(id) {
+                    self.dts.insert(cm, file, &module);
+                }
+            }
+        }
+
+        self.errors.extend(ErrorKind::flatten(self.checker.take_errors()));
+
+        let end = Instant::now();
+
+        log::info!("Checking `{}` took {:?}", display_path(&self.config.path), end - start);
+
+        Ok(self.errors.clone())
+    }
+
+    /// Invalidates `changed` files and modules depending on them, and drops
+    /// their errors.
+    fn invalidate(&mut self, cm: &SourceMap, changed: &[Arc<FileName>]) -> Result<(), Error> {
+        let mut invalidated = HashSet::new();
+        for filename in changed {
+            invalidated.extend(self.checker.invalidate(filename)?.into_iter().map(|filename| (*filename).clone()));
+        }
+
+        if invalidated.is_empty() {
+            return Ok(());
+        }
+
+        self.errors.retain(|err| {
+            let span = err.span();
+
+            span.is_dummy() || !invalidated.contains(&cm.lookup_byte_offset(span.lo).sf.name)
+        });
+
+        Ok(())
+    }
+}
+
+/// A change found in watch mode.
+enum Change {
+    /// Files in the module graph are modified, or root files are added.
+    Files(Vec<Arc<FileName>>),
+    /// A `tsconfig.json` is modified, so projects should be loaded again.
+    Config,
+}
+
+/// Polls modification times of `files` and `tsconfig.json` files of `configs`
+/// including the ones they extend, and root files of `configs`, until any of
+/// them is changed.
+fn wait_for_changes(files: &[Arc<FileName>], configs: &[&ProjectConfig]) -> Change {
+    let modified_time = |path: &Path| metadata(path).and_then(|m| m.modified()).ok();
+    let file_modified_time = |filename: &FileName| match filename {
+        FileName::Real(path) => modified_time(path),
+        _ => None,
+    };
+    // Files added to directories of `include` are not in the module graph.
+    let root_files = || configs.iter().map(|config| config.root_files().ok()).collect::<Vec<_>>();
+
+    let config_paths = configs
+        .iter()
+        .flat_map(|config| Some(&config.path).into_iter().chain(&config.extends))
+        .collect::<Vec<_>>();
+
+    let before = files.iter().map(|filename| file_modified_time(filename)).collect::<Vec<_>>();
+    let configs_before = config_paths.iter().map(|path| modified_time(path)).collect::<Vec<_>>();
+    let roots_before = root_files();
+
+    loop {
+        sleep(POLL_INTERVAL);
+
+        if config_paths
+            .iter()
+            .zip(&configs_before)
+            .any(|(path, before)| modified_time(path) != *before)
+        {
+            return Change::Config;
+        }
+
+        let changed = files
+            .iter()
+            .zip(&before)
+            .filter(|(filename, before)| file_modified_time(filename) != **before)
+            .map(|(filename, _)| filename.clone())
+            .collect::<Vec<_>>();
+
+        if !changed.is_empty() {
+            return Change::Files(changed);
+        }
+
+        if root_files() != roots_before {
+            return Change::Files(vec![]);
+        }
+    }
+}
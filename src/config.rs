@@ -0,0 +1,775 @@
+//! Loading of `tsconfig.json`.
+
+use std::{
+    collections::HashSet,
+    fs::read_to_string,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Error};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{ModuleConfig, Rule};
+use stc_ts_module_loader::resolvers::{
+    node::{ModuleResolution, NodeResolver},
+    paths::{PathsConfig, PathsResolver},
+};
+use swc_ecma_ast::EsVersion;
+use walkdir::WalkDir;
+
+/// Extensions of files which are included by `include` patterns.
+static SUPPORTED_EXTENSIONS: &[&str] = &[".d.ts", ".ts", ".tsx", ".mts", ".cts"];
+
+/// Extensions of JavaScript files, which are included only if `allowJs` is
+/// enabled.
+static JS_EXTENSIONS: &[&str] = &[".js", ".jsx", ".mjs", ".cjs"];
+
+/// Directories excluded when `exclude` is not specified.
+static DEFAULT_EXCLUDE: &[&str] = &["node_modules", "bower_components", "jspm_packages"];
+
+/// Options of which values are paths relative to the declaring
+/// `tsconfig.json`.
+static PATH_OPTIONS: &[&str] = &["typeRoots", "outDir", "rootDir", "declarationDir", "baseUrl", "rootDirs"];
+
+/// Content of a `tsconfig.json`, with `extends` applied and relative paths
+/// resolved.
+///
+/// See https://www.typescriptlang.org/tsconfig
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsConfig {
+    #[serde(default)]
+    pub compiler_options: CompilerOptions,
+
+    /// Not inherited by `extends`.
+    #[serde(default)]
+    pub references: Vec<ProjectReference>,
+
+    #[serde(default)]
+    pub files: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    pub strict: Option<bool>,
+    pub no_implicit_any: Option<bool>,
+    pub no_implicit_this: Option<bool>,
+    pub always_strict: Option<bool>,
+    pub strict_null_checks: Option<bool>,
+    pub strict_function_types: Option<bool>,
+    pub use_unknown_in_catch_variables: Option<bool>,
+
+    pub allow_unreachable_code: Option<bool>,
+    pub allow_unused_labels: Option<bool>,
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+    pub no_implicit_returns: Option<bool>,
+    pub suppress_excess_property_errors: Option<bool>,
+    pub suppress_implicit_any_index_errors: Option<bool>,
+    pub no_strict_generic_checks: Option<bool>,
+    pub no_unused_locals: Option<bool>,
+    pub no_unused_parameters: Option<bool>,
+    pub exact_optional_property_types: Option<bool>,
+    pub no_unchecked_indexed_access: Option<bool>,
+    pub no_property_access_from_index_signature: Option<bool>,
+    pub use_define_for_class_fields: Option<bool>,
+    pub experimental_decorators: Option<bool>,
+    pub allow_js: Option<bool>,
+    pub check_js: Option<bool>,
+
+    pub target: Option<String>,
+    pub module: Option<String>,
+    pub module_resolution: Option<String>,
+    pub lib: Option<Vec<String>>,
+    pub no_lib: Option<bool>,
+    pub jsx: Option<String>,
+
+    pub types: Option<Vec<String>>,
+    pub type_roots: Option<Vec<String>>,
+
+    pub base_url: Option<String>,
+    pub paths: Option<IndexMap<String, Vec<String>>>,
+    pub root_dirs: Option<Vec<String>>,
+
+    pub out_dir: Option<String>,
+
+    pub composite: Option<bool>,
+}
+
+/// An element of `references`.
+///
+/// See https://www.typescriptlang.org/docs/handbook/project-references.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectReference {
+    /// Absolute path to the referenced `tsconfig.json` or its directory.
+    pub path: String,
+}
+
+/// A `tsconfig.json` with paths resolved.
+#[derive(Debug, Clone)]
+pub struct ProjectConfig {
+    /// Path to the `tsconfig.json` file.
+    pub path: PathBuf,
+
+    /// Directory containing the `tsconfig.json` file.
+    pub dir: PathBuf,
+
+    /// Directory of the `tsconfig.json` declaring `compilerOptions.paths`,
+    /// which may be a base config. Values of `paths` are relative to it if
+    /// `baseUrl` is not specified.
+    pub paths_base: Option<PathBuf>,
+
+    /// Paths to the configs extended by this one, directly or not.
+    pub extends: Vec<PathBuf>,
+
+    pub raw: TsConfig,
+}
+
+impl ProjectConfig {
+    /// Loads `tsconfig.json` at `path`. If `path` is a directory,
+    /// `tsconfig.json` in it is used.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let path = if path.is_dir() { path.join("tsconfig.json") } else { path.to_path_buf() };
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("failed to find tsconfig at `{}`", path.display()))?;
+
+        let (raw, paths_base, extends) = load_with_extends(&path, &mut vec![])?;
+        let raw = serde_json::from_value(raw).with_context(|| format!("failed to parse `{}`", path.display()))?;
+
+        Ok(ProjectConfig {
+            dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            path,
+            paths_base,
+            extends,
+            raw,
+        })
+    }
+
+    /// Finds `tsconfig.json` in `dir` or its ancestors.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors().map(|dir| dir.join("tsconfig.json")).find(|path| path.is_file())
+    }
+
+    pub fn rule(&self) -> Rule {
+        let opts = &self.raw.compiler_options;
+        let strict = opts.strict.unwrap_or(false);
+
+        Rule {
+            no_implicit_any: opts.no_implicit_any.unwrap_or(strict),
+            no_implicit_this: opts.no_implicit_this.unwrap_or(strict),
+            always_strict: opts.always_strict.unwrap_or(strict),
+            strict_null_checks: opts.strict_null_checks.unwrap_or(strict),
+            strict_function_types: opts.strict_function_types.unwrap_or(strict),
+            use_unknown_in_catch_variables: opts.use_unknown_in_catch_variables.unwrap_or(strict),
+
+            allow_unreachable_code: opts.allow_unreachable_code.unwrap_or(false),
+            allow_unused_labels: opts.allow_unused_labels.unwrap_or(false),
+            no_fallthrough_cases_in_switch: opts.no_fallthrough_cases_in_switch.unwrap_or(false),
+            no_implicit_returns: opts.no_implicit_returns.unwrap_or(false),
+            suppress_excess_property_errors: opts.suppress_excess_property_errors.unwrap_or(false),
+            suppress_implicit_any_index_errors: opts.suppress_implicit_any_index_errors.unwrap_or(false),
+            no_strict_generic_checks: opts.no_strict_generic_checks.unwrap_or(false),
+            no_unused_locals: opts.no_unused_locals.unwrap_or(false),
+            no_unused_parameters: opts.no_unused_parameters.unwrap_or(false),
+            exact_optional_property_types: opts.exact_optional_property_types.unwrap_or(false),
+            no_unchecked_indexed_access: opts.no_unchecked_indexed_access.unwrap_or(false),
+            no_property_access_from_index_signature: opts.no_property_access_from_index_signature.unwrap_or(false),
+            // https://www.typescriptlang.org/tsconfig#useDefineForClassFields
+            use_define_property_for_class_fields: opts
+                .use_define_for_class_fields
+                .unwrap_or(matches!(self.target(), EsVersion::Es2022 | EsVersion::EsNext)),
+            experimental_decorators: opts.experimental_decorators.unwrap_or(false),
+            check_js: opts.check_js.unwrap_or(false),
+
+            jsx: opts
+                .jsx
+                .as_deref()
+                .and_then(|s| s.to_lowercase().parse().ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Defaults to `es3`, like `tsc`.
+    pub fn target(&self) -> EsVersion {
+        self.raw
+            .compiler_options
+            .target
+            .as_deref()
+            .and_then(parse_target)
+            .unwrap_or(EsVersion::Es3)
+    }
+
+    pub fn module(&self) -> ModuleConfig {
+        match self.raw.compiler_options.module.as_deref().map(str::to_lowercase) {
+            Some(s) => s.parse().unwrap_or(ModuleConfig::EsNext),
+            None => match self.target() {
+                EsVersion::Es3 | EsVersion::Es5 => ModuleConfig::CommonJs,
+                _ => ModuleConfig::Es2015,
+            },
+        }
+    }
+
+    /// Defaults to `module` if it's `node16` or `nodenext`, and `node10`
+    /// otherwise.
+    pub fn module_resolution(&self) -> ModuleResolution {
+        let opts = &self.raw.compiler_options;
+
+        opts.module_resolution
+            .as_deref()
+            .or_else(|| opts.module.as_deref())
+            .and_then(|s| s.to_lowercase().parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// `checkJs` implies `allowJs`.
+    pub fn allow_js(&self) -> bool {
+        let opts = &self.raw.compiler_options;
+
+        opts.allow_js.or(opts.check_js).unwrap_or(false)
+    }
+
+    /// Resolver applying `moduleResolution`, `baseUrl`, `paths` and
+    /// `rootDirs`.
+    pub fn resolver(&self) -> PathsResolver {
+        let opts = &self.raw.compiler_options;
+
+        PathsResolver::new(
+            NodeResolver::with_mode(self.module_resolution()).allow_js(self.allow_js()),
+            PathsConfig {
+                base_url: opts.base_url.as_ref().map(PathBuf::from),
+                paths: opts.paths.clone().unwrap_or_default(),
+                paths_base: self.paths_base.clone(),
+                root_dirs: opts.root_dirs.iter().flatten().map(PathBuf::from).collect(),
+            },
+        )
+    }
+
+    /// Builtin libraries to load.
+    ///
+    /// If `lib` is not specified, the default libraries of the target are
+    /// used.
+    pub fn libs(&self) -> Vec<Lib> {
+        let opts = &self.raw.compiler_options;
+        if opts.no_lib.unwrap_or(false) {
+            return vec![];
+        }
+
+        let mut libs = match &opts.lib {
+            Some(names) => names.iter().flat_map(|name| load_lib(name)).collect::<Vec<_>>(),
+            None => match self.target() {
+                EsVersion::Es3 | EsVersion::Es5 => Lib::load("es5.full"),
+                EsVersion::Es2015 => Lib::load("es2015.full"),
+                EsVersion::Es2016 => Lib::load("es2016.full"),
+                EsVersion::Es2017 => Lib::load("es2017.full"),
+                EsVersion::Es2018 => Lib::load("es2018.full"),
+                EsVersion::Es2019 => Lib::load("es2019.full"),
+                EsVersion::Es2020 => Lib::load("es2020.full"),
+                EsVersion::Es2021 => Lib::load("es2021.full"),
+                EsVersion::Es2022 => Lib::load("es2022.full"),
+                _ => Lib::load("esnext.full"),
+            },
+        };
+        libs.sort();
+        libs.dedup();
+        libs
+    }
+
+    pub fn types(&self) -> Option<&[String]> {
+        self.raw.compiler_options.types.as_deref()
+    }
+
+    pub fn type_roots(&self) -> Option<Vec<PathBuf>> {
+        self.raw
+            .compiler_options
+            .type_roots
+            .as_ref()
+            .map(|roots| roots.iter().map(PathBuf::from).collect())
+    }
+
+    /// Paths to `tsconfig.json` of the referenced projects.
+    pub fn references(&self) -> Vec<PathBuf> {
+        self.raw
+            .references
+            .iter()
+            .map(|r| {
+                let path = PathBuf::from(&r.path);
+                if path.is_dir() {
+                    path.join("tsconfig.json")
+                } else {
+                    path
+                }
+            })
+            .collect()
+    }
+
+    /// Expands `files`, `include` and `exclude` into the list of root files.
+    ///
+    /// See https://www.typescriptlang.org/tsconfig#include
+    pub fn root_files(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut files = vec![];
+
+        if let Some(explicit) = &self.raw.files {
+            for file in explicit {
+                let path = Path::new(file)
+                    .canonicalize()
+                    .with_context(|| format!("file `{}` specified in `{}` does not exist", file, self.path.display()))?;
+                files.push(path);
+            }
+        }
+
+        // `include` defaults to `**/*` only if `files` is not specified.
+        let include = match (&self.raw.include, &self.raw.files) {
+            (Some(include), _) => include.clone(),
+            (None, Some(_)) => vec![],
+            (None, None) => vec![self.dir.join("**/*").to_string_lossy().into_owned()],
+        };
+
+        if include.is_empty() {
+            return Ok(files);
+        }
+
+        let exclude = match &self.raw.exclude {
+            Some(exclude) => exclude.clone(),
+            None => {
+                let mut exclude = DEFAULT_EXCLUDE
+                    .iter()
+                    .map(|s| self.dir.join(s).to_string_lossy().into_owned())
+                    .collect::<Vec<_>>();
+                exclude.extend(self.raw.compiler_options.out_dir.clone());
+                exclude
+            }
+        };
+        let exclude = build_glob_set(&exclude)?;
+
+        let mut roots = include.iter().map(|pattern| literal_prefix(pattern)).collect::<Vec<_>>();
+        roots.sort();
+        roots.dedup();
+
+        let include = build_glob_set(&include)?;
+        let allow_js = self.allow_js();
+        let mut seen = files.iter().cloned().collect::<HashSet<_>>();
+
+        for root in roots {
+            let walker = WalkDir::new(&root)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_entry(|entry| !exclude.is_match(entry.path()));
+
+            for entry in walker {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    // A pattern may refer to a directory which does not exist.
+                    Err(..) => continue,
+                };
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+
+                let path = entry.path();
+                let name = path.to_string_lossy();
+                let is_js = JS_EXTENSIONS.iter().any(|ext| name.ends_with(ext));
+                if !SUPPORTED_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) && !(allow_js && is_js) {
+                    continue;
+                }
+
+                if include.is_match(path) && seen.insert(path.to_path_buf()) {
+                    files.push(path.to_path_buf());
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+/// Returns the longest prefix of `pattern` without a wildcard.
+fn literal_prefix(pattern: &str) -> PathBuf {
+    let mut buf = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        let s = component.as_os_str().to_string_lossy();
+        if s.contains('*') || s.contains('?') {
+            break;
+        }
+        buf.push(component);
+    }
+    buf
+}
+
+/// Loads a `tsconfig.json` as a [Value], applying `extends`.
+///
+/// Also returns the directory of the file which declares the effective
+/// `compilerOptions.paths`, and paths to the extended files.
+///
+/// `stack` is used to detect cycles.
+fn load_with_extends(path: &Path, stack: &mut Vec<PathBuf>) -> Result<(Value, Option<PathBuf>, Vec<PathBuf>), Error> {
+    if stack.iter().any(|p| p == path) {
+        bail!("circularity detected while resolving `extends` of `{}`", path.display());
+    }
+
+    let mut value = parse_tsconfig(path)?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+    resolve_relative_paths(&mut value, dir);
+
+    let declares_paths = value.get("compilerOptions").and_then(|opts| opts.get("paths")).is_some();
+    let own_paths_base = declares_paths.then(|| dir.to_path_buf());
+
+    let extends = match value.as_object_mut().and_then(|obj| obj.remove("extends")) {
+        Some(Value::String(s)) => vec![s],
+        Some(Value::Array(arr)) => arr.into_iter().filter_map(|v| v.as_str().map(String::from)).collect(),
+        _ => vec![],
+    };
+
+    if extends.is_empty() {
+        return Ok((value, own_paths_base, vec![]));
+    }
+
+    stack.push(path.to_path_buf());
+
+    let mut base = Value::Object(Default::default());
+    let mut paths_base = None;
+    let mut chain = vec![];
+    for specifier in extends {
+        let base_path = resolve_extends(dir, &specifier)
+            .with_context(|| format!("failed to resolve `{}` extended by `{}`", specifier, path.display()))?;
+        let (mut loaded, loaded_paths_base, loaded_chain) = load_with_extends(&base_path, stack)?;
+        chain.push(base_path);
+        chain.extend(loaded_chain);
+        // `references` is not inherited.
+        if let Some(obj) = loaded.as_object_mut() {
+            obj.remove("references");
+        }
+
+        // Later bases override `paths` of earlier ones.
+        paths_base = loaded_paths_base.or(paths_base);
+        merge_config(&mut base, loaded);
+    }
+
+    stack.pop();
+
+    merge_config(&mut base, value);
+
+    Ok((base, own_paths_base.or(paths_base), chain))
+}
+
+/// Resolves the value of `extends`, which is a relative path or a package
+/// specifier.
+fn resolve_extends(dir: &Path, specifier: &str) -> Result<PathBuf, Error> {
+    let with_json = |path: PathBuf| -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path);
+        }
+        if path.is_dir() {
+            let path = path.join("tsconfig.json");
+            return path.is_file().then_some(path);
+        }
+        let path = PathBuf::from(format!("{}.json", path.display()));
+        path.is_file().then_some(path)
+    };
+
+    let path = Path::new(specifier);
+    if path.is_absolute() || specifier.starts_with("./") || specifier.starts_with("../") {
+        return with_json(normalize(&dir.join(path))).context("file not found");
+    }
+
+    for dir in dir.ancestors() {
+        let node_modules = dir.join("node_modules");
+        if !node_modules.is_dir() {
+            continue;
+        }
+
+        if let Some(path) = with_json(node_modules.join(specifier)) {
+            return Ok(path);
+        }
+    }
+
+    bail!("package not found")
+}
+
+/// Makes relative paths of `value` absolute, so they are relative to the file
+/// declaring them even after merging.
+fn resolve_relative_paths(value: &mut Value, dir: &Path) {
+    let resolve = |v: &mut Value| {
+        if let Value::String(s) = v {
+            *s = normalize(&dir.join(&*s)).to_string_lossy().into_owned();
+        }
+    };
+
+    let obj = match value.as_object_mut() {
+        Some(obj) => obj,
+        None => return,
+    };
+
+    for key in ["files", "include", "exclude"] {
+        if let Some(Value::Array(arr)) = obj.get_mut(key) {
+            arr.iter_mut().for_each(resolve);
+        }
+    }
+
+    if let Some(Value::Array(arr)) = obj.get_mut("references") {
+        for r in arr {
+            if let Some(path) = r.get_mut("path") {
+                resolve(path);
+            }
+        }
+    }
+
+    if let Some(Value::Object(opts)) = obj.get_mut("compilerOptions") {
+        for key in PATH_OPTIONS {
+            match opts.get_mut(*key) {
+                Some(Value::Array(arr)) => arr.iter_mut().for_each(resolve),
+                Some(v) => resolve(v),
+                None => {}
+            }
+        }
+    }
+}
+
+/// Merges `child` into `base`. `compilerOptions` are merged key by key, and
+/// other top-level keys are overwritten.
+fn merge_config(base: &mut Value, child: Value) {
+    let (base, child) = match (base.as_object_mut(), child) {
+        (Some(base), Value::Object(child)) => (base, child),
+        (_, child) => {
+            *base = child;
+            return;
+        }
+    };
+
+    for (key, value) in child {
+        match (key.as_str(), base.get_mut(&key), value) {
+            ("compilerOptions", Some(Value::Object(base_opts)), Value::Object(opts)) => {
+                base_opts.extend(opts);
+            }
+            (_, _, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Removes `.` and `..` from `path` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut buf = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                buf.pop();
+            }
+            _ => buf.push(component),
+        }
+    }
+    buf
+}
+
+/// Parses a `tsconfig.json`, which is JSON with comments and trailing commas.
+fn parse_tsconfig(path: &Path) -> Result<Value, Error> {
+    let content = read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    let content = strip_json_comments(&content);
+
+    serde_json::from_str(&content).with_context(|| format!("failed to parse `{}`", path.display()))
+}
+
+/// Removes comments and trailing commas so `serde_json` can parse the input.
+pub(crate) fn strip_json_comments(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    let mut in_str = false;
+
+    while let Some(c) = chars.next() {
+        if in_str {
+            buf.push(c);
+            match c {
+                '\\' => buf.extend(chars.next()),
+                '"' => in_str = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_str = true;
+                buf.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        buf.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            ']' | '}' => {
+                // Remove trailing comma.
+                let trimmed = buf.trim_end().len();
+                if buf[..trimmed].ends_with(',') {
+                    buf.truncate(trimmed - 1);
+                }
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+/// Converts patterns of `include` and `exclude` to a [GlobSet].
+///
+/// A pattern without a wildcard in the last segment and without an
+/// extension is treated as a directory.
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let last = pattern.rsplit('/').next().unwrap_or(pattern);
+
+        builder.add(glob(pattern)?);
+        if !last.contains('*') && !last.contains('?') && !last.contains('.') {
+            builder.add(glob(&format!("{}/**/*", pattern))?);
+        }
+    }
+
+    builder.build().context("failed to build glob set")
+}
+
+/// `*` of tsconfig does not match path separators.
+fn glob(pattern: &str) -> Result<globset::Glob, Error> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .with_context(|| format!("invalid pattern `{}`", pattern))
+}
+
+fn parse_target(s: &str) -> Option<EsVersion> {
+    Some(match &*s.to_lowercase() {
+        "es3" => EsVersion::Es3,
+        "es5" => EsVersion::Es5,
+        "es6" | "es2015" => EsVersion::Es2015,
+        "es2016" => EsVersion::Es2016,
+        "es2017" => EsVersion::Es2017,
+        "es2018" => EsVersion::Es2018,
+        "es2019" => EsVersion::Es2019,
+        "es2020" => EsVersion::Es2020,
+        "es2021" => EsVersion::Es2021,
+        "es2022" => EsVersion::Es2022,
+        "esnext" => EsVersion::EsNext,
+        _ => return None,
+    })
+}
+
+/// Loads a builtin library by the name used in the `lib` option.
+fn load_lib(name: &str) -> Vec<Lib> {
+    let name = name.to_lowercase().replace("es6", "es2015");
+
+    let libs = Lib::load(&name);
+    if !libs.is_empty() {
+        return libs;
+    }
+
+    // `dom` is stored as `dom.generated`.
+    Lib::load(&format!("{}.generated", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/config")
+            .join(name)
+            .canonicalize()
+            .unwrap()
+    }
+
+    /// Root files of the project in `dir`, relative to `dir`.
+    fn root_files(dir: &Path) -> Vec<String> {
+        let config = ProjectConfig::load(dir).unwrap();
+
+        config
+            .root_files()
+            .unwrap()
+            .iter()
+            .map(|path| path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn extends_chain() {
+        let dir = fixture("extends");
+        let config = ProjectConfig::load(&dir).unwrap();
+
+        assert_eq!(config.target(), EsVersion::Es2020);
+        assert!(matches!(config.module(), ModuleConfig::CommonJs));
+
+        let rule = config.rule();
+        assert!(rule.strict_null_checks);
+        assert!(!rule.no_implicit_any);
+
+        assert_eq!(config.paths_base, Some(dir.join("configs")));
+        assert_eq!(config.extends, vec![dir.join("configs/base.json"), dir.join("configs/strict.json")]);
+        // `files` of a base config is relative to the base config.
+        assert_eq!(root_files(&dir), vec!["src/a.ts"]);
+    }
+
+    #[test]
+    fn extends_package() {
+        let dir = fixture("extends_package");
+        let base = dir.join("node_modules/@tsconfig/node18/tsconfig.json");
+
+        assert_eq!(resolve_extends(&dir.join("app"), "@tsconfig/node18").unwrap(), base);
+        assert_eq!(resolve_extends(&dir.join("app"), "@tsconfig/node18/tsconfig").unwrap(), base);
+        assert!(resolve_extends(&dir.join("app"), "@tsconfig/node20").is_err());
+
+        let config = ProjectConfig::load(&dir.join("app")).unwrap();
+        assert_eq!(config.target(), EsVersion::Es2022);
+        assert!(config.rule().strict_null_checks);
+        assert_eq!(config.extends, vec![base]);
+    }
+
+    #[test]
+    fn extends_cycle() {
+        let err = ProjectConfig::load(&fixture("extends_cycle")).unwrap_err();
+
+        assert!(format!("{:?}", err).contains("circularity"), "{:?}", err);
+    }
+
+    #[test]
+    fn include_and_exclude() {
+        assert_eq!(root_files(&fixture("include")), vec!["src/a.ts", "src/nested/b.d.ts"]);
+    }
+
+    #[test]
+    fn default_include_skips_node_modules() {
+        assert_eq!(root_files(&fixture("default_include")), vec!["a.ts"]);
+    }
+
+    #[test]
+    fn files_keep_order_and_disable_default_include() {
+        assert_eq!(root_files(&fixture("files")), vec!["b.ts", "a.ts"]);
+    }
+}
@@ -0,0 +1,500 @@
+//! Machine-readable output formats for diagnostics.
+
+use std::{
+    collections::HashMap,
+    env::current_dir,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+use stc_ts_errors::{Error, ErrorKind};
+use swc_common::{BytePos, FileName, SourceMap, Spanned};
+use url::Url;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Rich diagnostics with code frames.
+    Human,
+    /// One JSON object per line.
+    Json,
+    /// SARIF 2.1.0 log.
+    Sarif,
+    /// Same as `tsc --pretty false`.
+    Tsc,
+    /// Same as `tsc --pretty`, including the summary.
+    Pretty,
+}
+
+/// A diagnostic, detached from [SourceMap].
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    /// `None` for diagnostics not associated with a file.
+    pub file: Option<String>,
+    pub start: Position,
+    pub end: Position,
+    /// Like `TS2322`.
+    pub code: String,
+    pub category: &'static str,
+    pub message: String,
+    /// Source lines from `start` to `end`, used for code frames.
+    #[serde(skip)]
+    pub lines: Vec<String>,
+}
+
+/// 1-based line and column. Columns are counted in UTF-16 code units, like
+/// `tsc`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    pub fn new(cm: &SourceMap, err: &Error) -> Self {
+        let span = err.span();
+        let code = format!("TS{}", ErrorKind::normalize_error_code(err.code()));
+        let message = err.message_chain();
+
+        if span.is_dummy() {
+            return Diagnostic {
+                file: None,
+                start: Position { line: 1, column: 1 },
+                end: Position { line: 1, column: 1 },
+                code,
+                category: "error",
+                message,
+                lines: vec![],
+            };
+        }
+
+        let (file, start) = position(cm, span.lo);
+        let (_, end) = position(cm, span.hi);
+
+        let fm = cm.lookup_byte_offset(span.lo).sf;
+        let lines = (start.line - 1..end.line)
+            .map(|line| fm.get_line(line).unwrap_or_default().into_owned())
+            .collect();
+
+        Diagnostic {
+            file,
+            start,
+            end,
+            code,
+            category: "error",
+            message,
+            lines,
+        }
+    }
+}
+
+fn position(cm: &SourceMap, pos: BytePos) -> (Option<String>, Position) {
+    let loc = cm.lookup_char_pos(pos);
+
+    let file = match &loc.file.name {
+        FileName::Real(path) => Some(display_path(path)),
+        name => Some(name.to_string()),
+    };
+    let column = match loc.file.get_line(loc.line - 1) {
+        Some(line) => line.chars().take(loc.col.0).map(char::len_utf16).sum(),
+        None => loc.col.0,
+    };
+
+    (
+        file,
+        Position {
+            line: loc.line,
+            column: column + 1,
+        },
+    )
+}
+
+/// Relative to the current directory, with `/` as the separator. Like `tsc`,
+/// files outside of the current directory use `..`.
+pub(crate) fn display_path(path: &Path) -> String {
+    let path = match current_dir() {
+        Ok(cwd) => relative_path(&cwd, path),
+        Err(_) => path.to_path_buf(),
+    };
+
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let common = base.components().zip(path.components()).take_while(|(a, b)| a == b).count();
+    // Paths on different drives.
+    if common == 0 {
+        return path.to_path_buf();
+    }
+
+    let mut buf = PathBuf::new();
+    for _ in base.components().skip(common) {
+        buf.push("..");
+    }
+    buf.extend(path.components().skip(common));
+    buf
+}
+
+/// Writes `diagnostics` in `format`.
+///
+/// [Format::Human] is handled by [swc_common::errors::Handler] and writes
+/// nothing here.
+pub fn write(format: Format, diagnostics: &[Diagnostic], w: &mut dyn Write) -> io::Result<()> {
+    match format {
+        Format::Human => Ok(()),
+        Format::Json => write_json(diagnostics, w),
+        Format::Sarif => write_sarif(diagnostics, w),
+        Format::Tsc => write_tsc(diagnostics, w),
+        Format::Pretty => write_pretty(diagnostics, w),
+    }
+}
+
+fn write_json(diagnostics: &[Diagnostic], w: &mut dyn Write) -> io::Result<()> {
+    for d in diagnostics {
+        serde_json::to_writer(&mut *w, d)?;
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
+/// `path(line,col): error TS2322: message`, with the elaboration on the
+/// following lines.
+fn write_tsc(diagnostics: &[Diagnostic], w: &mut dyn Write) -> io::Result<()> {
+    for d in diagnostics {
+        if let Some(file) = &d.file {
+            write!(w, "{}({},{}): ", file, d.start.line, d.start.column)?;
+        }
+
+        writeln!(w, "{} {}: {}", d.category, d.code, d.message)?;
+    }
+
+    Ok(())
+}
+
+// Escape sequences used by `tsc --pretty`.
+const GUTTER: &str = "\u{1b}[7m";
+const RESET: &str = "\u{1b}[0m";
+const RED: &str = "\u{1b}[91m";
+const YELLOW: &str = "\u{1b}[93m";
+const CYAN: &str = "\u{1b}[96m";
+const GREY: &str = "\u{1b}[90m";
+
+fn color(text: &str, color: &str) -> String {
+    format!("{}{}{}", color, text, RESET)
+}
+
+/// `path:line:col - error TS2322: message`, followed by a code frame, and a
+/// summary of all errors.
+fn write_pretty(diagnostics: &[Diagnostic], w: &mut dyn Write) -> io::Result<()> {
+    for d in diagnostics {
+        if let Some(file) = &d.file {
+            write!(
+                w,
+                "{}:{}:{} - ",
+                color(file, CYAN),
+                color(&d.start.line.to_string(), YELLOW),
+                color(&d.start.column.to_string(), YELLOW)
+            )?;
+        }
+
+        write!(
+            w,
+            "{}{}{}",
+            color(d.category, RED),
+            color(&format!(" {}: ", d.code), GREY),
+            d.message
+        )?;
+
+        if d.file.is_some() {
+            writeln!(w)?;
+            write_code_frame(d, w)?;
+        }
+
+        writeln!(w)?;
+        writeln!(w)?;
+    }
+
+    write_summary(diagnostics, w)
+}
+
+/// Prints the lines of `d` with tildes under the span. Like `tsc`, only the
+/// first two and last two lines are printed for spans of 5 or more lines.
+fn write_code_frame(d: &Diagnostic, w: &mut dyn Write) -> io::Result<()> {
+    let first_line = d.start.line - 1;
+    let last_line = d.end.line - 1;
+    let has_more_than_five_lines = last_line - first_line >= 4;
+
+    let mut gutter_width = d.end.line.to_string().len();
+    if has_more_than_five_lines {
+        gutter_width = gutter_width.max("...".len());
+    }
+
+    let mut i = first_line;
+    while i <= last_line {
+        writeln!(w)?;
+        if has_more_than_five_lines && first_line + 1 < i && i < last_line - 1 {
+            writeln!(w, "{} ", color(&format!("{:>1$}", "...", gutter_width), GUTTER))?;
+            i = last_line - 1;
+        }
+
+        // Columns are in UTF-16 code units.
+        let line = d.lines[i - first_line].trim_end().replace('\t', " ");
+        let units = line.encode_utf16().collect::<Vec<_>>();
+
+        writeln!(w, "{} {}", color(&format!("{:>1$}", i + 1, gutter_width), GUTTER), line)?;
+        write!(w, "{} {}", color(&" ".repeat(gutter_width), GUTTER), RED)?;
+
+        let start = if i == first_line {
+            (d.start.column - 1).min(units.len())
+        } else {
+            0
+        };
+        let end = if i == last_line {
+            (d.end.column - 1).clamp(start, units.len())
+        } else {
+            units.len()
+        };
+
+        for &unit in &units[..start] {
+            let c = char::from_u32(unit as u32)
+                .filter(|c| c.is_whitespace() || *c == '\u{feff}')
+                .unwrap_or(' ');
+            write!(w, "{}", c)?;
+        }
+        write!(w, "{}{}", "~".repeat(end - start), RESET)?;
+
+        i += 1;
+    }
+
+    Ok(())
+}
+
+/// `Found 2 errors in 2 files.`, followed by the number of errors per file.
+fn write_summary(diagnostics: &[Diagnostic], w: &mut dyn Write) -> io::Result<()> {
+    let errors = diagnostics.iter().filter(|d| d.category == "error").collect::<Vec<_>>();
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    // Like `tsc`, a file is reported with the line of its first error.
+    let first_line = |file: &str| {
+        diagnostics
+            .iter()
+            .find(|d| d.file.as_deref() == Some(file))
+            .map_or(1, |d| d.start.line)
+    };
+    let files = errors
+        .iter()
+        .filter_map(|d| d.file.as_deref())
+        .map(|file| (file, first_line(file)))
+        .collect::<Vec<_>>();
+    let mut distinct_files = vec![];
+    for &file in &files {
+        if !distinct_files.contains(&file) {
+            distinct_files.push(file);
+        }
+    }
+
+    let file_ref = |(file, line): (&str, usize)| format!("{}{}", file, color(&format!(":{}", line), GREY));
+
+    let message = match (errors.len(), &*distinct_files) {
+        (1, [file]) => format!("Found 1 error in {}", file_ref(*file)),
+        (1, _) => "Found 1 error.".into(),
+        (count, []) => format!("Found {} errors.", count),
+        (count, [file]) => format!("Found {} errors in the same file, starting at: {}", count, file_ref(*file)),
+        (count, files) => format!("Found {} errors in {} files.", count, files.len()),
+    };
+    writeln!(w)?;
+    writeln!(w, "{}", message)?;
+    writeln!(w)?;
+
+    if distinct_files.len() > 1 {
+        // `tsc` computes the number of digits with logarithms.
+        let number_length = |n: usize| (n as f64).ln() * std::f64::consts::LOG10_E + 1.0;
+        let counts = distinct_files
+            .iter()
+            .map(|&(file, line)| ((file, line), files.iter().filter(|f| f.0 == file).count()))
+            .collect::<Vec<_>>();
+        let max_errors = counts.iter().map(|&(_, count)| count).max().unwrap_or(0);
+
+        let header = "Errors  Files";
+        let heading_len = "Errors".len() as f64;
+        let left_padding_goal = heading_len.max(number_length(max_errors));
+        let header_padding = (number_length(max_errors) - heading_len).max(0.0);
+
+        writeln!(w, "{}{}", " ".repeat(header_padding as usize), header)?;
+        for (file, count) in counts {
+            let digits = number_length(count) as usize as f64;
+            let left_padding = if digits < left_padding_goal {
+                " ".repeat((left_padding_goal - digits) as usize)
+            } else {
+                String::new()
+            };
+
+            writeln!(w, "{}{}  {}", left_padding, count, file_ref(file))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// See https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+fn write_sarif(diagnostics: &[Diagnostic], w: &mut dyn Write) -> io::Result<()> {
+    let mut rules = vec![];
+    let mut rule_indices = HashMap::new();
+
+    let results = diagnostics
+        .iter()
+        .map(|d| {
+            let rule_index = *rule_indices.entry(d.code.clone()).or_insert_with(|| {
+                rules.push(json!({ "id": d.code }));
+                rules.len() - 1
+            });
+
+            let locations = match &d.file {
+                Some(file) => vec![json!({
+                    "physicalLocation": {
+                        "artifactLocation": artifact_location(file),
+                        "region": {
+                            "startLine": d.start.line,
+                            "startColumn": d.start.column,
+                            "endLine": d.end.line,
+                            "endColumn": d.end.column,
+                        },
+                    },
+                })],
+                None => vec![],
+            };
+
+            json!({
+                "ruleId": d.code,
+                "ruleIndex": rule_index,
+                "level": d.category,
+                "message": {
+                    "text": d.message,
+                },
+                "locations": locations,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut original_uri_base_ids = json!({});
+    if let Some(root) = current_dir().ok().and_then(|cwd| Url::from_directory_path(cwd).ok()) {
+        original_uri_base_ids = json!({
+            "%SRCROOT%": {
+                "uri": root.as_str(),
+            },
+        });
+    }
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "stc",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": original_uri_base_ids,
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+
+    serde_json::to_writer_pretty(&mut *w, &log)?;
+    writeln!(w)
+}
+
+/// Files outside of the current directory can't be relative to `%SRCROOT%`.
+fn artifact_location(file: &str) -> serde_json::Value {
+    if let Ok(uri) = Url::from_file_path(file) {
+        json!({
+            "uri": uri.as_str(),
+        })
+    } else {
+        json!({
+            "uri": file,
+            "uriBaseId": "%SRCROOT%",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_common::{Span, SyntaxContext, DUMMY_SP};
+
+    use super::*;
+
+    const A: &str = "function f() {\n    return;\n\tf();\n}\nconst s = \"😀\"; s;\n";
+    const B: &str = "function g() {\n    1;\n    2;\n    3;\n    4;\n    5;\n}\n";
+
+    fn diagnostics() -> Vec<Diagnostic> {
+        let cm = SourceMap::default();
+        let cwd = current_dir().unwrap();
+        let a = cm.new_source_file(FileName::Real(cwd.join("src/a.ts")), A.into());
+        let b = cm.new_source_file(FileName::Real(cwd.join("src/b.ts")), B.into());
+
+        let span = |start: BytePos, lo: u32, hi: u32| Span::new(start + BytePos(lo), start + BytePos(hi), SyntaxContext::empty());
+
+        let errors: Vec<Error> = vec![
+            ErrorKind::UnreachableCode {
+                span: span(a.start_pos, 28, 32),
+            }
+            .into(),
+            ErrorKind::TS2447 {
+                span: span(a.start_pos, 53, 54),
+            }
+            .into(),
+            ErrorKind::DuplicateFnImpl {
+                span: span(b.start_pos, 0, 51),
+            }
+            .into(),
+            ErrorKind::OptionInvalidForEs3 { span: DUMMY_SP }.into(),
+        ];
+
+        errors.iter().map(|err| Diagnostic::new(&cm, err)).collect()
+    }
+
+    fn output(format: Format) -> String {
+        let mut buf = vec![];
+        write(format, &diagnostics(), &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn tsc() {
+        assert_eq!(output(Format::Tsc), include_str!("../tests/format/tsc.txt"));
+    }
+
+    #[test]
+    fn pretty() {
+        assert_eq!(output(Format::Pretty), include_str!("../tests/format/pretty.txt"));
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(output(Format::Json), include_str!("../tests/format/json.txt"));
+    }
+
+    #[test]
+    fn sarif() {
+        let root = Url::from_directory_path(current_dir().unwrap()).unwrap();
+
+        assert_eq!(
+            output(Format::Sarif).replace(root.as_str(), "file:///cwd/"),
+            include_str!("../tests/format/sarif.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn absolute_artifact_location() {
+        assert_eq!(artifact_location("/a b/c.ts"), json!({ "uri": "file:///a%20b/c.ts" }));
+    }
+}
@@ -1,13 +1,17 @@
 extern crate swc_node_base;
 
-use std::{path::PathBuf, sync::Arc, time::Instant};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 
 use anyhow::Error;
 use clap::Parser;
 use stc_ts_builtin_types::Lib;
 use stc_ts_env::{Env, ModuleConfig, Rule};
 use stc_ts_file_analyzer::env::EnvFactory;
-use stc_ts_lang_server::LspCommand;
+use stc_ts_lang_server::{DefaultWorkspaceLoader, LoadWorkspace, LspCommand, Workspace};
 use stc_ts_module_loader::resolvers::node::NodeResolver;
 use stc_ts_type_checker::{
     loader::{DefaultFileLoader, ModuleLoader},
@@ -20,13 +24,21 @@ use swc_common::{
 use swc_ecma_ast::EsVersion;
 use tracing_subscriber::EnvFilter;
 
-use crate::check::TestCommand;
+use crate::{
+    check::{CheckCommand, TestCommand},
+    config::ProjectConfig,
+};
 
 mod check;
+mod config;
+mod format;
+mod project;
 
 #[derive(Debug, Parser)]
 #[command(name = "stc", about = "Super fast type checker for typescript", author, rename_all = "camel")]
 enum Command {
+    Check(CheckCommand),
+    #[command(hide = true)]
     Test(TestCommand),
     Lsp(LspCommand),
 }
@@ -63,7 +75,14 @@ async fn main() -> Result<(), Error> {
         log::info!("Initialization took {:?}", end - start);
     }
 
+    let mut has_error = false;
+
     match command {
+        Command::Check(cmd) => {
+            let error_count = cmd.run(cm, handler)?;
+
+            has_error = error_count > 0;
+        }
         Command::Test(cmd) => {
             let libs = {
                 let start = Instant::now();
@@ -94,7 +113,7 @@ async fn main() -> Result<(), Error> {
                     handler.clone(),
                     env.clone(),
                     None,
-                    ModuleLoader::new(cm.clone(), env.clone(), NodeResolver, DefaultFileLoader),
+                    ModuleLoader::new(cm.clone(), env.clone(), NodeResolver::new(), DefaultFileLoader),
                 );
 
                 checker.load_typings(&path, None, cmd.types.as_deref());
@@ -113,7 +132,7 @@ async fn main() -> Result<(), Error> {
                     handler.clone(),
                     env.clone(),
                     None,
-                    ModuleLoader::new(cm, env, NodeResolver, DefaultFileLoader),
+                    ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader),
                 );
 
                 checker.check(Arc::new(FileName::Real(path)));
@@ -138,7 +157,7 @@ async fn main() -> Result<(), Error> {
             }
         }
         Command::Lsp(cmd) => {
-            cmd.run().await?;
+            cmd.run_with(TsConfigLoader).await?;
         }
     }
 
@@ -146,5 +165,30 @@ async fn main() -> Result<(), Error> {
 
     log::info!("Done in {:?}", end - start);
 
+    if has_error {
+        std::process::exit(1);
+    }
+
     Ok(())
 }
+
+/// Loads `tsconfig.json` of the workspace opened in the editor.
+struct TsConfigLoader;
+
+impl LoadWorkspace for TsConfigLoader {
+    fn load(&self, root: &Path) -> Result<Workspace, Error> {
+        let path = root.join("tsconfig.json");
+        if !path.is_file() {
+            return DefaultWorkspaceLoader.load(root);
+        }
+
+        let config = ProjectConfig::load(&path)?;
+
+        Ok(Workspace {
+            env: Env::simple(config.rule(), config.target(), config.module(), &config.libs()),
+            dir: Some(config.dir.clone()),
+            type_roots: config.type_roots(),
+            types: config.types().map(<[String]>::to_vec),
+        })
+    }
+}
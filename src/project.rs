@@ -0,0 +1,192 @@
+//! Project references.
+//!
+//! See https://www.typescriptlang.org/docs/handbook/project-references.html
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use anyhow::{bail, Context, Error};
+use stc_ts_type_checker::loader::{DefaultFileLoader, LoadFile};
+use swc_common::{FileName, SourceFile, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use swc_ecma_parser::{Syntax, TsConfig};
+
+use crate::config::ProjectConfig;
+
+/// Projects reachable from a root project via `references`.
+pub struct ProjectGraph {
+    /// Sorted so that a project comes after all projects it references. The
+    /// root project is the last one.
+    pub projects: Vec<ProjectConfig>,
+}
+
+impl ProjectGraph {
+    pub fn load(root: ProjectConfig) -> Result<Self, Error> {
+        let mut projects = vec![];
+        let mut stack = vec![];
+
+        visit(root, &mut stack, &mut projects)?;
+
+        Ok(ProjectGraph { projects })
+    }
+}
+
+/// Post-order traversal of references.
+fn visit(project: ProjectConfig, stack: &mut Vec<PathBuf>, done: &mut Vec<ProjectConfig>) -> Result<(), Error> {
+    if done.iter().any(|p| p.path == project.path) {
+        return Ok(());
+    }
+    if stack.contains(&project.path) {
+        bail!("project references form a cycle at `{}`", project.path.display());
+    }
+
+    stack.push(project.path.clone());
+
+    for path in project.references() {
+        let referenced = ProjectConfig::load(&path)
+            .with_context(|| format!("failed to load project `{}` referenced by `{}`", path.display(), project.path.display()))?;
+
+        if !referenced.raw.compiler_options.composite.unwrap_or(false) {
+            log::warn!(
+                "Referenced project `{}` should have `\"composite\": true`",
+                referenced.path.display()
+            );
+        }
+
+        visit(referenced, stack, done)?;
+    }
+
+    stack.pop();
+    done.push(project);
+
+    Ok(())
+}
+
+/// Declarations emitted for source files of referenced projects, keyed by the
+/// path of the source file.
+#[derive(Clone, Default)]
+pub struct ReferencedDts(Arc<RwLock<HashMap<PathBuf, Arc<String>>>>);
+
+impl ReferencedDts {
+    pub fn insert(&self, cm: &Arc<SourceMap>, path: PathBuf, dts: &Module) {
+        let code = print_module(cm, dts);
+
+        self.0.write().unwrap().insert(path, Arc::new(code));
+    }
+
+    fn get(&self, path: &Path) -> Option<Arc<String>> {
+        self.0.read().unwrap().get(path).cloned()
+    }
+}
+
+/// Serves `.d.ts` of referenced projects instead of their sources, so a
+/// referenced project is not analyzed again.
+pub struct ProjectFileLoader {
+    pub dts: ReferencedDts,
+}
+
+impl LoadFile for ProjectFileLoader {
+    fn load_file(&self, cm: &Arc<SourceMap>, filename: &Arc<FileName>) -> Result<(Arc<SourceFile>, Syntax), Error> {
+        if let FileName::Real(path) = &**filename {
+            if let Some(code) = self.dts.get(path) {
+                // Spans of the declarations should not point to the source file.
+                let fm = cm.new_source_file(FileName::Real(dts_path(path)), (*code).clone());
+
+                return Ok((
+                    fm,
+                    Syntax::Typescript(TsConfig {
+                        dts: true,
+                        ..Default::default()
+                    }),
+                ));
+            }
+        }
+
+        DefaultFileLoader.load_file(cm, filename)
+    }
+}
+
+/// `a.ts` => `a.d.ts`, `a.mts` => `a.d.mts`.
+fn dts_path(path: &Path) -> PathBuf {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("mts") => path.with_extension("d.mts"),
+        Some("cts") => path.with_extension("d.cts"),
+        _ => path.with_extension("d.ts"),
+    }
+}
+
+fn print_module(cm: &Arc<SourceMap>, module: &Module) -> String {
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None)),
+        };
+
+        emitter.emit_module(module).expect("failed to emit .d.ts");
+    }
+
+    String::from_utf8(buf).expect("codegen should emit valid utf8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(name: &str) -> (PathBuf, Result<ProjectGraph, Error>) {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/config")
+            .join(name)
+            .canonicalize()
+            .unwrap();
+        let graph = ProjectGraph::load(ProjectConfig::load(&dir).unwrap());
+
+        (dir, graph)
+    }
+
+    #[test]
+    fn references_come_first() {
+        let (dir, graph) = load("references");
+
+        let dirs = graph
+            .unwrap()
+            .projects
+            .iter()
+            .map(|p| p.dir.strip_prefix(&dir).unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        // `shared` is referenced by both `app` and `tools`, but checked once.
+        assert_eq!(dirs, vec!["shared", "app", "tools", ""]);
+    }
+
+    #[test]
+    fn reference_cycle() {
+        let (_, graph) = load("references_cycle");
+
+        let err = graph.err().expect("cycle should be an error");
+        assert!(format!("{:?}", err).contains("cycle"), "{:?}", err);
+    }
+
+    #[test]
+    fn referenced_dts_is_named_dts() {
+        let dts = ReferencedDts::default();
+        dts.0
+            .write()
+            .unwrap()
+            .insert(PathBuf::from("/p/a.mts"), Arc::new("export declare const a: number;\n".into()));
+
+        let cm = Arc::new(SourceMap::default());
+        let (fm, syntax) = ProjectFileLoader { dts }
+            .load_file(&cm, &Arc::new(FileName::Real("/p/a.mts".into())))
+            .unwrap();
+
+        assert_eq!(fm.name, FileName::Real("/p/a.d.mts".into()));
+        assert!(syntax.dts());
+    }
+}
@@ -0,0 +1 @@
+export const test = 1;
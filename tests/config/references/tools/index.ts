@@ -0,0 +1 @@
+export const tools = 1;